@@ -0,0 +1,91 @@
+use crate::math::Vector;
+use crate::neuralnetwork::NeuralNetwork;
+
+/// An autoencoder built on a `NeuralNetwork` whose layers form an encoder
+/// followed by a mirrored decoder. The output of layer `bottleneck_layer`
+/// is the code. Since every layer is sigmoidal, inputs should be scaled to
+/// [0, 1].
+pub struct Autoencoder {
+    pub network: NeuralNetwork,
+    pub bottleneck_layer: usize, // Index of the layer whose output is the code
+}
+
+impl Autoencoder {
+    /// Creates an autoencoder with layer sizes
+    /// `input_size -> hidden... -> bottleneck -> reversed hidden... -> input_size`.
+    pub fn new(input_size: usize, hidden: &[usize], bottleneck: usize) -> Self {
+        let mut sizes = vec![input_size];
+        sizes.extend_from_slice(hidden);
+        sizes.push(bottleneck);
+        sizes.extend(hidden.iter().rev());
+        sizes.push(input_size);
+        Self { network: NeuralNetwork::new(&sizes), bottleneck_layer: hidden.len() }
+    }
+
+    /// Wraps an already configured network (initializers, regularization,
+    /// dropout, ...). Layers `0..=bottleneck_layer` form the encoder.
+    pub fn from_network(network: NeuralNetwork, bottleneck_layer: usize) -> Self {
+        let layers = &network.layers;
+        assert!(bottleneck_layer + 1 < layers.len(), "The decoder needs at least one layer");
+        assert_eq!(
+            layers[0].weights.cols - 1,
+            layers.last().unwrap().weights.row_count(),
+            "Network output size must match its input size"
+        );
+        Self { network, bottleneck_layer }
+    }
+
+    pub fn input_size(&self) -> usize {
+        self.network.layers[0].weights.cols - 1
+    }
+
+    pub fn bottleneck_size(&self) -> usize {
+        self.network.layers[self.bottleneck_layer].weights.row_count()
+    }
+
+    /// Maps an input to its code.
+    pub fn encode(&self, input: &Vector) -> Vector {
+        assert_eq!(input.len(), self.input_size(), "Input has the wrong number of features");
+        self.network.forward_layers(input, 0..self.bottleneck_layer + 1).pop().unwrap()
+    }
+
+    /// Maps a code back to the input space.
+    pub fn decode(&self, code: &Vector) -> Vector {
+        assert_eq!(code.len(), self.bottleneck_size(), "Code has the wrong size");
+        self.network.forward_layers(code, self.bottleneck_layer + 1..self.network.layers.len()).pop().unwrap()
+    }
+
+    /// `decode(encode(input))`
+    pub fn reconstruct(&self, input: &Vector) -> Vector {
+        self.network.predict(input)
+    }
+
+    /// Mean squared reconstruction error of one input, usable as an
+    /// anomaly score: inputs unlike the training data reconstruct poorly.
+    pub fn reconstruction_error(&self, input: &Vector) -> f64 {
+        let output = self.reconstruct(input);
+        input.iter().zip(output.iter()).map(|(x, y)| (x - y).powi(2)).sum::<f64>() / input.len() as f64
+    }
+
+    /// Reconstruction error of every input.
+    pub fn reconstruction_errors(&self, inputs: &[Vector]) -> Vec<f64> {
+        inputs.iter().map(|x| self.reconstruction_error(x)).collect()
+    }
+
+    /// Trains the network to reproduce its inputs with mini-batch gradient
+    /// descent. Returns the mean squared reconstruction error of each epoch.
+    pub fn fit(&mut self, inputs: &[Vector], learning_rate: f64, batch_size: usize, epochs: usize) -> Vec<f64> {
+        assert!(!inputs.is_empty(), "Cannot fit on an empty dataset");
+        let values = (inputs.len() * self.input_size()) as f64;
+        let batches = self.network.batches(inputs.len(), batch_size);
+        let mut history = Vec::with_capacity(epochs);
+        for _ in 0..epochs {
+            let mut total_error = 0.0;
+            for batch in batches.iter().cloned() {
+                total_error += self.network.train_step(&inputs[batch.clone()], &inputs[batch], learning_rate);
+            }
+            history.push(total_error / values);
+        }
+        history
+    }
+}
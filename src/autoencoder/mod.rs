@@ -0,0 +1,6 @@
+#[allow(clippy::module_inception)]
+pub mod autoencoder;
+pub mod vae;
+
+pub use autoencoder::Autoencoder;
+pub use vae::{VAELoss, VAE};
@@ -0,0 +1,253 @@
+use crate::math::{Matrix, Vector};
+use crate::neuralnetwork::{Loss, NeuralNetwork};
+use crate::utils::initializer::Initializer;
+use crate::utils::layer::Layer;
+use crate::utils::random::standard_normal;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// Variational autoencoder with a diagonal Gaussian posterior
+/// `q(z | x) = N(μ(x), diag(exp(log σ²(x))))` and a standard normal prior.
+///
+/// The encoder is a sigmoid `NeuralNetwork` followed by two linear heads
+/// for `μ` and `log σ²`; the decoder is a sigmoid `NeuralNetwork` mapping
+/// `z` back to the input space, so inputs should be scaled to [0, 1].
+/// Training minimizes the negative ELBO, `reconstruction + KL(q(z | x) ‖ N(0, I))`,
+/// averaged over the batch, using the reparameterization `z = μ + σ ⊙ ε`.
+pub struct VAE {
+    pub encoder: NeuralNetwork,
+    pub mean_weights: Matrix,         // Dimensions: latent x (hidden + 1), bias in column 0
+    pub log_variance_weights: Matrix, // Dimensions: latent x (hidden + 1), bias in column 0
+    pub decoder: NeuralNetwork,
+    pub reconstruction: Loss, // MeanSquaredError or BinaryCrossEntropy
+}
+
+/// Negative ELBO of one mini-batch and its two terms, averaged over the batch.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VAELoss {
+    pub reconstruction: f64,
+    pub kl_divergence: f64,
+}
+
+impl VAELoss {
+    pub fn total(&self) -> f64 {
+        self.reconstruction + self.kl_divergence
+    }
+}
+
+impl VAE {
+    /// Creates a VAE with encoder sizes `input_size -> hidden...`, a latent
+    /// space of `latent_size` and a mirrored decoder
+    /// `latent_size -> reversed hidden... -> input_size`.
+    /// The reconstruction term is binary cross-entropy.
+    pub fn new(input_size: usize, hidden: &[usize], latent_size: usize) -> Self {
+        assert!(!hidden.is_empty(), "The encoder needs at least one hidden layer");
+        let mut encoder_sizes = vec![input_size];
+        encoder_sizes.extend_from_slice(hidden);
+        let mut decoder_sizes = vec![latent_size];
+        decoder_sizes.extend(hidden.iter().rev());
+        decoder_sizes.push(input_size);
+
+        let mut rng = rand::rng();
+        let head = |rng: &mut _| {
+            Layer::with_initializer(*hidden.last().unwrap(), latent_size, &Initializer::XavierUniform, &Initializer::Zeros, rng)
+                .weights
+        };
+        Self {
+            encoder: NeuralNetwork::new(&encoder_sizes),
+            mean_weights: head(&mut rng),
+            log_variance_weights: head(&mut rng),
+            decoder: NeuralNetwork::new(&decoder_sizes),
+            reconstruction: Loss::BinaryCrossEntropy,
+        }
+    }
+
+    /// Re-initializes the encoder, heads and decoder from the given weight and
+    /// bias initializers, drawing from an RNG seeded with `seed`.
+    pub fn with_initializer(mut self, weights: Initializer, biases: Initializer, seed: u64) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        for l in 0..self.encoder.layers.len() {
+            self.encoder.initialize_layer(l, &weights, &biases, &mut rng);
+        }
+        let (latent_size, hidden_size) = (self.latent_size(), self.mean_weights.cols - 1);
+        self.mean_weights = Layer::with_initializer(hidden_size, latent_size, &weights, &biases, &mut rng).weights;
+        self.log_variance_weights = Layer::with_initializer(hidden_size, latent_size, &weights, &biases, &mut rng).weights;
+        for l in 0..self.decoder.layers.len() {
+            self.decoder.initialize_layer(l, &weights, &biases, &mut rng);
+        }
+        self
+    }
+
+    /// Uses `loss` for the reconstruction term instead of binary cross-entropy.
+    pub fn with_reconstruction(mut self, loss: Loss) -> Self {
+        assert!(
+            matches!(loss, Loss::MeanSquaredError | Loss::BinaryCrossEntropy),
+            "Reconstruction loss must be MeanSquaredError or BinaryCrossEntropy"
+        );
+        self.reconstruction = loss;
+        self
+    }
+
+    pub fn input_size(&self) -> usize {
+        self.encoder.layers[0].weights.cols - 1
+    }
+
+    pub fn latent_size(&self) -> usize {
+        self.mean_weights.row_count()
+    }
+
+    /// Mean and log-variance of `q(z | input)`.
+    pub fn encode(&self, input: &Vector) -> (Vector, Vector) {
+        assert_eq!(input.len(), self.input_size(), "Input has the wrong number of features");
+        let hidden = Layer::extend_with_bias(&self.encoder.predict(input));
+        (self.mean_weights.gemv(&hidden), self.log_variance_weights.gemv(&hidden))
+    }
+
+    /// Mean of `p(x | z)`.
+    pub fn decode(&self, latent: &Vector) -> Vector {
+        assert_eq!(latent.len(), self.latent_size(), "Latent vector has the wrong size");
+        self.decoder.predict(latent)
+    }
+
+    /// Decodes the posterior mean of `input`.
+    pub fn reconstruct(&self, input: &Vector) -> Vector {
+        self.decode(&self.encode(input).0)
+    }
+
+    /// Mean squared error between `input` and its reconstruction, usable as
+    /// an anomaly score.
+    pub fn reconstruction_error(&self, input: &Vector) -> f64 {
+        let output = self.reconstruct(input);
+        input.iter().zip(output.iter()).map(|(x, y)| (x - y).powi(2)).sum::<f64>() / input.len() as f64
+    }
+
+    /// `KL(q(z | input) ‖ N(0, I)) = -½ Σ (1 + log σ² - μ² - σ²)`
+    pub fn kl_divergence(&self, input: &Vector) -> f64 {
+        let (mean, log_variance) = self.encode(input);
+        Self::kl(&mean, &log_variance)
+    }
+
+    fn kl(mean: &Vector, log_variance: &Vector) -> f64 {
+        -0.5 * mean.iter().zip(log_variance.iter()).map(|(m, lv)| 1.0 + lv - m * m - lv.exp()).sum::<f64>()
+    }
+
+    /// Draws `z = μ + σ ⊙ ε` from `q(z | input)`.
+    pub fn sample_latent<R: Rng + ?Sized>(&self, input: &Vector, rng: &mut R) -> Vector {
+        let (mean, log_variance) = self.encode(input);
+        Vector::new(mean.iter().zip(log_variance.iter()).map(|(m, lv)| m + (0.5 * lv).exp() * standard_normal(rng)).collect())
+    }
+
+    /// Generates `count` new samples by decoding draws from the prior N(0, I).
+    pub fn sample<R: Rng + ?Sized>(&self, count: usize, rng: &mut R) -> Vec<Vector> {
+        (0..count)
+            .map(|_| self.decode(&Vector::new((0..self.latent_size()).map(|_| standard_normal(rng)).collect())))
+            .collect()
+    }
+
+    /// One gradient descent step on the negative ELBO of a mini-batch.
+    pub fn train_step<R: Rng + ?Sized>(&mut self, batch: &[Vector], learning_rate: f64, rng: &mut R) -> VAELoss {
+        let n = batch.len() as f64;
+        let latent_size = self.latent_size();
+
+        // Encoder forward pass and reparameterization. The heads are applied
+        // inside the closure so the encoder's backward pass receives their
+        // gradient w.r.t. the hidden representation.
+        let mut kl_divergence = 0.0;
+        let mut mean_grad = Matrix::zeros(latent_size, self.mean_weights.cols);
+        let mut log_variance_grad = Matrix::zeros(latent_size, self.log_variance_weights.cols);
+        let mut reconstruction = 0.0;
+        let mut decoder_grads = Vec::new();
+
+        let mean_weights = &self.mean_weights;
+        let log_variance_weights = &self.log_variance_weights;
+        let decoder = &mut self.decoder;
+        let loss = self.reconstruction;
+        let (_, encoder_grads, _) = self.encoder.backpropagate_with(batch, |hidden| {
+            let extended: Vec<Vector> = hidden.iter().map(Layer::extend_with_bias).collect();
+            let means: Vec<Vector> = extended.iter().map(|h| mean_weights.gemv(h)).collect();
+            let log_variances: Vec<Vector> = extended.iter().map(|h| log_variance_weights.gemv(h)).collect();
+            let noise: Vec<Vector> =
+                (0..batch.len()).map(|_| Vector::new((0..latent_size).map(|_| standard_normal(rng)).collect())).collect();
+            let latents: Vec<Vector> = (0..batch.len())
+                .map(|s| {
+                    Vector::new(
+                        (0..latent_size)
+                            .map(|j| means[s][j] + (0.5 * log_variances[s][j]).exp() * noise[s][j])
+                            .collect(),
+                    )
+                })
+                .collect();
+
+            // Decoder forward and backward pass on the reconstruction loss
+            let (value, grads, latent_grads) = decoder.backpropagate_with(&latents, |outputs| {
+                let (value, grad) = loss.compute(&Matrix::from_vector(outputs.to_vec()), &Matrix::from_vector(batch.to_vec()));
+                (value, grad.rows)
+            });
+            reconstruction = value;
+            decoder_grads = grads;
+
+            // Gradients w.r.t. μ and log σ² of the reconstruction term (through
+            // z) plus the KL term, averaged over the batch
+            let mut hidden_grads = Vec::with_capacity(batch.len());
+            for s in 0..batch.len() {
+                kl_divergence += Self::kl(&means[s], &log_variances[s]) / n;
+                let mut mean_delta = Vector::zeros(latent_size);
+                let mut log_variance_delta = Vector::zeros(latent_size);
+                for j in 0..latent_size {
+                    let std_dev = (0.5 * log_variances[s][j]).exp();
+                    mean_delta[j] = latent_grads[s][j] + means[s][j] / n;
+                    log_variance_delta[j] =
+                        latent_grads[s][j] * noise[s][j] * 0.5 * std_dev + 0.5 * (std_dev * std_dev - 1.0) / n;
+                }
+                mean_grad.add_assign(&mean_delta.outer_product(&extended[s]));
+                log_variance_grad.add_assign(&log_variance_delta.outer_product(&extended[s]));
+                // Column 0 of the head weights is the bias, so skip it
+                hidden_grads.push(Vector::new(
+                    (1..mean_weights.cols)
+                        .map(|i| {
+                            (0..latent_size)
+                                .map(|j| mean_weights[(j, i)] * mean_delta[j] + log_variance_weights[(j, i)] * log_variance_delta[j])
+                                .sum()
+                        })
+                        .collect(),
+                ));
+            }
+            (0.0, hidden_grads)
+        });
+
+        self.decoder.apply_gradients(&decoder_grads, learning_rate);
+        self.encoder.apply_gradients(&encoder_grads, learning_rate);
+        self.mean_weights.add_assign(&mean_grad.map(|g| -learning_rate * g));
+        self.log_variance_weights.add_assign(&log_variance_grad.map(|g| -learning_rate * g));
+        VAELoss { reconstruction, kl_divergence }
+    }
+
+    /// Trains with mini-batches taken in order from `inputs`. Returns the mean
+    /// loss terms of each epoch.
+    pub fn fit<R: Rng + ?Sized>(
+        &mut self,
+        inputs: &[Vector],
+        learning_rate: f64,
+        batch_size: usize,
+        epochs: usize,
+        rng: &mut R,
+    ) -> Vec<VAELoss> {
+        assert!(!inputs.is_empty(), "Cannot fit on an empty dataset");
+        assert!(batch_size > 0, "Batch size must be positive");
+        let mut history = Vec::with_capacity(epochs);
+        for _ in 0..epochs {
+            let mut total = VAELoss { reconstruction: 0.0, kl_divergence: 0.0 };
+            let mut batches = 0;
+            for batch in inputs.chunks(batch_size) {
+                let loss = self.train_step(batch, learning_rate, rng);
+                total.reconstruction += loss.reconstruction;
+                total.kl_divergence += loss.kl_divergence;
+                batches += 1;
+            }
+            total.reconstruction /= batches as f64;
+            total.kl_divergence /= batches as f64;
+            history.push(total);
+        }
+        history
+    }
+}
@@ -0,0 +1,3 @@
+pub mod tape;
+
+pub use tape::{Gradients, Tape, Var};
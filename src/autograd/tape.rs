@@ -0,0 +1,406 @@
+use crate::math::Matrix;
+use crate::utils::activation::{relu, relu_derivative, sigmoid, sigmoid_derivative, tanh_derivative};
+use std::cell::RefCell;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// The operation that produced a node, with the indices of its inputs.
+#[derive(Debug, Clone, Copy)]
+enum Op {
+    Leaf,
+    Add(usize, usize),
+    Sub(usize, usize),
+    Mul(usize, usize),
+    Div(usize, usize),
+    Neg(usize),
+    Scale(usize, f64),
+    Powf(usize, f64),
+    MatMul(usize, usize),
+    Transpose(usize),
+    Exp(usize),
+    Log(usize),
+    Sigmoid(usize),
+    Tanh(usize),
+    ReLU(usize),
+    Sum(usize),
+    Mean(usize),
+    SumRows(usize),
+    SumCols(usize),
+}
+
+struct Node {
+    value: Matrix,
+    op: Op,
+}
+
+/// Records every operation performed on its variables so that gradients
+/// can be computed in a single reverse sweep.
+///
+/// ```ignore
+/// let tape = Tape::new();
+/// let w = tape.var(Matrix::new(vec![vec![0.5], vec![-0.3]]));
+/// let x = tape.constant(inputs);
+/// let loss = -x.matmul(w).sigmoid().log().mean();
+/// let grads = loss.backward();
+/// let dw = grads.wrt(w);
+/// ```
+pub struct Tape {
+    nodes: RefCell<Vec<Node>>,
+}
+
+/// A handle to a value recorded on a `Tape`.
+#[derive(Clone, Copy)]
+pub struct Var<'t> {
+    tape: &'t Tape,
+    index: usize,
+}
+
+/// Gradients of a scalar output w.r.t. every node on the tape.
+pub struct Gradients {
+    grads: Vec<Option<Matrix>>,
+}
+
+impl Tape {
+    pub fn new() -> Self {
+        Self { nodes: RefCell::new(Vec::new()) }
+    }
+
+    /// Records a new input variable.
+    pub fn var(&self, value: Matrix) -> Var<'_> {
+        self.push(value, Op::Leaf)
+    }
+
+    /// Records an input such as data or targets. Identical to `var`;
+    /// the separate name only documents that its gradient is not needed.
+    pub fn constant(&self, value: Matrix) -> Var<'_> {
+        self.push(value, Op::Leaf)
+    }
+
+    /// Records a 1 x 1 scalar variable.
+    pub fn scalar(&self, value: f64) -> Var<'_> {
+        self.push(Matrix::new(vec![vec![value]]), Op::Leaf)
+    }
+
+    /// Number of nodes recorded so far.
+    pub fn len(&self) -> usize {
+        self.nodes.borrow().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn push(&self, value: Matrix, op: Op) -> Var<'_> {
+        let mut nodes = self.nodes.borrow_mut();
+        nodes.push(Node { value, op });
+        Var { tape: self, index: nodes.len() - 1 }
+    }
+
+    fn value(&self, index: usize) -> Matrix {
+        self.nodes.borrow()[index].value.clone()
+    }
+
+    fn shape(&self, index: usize) -> (usize, usize) {
+        let nodes = self.nodes.borrow();
+        (nodes[index].value.row_count(), nodes[index].value.col_count())
+    }
+
+    /// Reverse sweep from `output`, which must be a 1 x 1 scalar.
+    fn backward(&self, output: usize) -> Gradients {
+        let nodes = self.nodes.borrow();
+        assert_eq!(
+            (nodes[output].value.row_count(), nodes[output].value.col_count()),
+            (1, 1),
+            "backward() must be called on a scalar (1 x 1) variable"
+        );
+        let mut grads: Vec<Option<Matrix>> = vec![None; nodes.len()];
+        grads[output] = Some(Matrix::new(vec![vec![1.0]]));
+
+        // Nodes are appended in evaluation order, so walking the tape
+        // backwards visits every node after all of its consumers.
+        for index in (0..=output).rev() {
+            let Some(grad) = grads[index].take() else {
+                continue;
+            };
+            let node = &nodes[index];
+            let value = |i: usize| &nodes[i].value;
+            let mut accumulate = |i: usize, g: Matrix| {
+                let (rows, cols) = (nodes[i].value.row_count(), nodes[i].value.col_count());
+                let g = reduce_to(&g, rows, cols);
+                match &mut grads[i] {
+                    Some(existing) => existing.add_assign(&g),
+                    slot => *slot = Some(g),
+                }
+            };
+            match node.op {
+                Op::Leaf => {}
+                Op::Add(a, b) => {
+                    accumulate(a, grad.clone());
+                    accumulate(b, grad.clone());
+                }
+                Op::Sub(a, b) => {
+                    accumulate(a, grad.clone());
+                    accumulate(b, grad.map(|g| -g));
+                }
+                Op::Mul(a, b) => {
+                    let (rows, cols) = (grad.row_count(), grad.col_count());
+                    accumulate(a, grad.hadamard(&broadcast(value(b), rows, cols)));
+                    accumulate(b, grad.hadamard(&broadcast(value(a), rows, cols)));
+                }
+                Op::Div(a, b) => {
+                    let (rows, cols) = (grad.row_count(), grad.col_count());
+                    let va = broadcast(value(a), rows, cols);
+                    let vb = broadcast(value(b), rows, cols);
+                    accumulate(a, grad.hadamard(&vb.map(|x| 1.0 / x)));
+                    accumulate(b, grad.hadamard(&va).hadamard(&vb.map(|x| -1.0 / (x * x))));
+                }
+                Op::Neg(a) => accumulate(a, grad.map(|g| -g)),
+                Op::Scale(a, factor) => accumulate(a, grad.map(|g| g * factor)),
+                Op::Powf(a, p) => accumulate(a, grad.hadamard(&value(a).map(|x| p * x.powf(p - 1.0)))),
+                Op::MatMul(a, b) => {
+                    accumulate(a, grad.gemm(&value(b).transpose()));
+                    accumulate(b, value(a).transpose().gemm(&grad));
+                }
+                Op::Transpose(a) => accumulate(a, grad.transpose()),
+                Op::Exp(a) => accumulate(a, grad.hadamard(&node.value)),
+                Op::Log(a) => accumulate(a, grad.hadamard(&value(a).map(|x| 1.0 / x))),
+                Op::Sigmoid(a) => accumulate(a, grad.hadamard(&node.value.map(sigmoid_derivative))),
+                Op::Tanh(a) => accumulate(a, grad.hadamard(&node.value.map(tanh_derivative))),
+                Op::ReLU(a) => accumulate(a, grad.hadamard(&value(a).map(relu_derivative))),
+                Op::Sum(a) => {
+                    let (rows, cols) = (value(a).row_count(), value(a).col_count());
+                    accumulate(a, broadcast(&grad, rows, cols));
+                }
+                Op::Mean(a) => {
+                    let (rows, cols) = (value(a).row_count(), value(a).col_count());
+                    let n = (rows * cols) as f64;
+                    accumulate(a, broadcast(&grad.map(|g| g / n), rows, cols));
+                }
+                Op::SumRows(a) | Op::SumCols(a) => {
+                    let (rows, cols) = (value(a).row_count(), value(a).col_count());
+                    accumulate(a, broadcast(&grad, rows, cols));
+                }
+            }
+            grads[index] = Some(grad);
+        }
+        Gradients { grads }
+    }
+}
+
+impl Default for Tape {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Output shape of a broadcasting element-wise operation. Each dimension
+/// must either match or be 1 on one side.
+fn broadcast_shape(a: (usize, usize), b: (usize, usize)) -> (usize, usize) {
+    let dim = |x: usize, y: usize| {
+        assert!(x == y || x == 1 || y == 1, "Shapes {:?} and {:?} cannot be broadcast together", a, b);
+        x.max(y)
+    };
+    (dim(a.0, b.0), dim(a.1, b.1))
+}
+
+/// Repeats a matrix with unit dimensions up to `rows x cols`.
+fn broadcast(m: &Matrix, rows: usize, cols: usize) -> Matrix {
+    if m.row_count() == rows && m.col_count() == cols {
+        return m.clone();
+    }
+    let mut out = Matrix::zeros(rows, cols);
+    for i in 0..rows {
+        for j in 0..cols {
+            let si = if m.row_count() == 1 { 0 } else { i };
+            let sj = if m.col_count() == 1 { 0 } else { j };
+            out[(i, j)] = m[(si, sj)];
+        }
+    }
+    out
+}
+
+/// Sums a broadcast gradient back down to the shape of the original operand.
+fn reduce_to(g: &Matrix, rows: usize, cols: usize) -> Matrix {
+    if g.row_count() == rows && g.col_count() == cols {
+        return g.clone();
+    }
+    let mut out = Matrix::zeros(rows, cols);
+    for i in 0..g.row_count() {
+        for j in 0..g.col_count() {
+            let ti = if rows == 1 { 0 } else { i };
+            let tj = if cols == 1 { 0 } else { j };
+            out[(ti, tj)] += g[(i, j)];
+        }
+    }
+    out
+}
+
+impl<'t> Var<'t> {
+    /// The value of this variable.
+    pub fn value(&self) -> Matrix {
+        self.tape.value(self.index)
+    }
+
+    /// The value of a 1 x 1 variable as a scalar.
+    pub fn scalar(&self) -> f64 {
+        self.value()[(0, 0)]
+    }
+
+    /// (rows, cols) of the value.
+    pub fn shape(&self) -> (usize, usize) {
+        self.tape.shape(self.index)
+    }
+
+    /// Computes the gradients of this scalar variable w.r.t. everything on the tape.
+    pub fn backward(&self) -> Gradients {
+        self.tape.backward(self.index)
+    }
+
+    fn unary(&self, value: Matrix, op: Op) -> Var<'t> {
+        self.tape.push(value, op)
+    }
+
+    fn elementwise<F: Fn(f64, f64) -> f64>(&self, other: Var<'t>, f: F, op: Op) -> Var<'t> {
+        assert!(std::ptr::eq(self.tape, other.tape), "Variables belong to different tapes");
+        let (rows, cols) = broadcast_shape(self.shape(), other.shape());
+        let a = broadcast(&self.value(), rows, cols);
+        let b = broadcast(&other.value(), rows, cols);
+        let mut out = Matrix::zeros(rows, cols);
+        for i in 0..rows {
+            for j in 0..cols {
+                out[(i, j)] = f(a[(i, j)], b[(i, j)]);
+            }
+        }
+        self.tape.push(out, op)
+    }
+
+    /// Multiplies every element by a constant.
+    pub fn scale(self, factor: f64) -> Var<'t> {
+        self.unary(self.value().map(|x| x * factor), Op::Scale(self.index, factor))
+    }
+
+    /// Raises every element to a constant power.
+    pub fn powf(self, p: f64) -> Var<'t> {
+        self.unary(self.value().map(|x| x.powf(p)), Op::Powf(self.index, p))
+    }
+
+    /// Matrix product.
+    pub fn matmul(self, other: Var<'t>) -> Var<'t> {
+        let value = self.value().gemm(&other.value());
+        self.tape.push(value, Op::MatMul(self.index, other.index))
+    }
+
+    pub fn transpose(self) -> Var<'t> {
+        self.unary(self.value().transpose(), Op::Transpose(self.index))
+    }
+
+    pub fn exp(self) -> Var<'t> {
+        self.unary(self.value().map(f64::exp), Op::Exp(self.index))
+    }
+
+    /// Natural logarithm.
+    pub fn log(self) -> Var<'t> {
+        self.unary(self.value().map(f64::ln), Op::Log(self.index))
+    }
+
+    pub fn sigmoid(self) -> Var<'t> {
+        self.unary(self.value().map(sigmoid), Op::Sigmoid(self.index))
+    }
+
+    pub fn tanh(self) -> Var<'t> {
+        self.unary(self.value().map(f64::tanh), Op::Tanh(self.index))
+    }
+
+    pub fn relu(self) -> Var<'t> {
+        self.unary(self.value().map(relu), Op::ReLU(self.index))
+    }
+
+    /// Sum of all elements (1 x 1).
+    pub fn sum(self) -> Var<'t> {
+        let total = self.value().rows.iter().map(|row| row.iter().sum::<f64>()).sum::<f64>();
+        self.unary(Matrix::new(vec![vec![total]]), Op::Sum(self.index))
+    }
+
+    /// Mean of all elements (1 x 1).
+    pub fn mean(self) -> Var<'t> {
+        let (rows, cols) = self.shape();
+        let total = self.value().rows.iter().map(|row| row.iter().sum::<f64>()).sum::<f64>();
+        self.unary(Matrix::new(vec![vec![total / (rows * cols) as f64]]), Op::Mean(self.index))
+    }
+
+    /// Sums over the rows, producing a 1 x cols row vector.
+    pub fn sum_rows(self) -> Var<'t> {
+        let sums = self.value().column_sums();
+        self.unary(Matrix::from_vector(vec![sums]), Op::SumRows(self.index))
+    }
+
+    /// Sums over the columns, producing a rows x 1 column vector.
+    pub fn sum_cols(self) -> Var<'t> {
+        let sums = self.value().rows.iter().map(|row| vec![row.iter().sum::<f64>()]).collect();
+        self.unary(Matrix::new(sums), Op::SumCols(self.index))
+    }
+
+    /// Row-wise softmax built from differentiable primitives.
+    pub fn softmax(self) -> Var<'t> {
+        // Subtract the (constant) row maximum for numerical stability
+        let max = self.tape.constant(Matrix::new(
+            self.value()
+                .rows
+                .iter()
+                .map(|row| vec![row.iter().cloned().fold(f64::NEG_INFINITY, f64::max)])
+                .collect(),
+        ));
+        let exp = (self - max).exp();
+        exp / exp.sum_cols()
+    }
+}
+
+impl<'t> Add for Var<'t> {
+    type Output = Var<'t>;
+    fn add(self, other: Var<'t>) -> Var<'t> {
+        self.elementwise(other, |a, b| a + b, Op::Add(self.index, other.index))
+    }
+}
+
+impl<'t> Sub for Var<'t> {
+    type Output = Var<'t>;
+    fn sub(self, other: Var<'t>) -> Var<'t> {
+        self.elementwise(other, |a, b| a - b, Op::Sub(self.index, other.index))
+    }
+}
+
+/// Element-wise product.
+impl<'t> Mul for Var<'t> {
+    type Output = Var<'t>;
+    fn mul(self, other: Var<'t>) -> Var<'t> {
+        self.elementwise(other, |a, b| a * b, Op::Mul(self.index, other.index))
+    }
+}
+
+/// Element-wise quotient.
+impl<'t> Div for Var<'t> {
+    type Output = Var<'t>;
+    fn div(self, other: Var<'t>) -> Var<'t> {
+        self.elementwise(other, |a, b| a / b, Op::Div(self.index, other.index))
+    }
+}
+
+impl<'t> Neg for Var<'t> {
+    type Output = Var<'t>;
+    fn neg(self) -> Var<'t> {
+        self.unary(self.value().map(|x| -x), Op::Neg(self.index))
+    }
+}
+
+impl Gradients {
+    /// Gradient w.r.t. `var`, with the same shape as its value.
+    /// Variables the output does not depend on get a zero gradient.
+    pub fn wrt(&self, var: Var<'_>) -> Matrix {
+        match self.grads.get(var.index).and_then(|g| g.as_ref()) {
+            Some(grad) => grad.clone(),
+            None => {
+                let (rows, cols) = var.shape();
+                Matrix::zeros(rows, cols)
+            }
+        }
+    }
+}
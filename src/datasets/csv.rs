@@ -0,0 +1,376 @@
+use crate::math::{Matrix, Vector};
+use std::collections::BTreeSet;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Lines};
+use std::path::{Path, PathBuf};
+
+/// A table of numeric features with an optional target column.
+///
+/// Categorical columns are encoded as the index of their value in
+/// `categories[j]` (sorted), and a categorical target as the index into
+/// `target_classes`. Missing values are NaN.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dataset {
+    pub features: Matrix,
+    pub target: Option<Vector>,
+    pub feature_names: Vec<String>,
+    pub target_name: Option<String>,
+    pub categories: Vec<Option<Vec<String>>>, // Sorted categories of each categorical feature
+    pub target_classes: Option<Vec<String>>,  // Sorted classes of a categorical target
+}
+
+impl Dataset {
+    pub fn len(&self) -> usize {
+        self.features.row_count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// A column, by position or by header name.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Column {
+    Index(usize),
+    Name(String),
+}
+
+impl From<usize> for Column {
+    fn from(index: usize) -> Self {
+        Column::Index(index)
+    }
+}
+
+impl From<&str> for Column {
+    fn from(name: &str) -> Self {
+        Column::Name(name.to_string())
+    }
+}
+
+/// How to read a delimited text file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvOptions {
+    pub delimiter: char,
+    pub has_header: bool,              // The first record holds column names
+    pub target: Option<Column>,        // Column split off as the target
+    pub missing_markers: Vec<String>,  // Field values read as missing, compared after trimming
+    pub categorical: Vec<Column>,      // Columns treated as categorical even if numeric
+}
+
+impl Default for CsvOptions {
+    /// Comma separated with a header, no target, and `""`, `NA`, `NaN`,
+    /// `nan`, `null` and `?` as missing markers.
+    fn default() -> Self {
+        Self {
+            delimiter: ',',
+            has_header: true,
+            target: None,
+            missing_markers: ["", "NA", "NaN", "nan", "null", "?"].iter().map(|m| m.to_string()).collect(),
+            categorical: Vec::new(),
+        }
+    }
+}
+
+impl CsvOptions {
+    pub fn with_delimiter(mut self, delimiter: char) -> Self {
+        assert!(delimiter != '"', "The quote character cannot be the delimiter");
+        self.delimiter = delimiter;
+        self
+    }
+
+    pub fn with_header(mut self, has_header: bool) -> Self {
+        self.has_header = has_header;
+        self
+    }
+
+    pub fn with_target(mut self, column: impl Into<Column>) -> Self {
+        self.target = Some(column.into());
+        self
+    }
+
+    pub fn with_missing_markers(mut self, markers: &[&str]) -> Self {
+        self.missing_markers = markers.iter().map(|m| m.to_string()).collect();
+        self
+    }
+
+    pub fn with_categorical(mut self, column: impl Into<Column>) -> Self {
+        self.categorical.push(column.into());
+        self
+    }
+
+    fn is_missing(&self, field: &str) -> bool {
+        self.missing_markers.iter().any(|m| m == field)
+    }
+}
+
+/// Type of a column: numeric, or categorical with its sorted categories.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ColumnKind {
+    Numeric,
+    Categorical(Vec<String>),
+}
+
+/// Names and types of all columns of a file, and which one is the target.
+/// Inferred once and then used to encode every record the same way.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Schema {
+    pub names: Vec<String>,
+    pub kinds: Vec<ColumnKind>,
+    pub target: Option<usize>,
+}
+
+impl Schema {
+    /// Encodes records into a dataset. Fails on a category the schema does not know.
+    fn encode(&self, records: &[(usize, Vec<String>)], options: &CsvOptions) -> Result<Dataset, String> {
+        let feature_columns: Vec<usize> = (0..self.names.len()).filter(|&j| Some(j) != self.target).collect();
+        let mut rows = Vec::with_capacity(records.len());
+        let mut target = Vec::with_capacity(records.len());
+        for (line, fields) in records {
+            let value = |j: usize| -> Result<f64, String> {
+                let field = fields[j].as_str();
+                if options.is_missing(field) {
+                    return Ok(f64::NAN);
+                }
+                match &self.kinds[j] {
+                    ColumnKind::Numeric => field.parse().map_err(|_| format!("line {}: {:?} in column {:?} is not a number", line, field, self.names[j])),
+                    ColumnKind::Categorical(categories) => categories
+                        .binary_search_by(|c| c.as_str().cmp(field))
+                        .map(|k| k as f64)
+                        .map_err(|_| format!("line {}: unknown category {:?} in column {:?}", line, field, self.names[j])),
+                }
+            };
+            rows.push(Vector::new(feature_columns.iter().map(|&j| value(j)).collect::<Result<_, _>>()?));
+            if let Some(t) = self.target {
+                target.push(value(t)?);
+            }
+        }
+        let categories_of = |j: usize| match &self.kinds[j] {
+            ColumnKind::Categorical(categories) => Some(categories.clone()),
+            ColumnKind::Numeric => None,
+        };
+        Ok(Dataset {
+            features: Matrix { rows, cols: feature_columns.len() },
+            target: self.target.map(|_| Vector::new(target)),
+            feature_names: feature_columns.iter().map(|&j| self.names[j].clone()).collect(),
+            target_name: self.target.map(|t| self.names[t].clone()),
+            categories: feature_columns.iter().map(|&j| categories_of(j)).collect(),
+            target_classes: self.target.and_then(categories_of),
+        })
+    }
+}
+
+/// Reads a whole delimited file into memory.
+///
+/// A column is numeric when every non-missing value parses as a number and
+/// it is not listed in `options.categorical`; otherwise it is categorical.
+/// Fields may be quoted with `"` (doubling it inside the quotes escapes it);
+/// quoted fields cannot span lines. Blank lines are skipped.
+pub fn read_csv<P: AsRef<Path>>(path: P, options: &CsvOptions) -> Result<Dataset, String> {
+    let path = path.as_ref();
+    let mut records = Records::open(path, options)?;
+    let names = records.names.clone();
+    let all: Vec<(usize, Vec<String>)> = records.by_ref().collect::<Result<_, _>>()?;
+    let schema = infer(names, options, |visit| {
+        all.iter().for_each(|(_, fields)| visit(fields));
+        Ok(())
+    })?;
+    schema.encode(&all, options).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+/// Infers the schema of a file without holding it in memory, by reading it
+/// twice: once for the column types and once for the categories.
+pub fn infer_schema<P: AsRef<Path>>(path: P, options: &CsvOptions) -> Result<Schema, String> {
+    let path = path.as_ref();
+    let names = Records::open(path, options)?.names;
+    infer(names, options, |visit| {
+        for record in Records::open(path, options)? {
+            visit(&record?.1);
+        }
+        Ok(())
+    })
+}
+
+/// Reads a file in batches of `batch_size` records, each encoded with
+/// `schema` (e.g. from `infer_schema`), so files larger than memory can be
+/// processed batch by batch.
+pub fn stream_csv<P: AsRef<Path>>(path: P, options: &CsvOptions, schema: Schema, batch_size: usize) -> Result<CsvStream, String> {
+    assert!(batch_size > 0, "Batch size must be positive");
+    let records = Records::open(path.as_ref(), options)?;
+    if records.names.len() != schema.names.len() {
+        return Err(format!("{}: schema has {} columns but the file has {}", path.as_ref().display(), schema.names.len(), records.names.len()));
+    }
+    Ok(CsvStream { records, schema, options: options.clone(), batch_size })
+}
+
+/// Iterator over the batches of a file; see `stream_csv`.
+pub struct CsvStream {
+    records: Records,
+    schema: Schema,
+    options: CsvOptions,
+    batch_size: usize,
+}
+
+impl Iterator for CsvStream {
+    type Item = Result<Dataset, String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let batch: Result<Vec<_>, String> = self.records.by_ref().take(self.batch_size).collect();
+        match batch {
+            Ok(batch) if batch.is_empty() => None,
+            Ok(batch) => Some(self.schema.encode(&batch, &self.options).map_err(|e| format!("{}: {}", self.records.path.display(), e))),
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+/// Infers column kinds with one pass of `for_each_record` and the categories
+/// of the categorical columns with a second. Each pass hands the fields of
+/// every record to the visitor it is given.
+fn infer<F>(names: Vec<String>, options: &CsvOptions, for_each_record: F) -> Result<Schema, String>
+where
+    F: Fn(&mut dyn FnMut(&[String])) -> Result<(), String>,
+{
+    let resolve = |column: &Column| match column {
+        Column::Index(j) if *j < names.len() => Ok(*j),
+        Column::Index(j) => Err(format!("Column {} is out of range for {} columns", j, names.len())),
+        Column::Name(name) => names.iter().position(|n| n == name).ok_or(format!("No column named {:?}", name)),
+    };
+    let target = options.target.as_ref().map(resolve).transpose()?;
+    let mut numeric = vec![true; names.len()];
+    for column in &options.categorical {
+        numeric[resolve(column)?] = false;
+    }
+
+    for_each_record(&mut |fields| {
+        for (is_numeric, field) in numeric.iter_mut().zip(fields) {
+            if *is_numeric && !options.is_missing(field) && field.parse::<f64>().is_err() {
+                *is_numeric = false;
+            }
+        }
+    })?;
+    let mut categories: Vec<BTreeSet<String>> = vec![BTreeSet::new(); names.len()];
+    if numeric.iter().any(|n| !n) {
+        for_each_record(&mut |fields| {
+            for (j, field) in fields.iter().enumerate() {
+                if !numeric[j] && !options.is_missing(field) && !categories[j].contains(field) {
+                    categories[j].insert(field.clone());
+                }
+            }
+        })?;
+    }
+    let kinds = numeric
+        .into_iter()
+        .zip(categories)
+        .map(|(is_numeric, values)| if is_numeric { ColumnKind::Numeric } else { ColumnKind::Categorical(values.into_iter().collect()) })
+        .collect();
+    Ok(Schema { names, kinds, target })
+}
+
+/// Parsed records of a file with their line numbers, after the header.
+struct Records {
+    lines: Lines<BufReader<File>>,
+    path: PathBuf,
+    delimiter: char,
+    names: Vec<String>, // Header names, or x0, x1, ... without a header
+    line: usize,
+    first: Option<Vec<String>>, // First data record, read to count the columns of a headerless file
+}
+
+impl Records {
+    fn open(path: &Path, options: &CsvOptions) -> Result<Self, String> {
+        let file = File::open(path).map_err(|e| format!("Cannot read {}: {}", path.display(), e))?;
+        let mut records = Self { lines: BufReader::new(file).lines(), path: path.to_path_buf(), delimiter: options.delimiter, names: Vec::new(), line: 0, first: None };
+        let first = match records.next_fields() {
+            Some(first) => first?,
+            None => return Err(format!("{}: file is empty", path.display())),
+        };
+        if options.has_header {
+            records.names = first;
+        } else {
+            records.names = (0..first.len()).map(|j| format!("x{}", j)).collect();
+            records.first = Some(first);
+        }
+        Ok(records)
+    }
+
+    /// Fields of the next non-blank line.
+    fn next_fields(&mut self) -> Option<Result<Vec<String>, String>> {
+        loop {
+            let line = match self.lines.next()? {
+                Ok(line) => line,
+                Err(e) => return Some(Err(format!("{}: {}", self.path.display(), e))),
+            };
+            self.line += 1;
+            if !line.trim().is_empty() {
+                return Some(split_record(&line, self.delimiter).map_err(|e| format!("{}: line {}: {}", self.path.display(), self.line, e)));
+            }
+        }
+    }
+}
+
+impl Iterator for Records {
+    type Item = Result<(usize, Vec<String>), String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let fields = match self.first.take() {
+            Some(first) => first,
+            None => match self.next_fields()? {
+                Ok(fields) => fields,
+                Err(e) => return Some(Err(e)),
+            },
+        };
+        if fields.len() != self.names.len() {
+            return Some(Err(format!("{}: line {}: expected {} fields, found {}", self.path.display(), self.line, self.names.len(), fields.len())));
+        }
+        Some(Ok((self.line, fields)))
+    }
+}
+
+/// Splits a line on `delimiter`, honoring double quotes. Unquoted fields are
+/// trimmed; quoted fields are kept verbatim.
+fn split_record(line: &str, delimiter: char) -> Result<Vec<String>, String> {
+    let line = line.strip_suffix('\r').unwrap_or(line);
+    let mut fields = Vec::new();
+    let mut chars = line.chars().peekable();
+    loop {
+        // Skip leading whitespace unless it is the delimiter itself
+        while chars.peek().is_some_and(|&c| c != delimiter && c.is_whitespace()) {
+            chars.next();
+        }
+        let mut field = String::new();
+        if chars.peek() == Some(&'"') {
+            chars.next();
+            loop {
+                match chars.next() {
+                    Some('"') if chars.peek() == Some(&'"') => {
+                        chars.next();
+                        field.push('"');
+                    }
+                    Some('"') => break,
+                    Some(c) => field.push(c),
+                    None => return Err("unterminated quoted field".to_string()),
+                }
+            }
+            while chars.peek().is_some_and(|&c| c != delimiter && c.is_whitespace()) {
+                chars.next();
+            }
+            if chars.peek().is_some_and(|&c| c != delimiter) {
+                return Err("unexpected text after a quoted field".to_string());
+            }
+        } else {
+            while let Some(&c) = chars.peek() {
+                if c == delimiter {
+                    break;
+                }
+                field.push(c);
+                chars.next();
+            }
+            field = field.trim_end().to_string();
+        }
+        fields.push(field);
+        if chars.next().is_none() {
+            return Ok(fields);
+        }
+    }
+}
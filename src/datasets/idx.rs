@@ -0,0 +1,89 @@
+use crate::math::Vector;
+use std::fs;
+use std::path::Path;
+
+/// Reads an IDX file (the format of the MNIST distribution) and returns its
+/// dimensions together with the values in row-major order.
+///
+/// Supports all IDX element types: unsigned/signed bytes, 16- and 32-bit
+/// integers, and 32- and 64-bit floats, stored big-endian.
+pub fn read_idx<P: AsRef<Path>>(path: P) -> Result<(Vec<usize>, Vec<f64>), String> {
+    let path = path.as_ref();
+    let bytes = fs::read(path).map_err(|e| format!("Cannot read {}: {}", path.display(), e))?;
+    parse_idx(&bytes).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+fn parse_idx(bytes: &[u8]) -> Result<(Vec<usize>, Vec<f64>), String> {
+    if bytes.len() < 4 || bytes[0] != 0 || bytes[1] != 0 {
+        return Err("not an IDX file (bad magic number)".to_string());
+    }
+    let element_size: usize = match bytes[2] {
+        0x08 | 0x09 => 1,
+        0x0B => 2,
+        0x0C | 0x0D => 4,
+        0x0E => 8,
+        code => return Err(format!("unknown IDX data type 0x{:02X}", code)),
+    };
+    let rank = bytes[3] as usize;
+    let header = 4 + 4 * rank;
+    if bytes.len() < header {
+        return Err("truncated header".to_string());
+    }
+    let dims: Vec<usize> = bytes[4..header]
+        .chunks(4)
+        .map(|d| u32::from_be_bytes([d[0], d[1], d[2], d[3]]) as usize)
+        .collect();
+    let size = dims
+        .iter()
+        .try_fold(element_size, |size, &d| size.checked_mul(d))
+        .ok_or(format!("dimensions {:?} are too large", dims))?;
+    if bytes.len() - header != size {
+        return Err(format!("expected {} bytes of data for dimensions {:?} but found {}", size, dims, bytes.len() - header));
+    }
+
+    let data = bytes[header..]
+        .chunks(element_size)
+        .map(|b| match bytes[2] {
+            0x08 => b[0] as f64,
+            0x09 => b[0] as i8 as f64,
+            0x0B => i16::from_be_bytes([b[0], b[1]]) as f64,
+            0x0C => i32::from_be_bytes([b[0], b[1], b[2], b[3]]) as f64,
+            0x0D => f32::from_be_bytes([b[0], b[1], b[2], b[3]]) as f64,
+            _ => f64::from_be_bytes([b[0], b[1], b[2], b[3], b[4], b[5], b[6], b[7]]),
+        })
+        .collect();
+    Ok((dims, data))
+}
+
+/// Reads an IDX image file (rank 3: count x height x width) and returns one
+/// flattened image per vector, with pixel values scaled from 0..255 to [0, 1].
+pub fn read_images<P: AsRef<Path>>(path: P) -> Result<Vec<Vector>, String> {
+    let (dims, data) = read_idx(&path)?;
+    if dims.len() != 3 {
+        return Err(format!("{}: expected 3 dimensions for images, found {}", path.as_ref().display(), dims.len()));
+    }
+    let size = dims[1] * dims[2];
+    Ok(data.chunks(size.max(1)).map(|image| Vector::new(image.iter().map(|p| p / 255.0).collect())).collect())
+}
+
+/// Reads an IDX label file (rank 1).
+pub fn read_labels<P: AsRef<Path>>(path: P) -> Result<Vec<usize>, String> {
+    let (dims, data) = read_idx(&path)?;
+    if dims.len() != 1 {
+        return Err(format!("{}: expected 1 dimension for labels, found {}", path.as_ref().display(), dims.len()));
+    }
+    Ok(data.into_iter().map(|label| label as usize).collect())
+}
+
+/// Loads the MNIST training (`train = true`) or test set from a directory
+/// containing the uncompressed files `train-images-idx3-ubyte`,
+/// `train-labels-idx1-ubyte`, `t10k-images-idx3-ubyte` and `t10k-labels-idx1-ubyte`.
+pub fn load_mnist<P: AsRef<Path>>(dir: P, train: bool) -> Result<(Vec<Vector>, Vec<usize>), String> {
+    let prefix = if train { "train" } else { "t10k" };
+    let images = read_images(dir.as_ref().join(format!("{}-images-idx3-ubyte", prefix)))?;
+    let labels = read_labels(dir.as_ref().join(format!("{}-labels-idx1-ubyte", prefix)))?;
+    if images.len() != labels.len() {
+        return Err(format!("Found {} images but {} labels", images.len(), labels.len()));
+    }
+    Ok((images, labels))
+}
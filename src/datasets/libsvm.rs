@@ -0,0 +1,171 @@
+use crate::math::{Matrix, SparseMatrix, Vector};
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+
+/// Labelled sparse rows in the LIBSVM / SVMlight text format, one row per line:
+/// `label [qid:n] index:value index:value ... [# comment]`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SvmLightData {
+    pub features: SparseMatrix,
+    pub labels: Vector,
+    pub qids: Option<Vec<u64>>, // Query ids for ranking data, 0 on lines without one
+}
+
+impl SvmLightData {
+    /// Wraps dense rows, keeping only their non-zero entries.
+    pub fn from_dense(features: &Matrix, labels: &Vector) -> Self {
+        assert!(features.row_count() == labels.len(), "Mismatched input and target sizes!");
+        Self { features: SparseMatrix::from_dense(features), labels: labels.clone(), qids: None }
+    }
+
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.labels.len() == 0
+    }
+
+    /// The features as a dense matrix, whose rows can be passed straight to
+    /// e.g. `SoftMarginSVM::fit` or `KernelSVM::fit_qp`.
+    pub fn to_dense(&self) -> Matrix {
+        self.features.to_dense()
+    }
+}
+
+/// How feature indices in a file map to columns.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IndexBase {
+    Zero,
+    /// The LIBSVM convention: index 1 is the first column.
+    One,
+    /// One-based unless some line uses index 0.
+    Auto,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LibsvmOptions {
+    pub index_base: IndexBase,
+    pub n_features: Option<usize>, // Column count; by default the largest index seen
+}
+
+impl Default for LibsvmOptions {
+    fn default() -> Self {
+        Self { index_base: IndexBase::Auto, n_features: None }
+    }
+}
+
+impl LibsvmOptions {
+    pub fn with_index_base(mut self, index_base: IndexBase) -> Self {
+        self.index_base = index_base;
+        self
+    }
+
+    /// Fixes the column count, e.g. so a test split gets as many columns as
+    /// its training split even if its last features are all zero.
+    pub fn with_n_features(mut self, n_features: usize) -> Self {
+        self.n_features = Some(n_features);
+        self
+    }
+}
+
+/// Reads a LIBSVM / SVMlight file.
+///
+/// Indices within a line must be strictly increasing. Blank lines and
+/// comments starting with `#` are skipped.
+pub fn read_libsvm<P: AsRef<Path>>(path: P, options: &LibsvmOptions) -> Result<SvmLightData, String> {
+    let path = path.as_ref();
+    let file = File::open(path).map_err(|e| format!("Cannot read {}: {}", path.display(), e))?;
+    let mut rows: Vec<Entries> = Vec::new();
+    let mut labels = Vec::new();
+    let mut qids: Vec<Option<u64>> = Vec::new();
+    for (number, line) in BufReader::new(file).lines().enumerate() {
+        let line = line.map_err(|e| format!("{}: {}", path.display(), e))?;
+        let content = line.split('#').next().unwrap_or("");
+        if content.trim().is_empty() {
+            continue;
+        }
+        let (label, qid, entries) = parse_line(content).map_err(|e| format!("{}: line {}: {}", path.display(), number + 1, e))?;
+        labels.push(label);
+        qids.push(qid);
+        rows.push(entries);
+    }
+
+    let zero_based = match options.index_base {
+        IndexBase::Zero => true,
+        IndexBase::One => false,
+        IndexBase::Auto => rows.iter().flatten().any(|&(j, _)| j == 0),
+    };
+    if !zero_based && rows.iter().flatten().any(|&(j, _)| j == 0) {
+        return Err(format!("{}: index 0 in a one-based file", path.display()));
+    }
+    let offset = if zero_based { 0 } else { 1 };
+    let needed = rows.iter().flatten().map(|&(j, _)| j + 1 - offset).max().unwrap_or(0);
+    let cols = match options.n_features {
+        Some(n) if n < needed => return Err(format!("{}: found feature {} but n_features is {}", path.display(), needed, n)),
+        Some(n) => n,
+        None => needed,
+    };
+
+    let mut features = SparseMatrix::new(cols);
+    for entries in rows {
+        features.push_row(entries.into_iter().map(|(j, v)| (j - offset, v)));
+    }
+    let qids = qids.iter().any(|q| q.is_some()).then(|| qids.iter().map(|q| q.unwrap_or(0)).collect());
+    Ok(SvmLightData { features, labels: Vector::new(labels), qids })
+}
+
+/// Writes rows in the LIBSVM / SVMlight format, one-based unless
+/// `zero_based`. Explicitly stored zeros are written too.
+pub fn write_libsvm<P: AsRef<Path>>(path: P, data: &SvmLightData, zero_based: bool) -> Result<(), String> {
+    let path = path.as_ref();
+    assert!(data.features.row_count() == data.labels.len(), "Mismatched input and target sizes!");
+    if let Some(qids) = &data.qids {
+        assert!(qids.len() == data.labels.len(), "Mismatched qid and target sizes!");
+    }
+    let file = File::create(path).map_err(|e| format!("Cannot write {}: {}", path.display(), e))?;
+    let mut writer = BufWriter::new(file);
+    let offset = if zero_based { 0 } else { 1 };
+    for i in 0..data.len() {
+        let mut line = data.labels[i].to_string();
+        if let Some(qids) = &data.qids {
+            line.push_str(&format!(" qid:{}", qids[i]));
+        }
+        let (indices, values) = data.features.row(i);
+        for (j, v) in indices.iter().zip(values) {
+            line.push_str(&format!(" {}:{}", j + offset, v));
+        }
+        writeln!(writer, "{}", line).map_err(|e| format!("{}: {}", path.display(), e))?;
+    }
+    writer.flush().map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+/// Raw `(index, value)` pairs of a line, before the index base is applied.
+type Entries = Vec<(usize, f64)>;
+
+/// Splits a line without its comment into label, qid and entries.
+fn parse_line(content: &str) -> Result<(f64, Option<u64>, Entries), String> {
+    let mut tokens = content.split_whitespace();
+    let label = tokens.next().unwrap_or("");
+    let label: f64 = label.parse().map_err(|_| format!("invalid label {:?}", label))?;
+    let mut qid = None;
+    let mut entries: Entries = Vec::new();
+    for token in tokens {
+        let (key, value) = token.split_once(':').ok_or(format!("expected index:value, found {:?}", token))?;
+        if key == "qid" {
+            if qid.is_some() || !entries.is_empty() {
+                return Err("qid must come once, right after the label".to_string());
+            }
+            qid = Some(value.parse().map_err(|_| format!("invalid qid {:?}", value))?);
+            continue;
+        }
+        let index: usize = key.parse().map_err(|_| format!("invalid index {:?}", key))?;
+        let value: f64 = value.parse().map_err(|_| format!("invalid value {:?}", value))?;
+        if entries.last().is_some_and(|&(last, _)| last >= index) {
+            return Err(format!("index {} does not increase", index));
+        }
+        entries.push((index, value));
+    }
+    Ok((label, qid, entries))
+}
@@ -0,0 +1,9 @@
+pub mod csv;
+pub mod idx;
+pub mod libsvm;
+pub mod synthetic;
+
+pub use csv::{infer_schema, read_csv, stream_csv, Column, ColumnKind, CsvOptions, CsvStream, Dataset, Schema};
+pub use idx::{load_mnist, read_idx, read_images, read_labels};
+pub use libsvm::{read_libsvm, write_libsvm, IndexBase, LibsvmOptions, SvmLightData};
+pub use synthetic::swiss_roll_2d;
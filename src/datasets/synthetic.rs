@@ -0,0 +1,16 @@
+use crate::math::Vector;
+use crate::utils::random::normal;
+use rand::Rng;
+
+/// Points on a 2-D Swiss roll (a spiral `(t cos t, t sin t)` for
+/// `t ∈ [1.5π, 4.5π]`) scaled to roughly unit variance, with Gaussian noise
+/// of standard deviation `noise` added to each coordinate.
+pub fn swiss_roll_2d<R: Rng + ?Sized>(count: usize, noise: f64, rng: &mut R) -> Vec<Vector> {
+    let pi = std::f64::consts::PI;
+    (0..count)
+        .map(|_| {
+            let t = rng.random_range(1.5 * pi..4.5 * pi);
+            Vector::new(vec![t * t.cos() / 8.0 + normal(rng, 0.0, noise), t * t.sin() / 8.0 + normal(rng, 0.0, noise)])
+        })
+        .collect()
+}
@@ -0,0 +1,146 @@
+use crate::diffusion::schedule::NoiseSchedule;
+use crate::math::{Matrix, Vector};
+use crate::neuralnetwork::{Loss, Optimizer, Sequential, SinusoidalEncoding};
+use crate::utils::random::standard_normal;
+use rand::Rng;
+use rand::seq::SliceRandom;
+
+/// Denoising diffusion probabilistic model (Ho et al., 2020).
+///
+/// The network predicts the noise `ε` that was added to a sample. It receives
+/// the noisy sample followed by a sinusoidal embedding of the time step, so
+/// its input size is `data_dim + time_dim` and its output size `data_dim`.
+pub struct DDPM {
+    pub model: Sequential,
+    pub schedule: NoiseSchedule,
+    pub data_dim: usize,
+    time_embedding: SinusoidalEncoding,
+}
+
+impl DDPM {
+    /// Panics if the model's input or output size does not match `data_dim`
+    /// and `time_dim`.
+    pub fn new(model: Sequential, schedule: NoiseSchedule, data_dim: usize, time_dim: usize) -> Self {
+        assert_eq!(model.input_size, data_dim + time_dim, "Model input must be data_dim + time_dim");
+        assert_eq!(model.output_size(), data_dim, "Model must output one noise value per data dimension");
+        Self { model, schedule, data_dim, time_embedding: SinusoidalEncoding::new(time_dim) }
+    }
+
+    fn gaussian<R: Rng + ?Sized>(&self, rng: &mut R) -> Vector {
+        Vector::new((0..self.data_dim).map(|_| standard_normal(rng)).collect())
+    }
+
+    /// Forward process `x_t = √ᾱ_t x_0 + √(1 - ᾱ_t) ε`.
+    pub fn q_sample(&self, x0: &Vector, t: usize, noise: &Vector) -> Vector {
+        let alpha_bar = self.schedule.alpha_bars[t];
+        let mut x = x0.scale(alpha_bar.sqrt());
+        x.add_assign(noise, (1.0 - alpha_bar).sqrt());
+        x
+    }
+
+    /// Network input for a noisy sample at step `t`.
+    fn model_input(&self, x: &Vector, t: usize) -> Vector {
+        let mut input = x.data.clone();
+        input.extend(self.time_embedding.encoding(t).data);
+        Vector::new(input)
+    }
+
+    /// Predicted noise for a batch of samples that are all at step `t`.
+    pub fn predict_noise(&mut self, samples: &[Vector], t: usize) -> Vec<Vector> {
+        let inputs = Matrix::from_vector(samples.iter().map(|x| self.model_input(x, t)).collect());
+        self.model.forward(&inputs, false).rows
+    }
+
+    /// One optimizer step on `‖ε - ε_θ(x_t, t)‖²` with a random step and
+    /// noise per sample. Returns the batch loss.
+    pub fn train_step<R: Rng + ?Sized>(&mut self, batch: &[Vector], optimizer: &mut dyn Optimizer, rng: &mut R) -> f64 {
+        let mut inputs = Vec::with_capacity(batch.len());
+        let mut targets = Vec::with_capacity(batch.len());
+        for x0 in batch {
+            let t = rng.random_range(0..self.schedule.steps());
+            let noise = self.gaussian(rng);
+            inputs.push(self.model_input(&self.q_sample(x0, t, &noise), t));
+            targets.push(noise);
+        }
+        let (inputs, targets) = (Matrix::from_vector(inputs), Matrix::from_vector(targets));
+        self.model.train_step(&inputs, &targets, Loss::MeanSquaredError, optimizer)
+    }
+
+    /// Trains on shuffled mini-batches. Returns the mean loss of each epoch.
+    pub fn fit<R: Rng + ?Sized>(
+        &mut self,
+        data: &[Vector],
+        optimizer: &mut dyn Optimizer,
+        batch_size: usize,
+        epochs: usize,
+        rng: &mut R,
+    ) -> Vec<f64> {
+        assert!(data.iter().all(|x| x.len() == self.data_dim), "Samples must have data_dim features");
+        assert!(batch_size > 0, "Batch size must be positive");
+        let mut order = data.to_vec();
+        let mut history = Vec::with_capacity(epochs);
+        for _ in 0..epochs {
+            order.shuffle(rng);
+            let losses: Vec<f64> = order.chunks(batch_size).map(|batch| self.train_step(batch, optimizer, rng)).collect();
+            history.push(losses.iter().sum::<f64>() / losses.len() as f64);
+        }
+        history
+    }
+
+    /// Ancestral sampling: starts from `x_T ~ N(0, I)` and applies every
+    /// reverse step `x_{t-1} = (x_t - β_t / √(1 - ᾱ_t) ε_θ) / √α_t + σ_t z`
+    /// with the posterior variance `σ_t² = β̃_t`.
+    pub fn sample_ddpm<R: Rng + ?Sized>(&mut self, count: usize, rng: &mut R) -> Vec<Vector> {
+        let mut samples: Vec<Vector> = (0..count).map(|_| self.gaussian(rng)).collect();
+        for t in (0..self.schedule.steps()).rev() {
+            let noise = self.predict_noise(&samples, t);
+            let (alpha, beta) = (self.schedule.alphas[t], self.schedule.betas[t]);
+            let coefficient = beta / (1.0 - self.schedule.alpha_bars[t]).sqrt();
+            let sigma = self.schedule.posterior_variance(t).sqrt();
+            for (x, eps) in samples.iter_mut().zip(&noise) {
+                x.add_assign(eps, -coefficient);
+                x.scale_assign(1.0 / alpha.sqrt());
+                if t > 0 {
+                    let z = self.gaussian(rng);
+                    x.add_assign(&z, sigma);
+                }
+            }
+        }
+        samples
+    }
+
+    /// Deterministic DDIM sampling (η = 0) from `count` fresh noise vectors
+    /// using `steps` evenly spaced time steps.
+    pub fn sample_ddim<R: Rng + ?Sized>(&mut self, count: usize, steps: usize, rng: &mut R) -> Vec<Vector> {
+        let noise: Vec<Vector> = (0..count).map(|_| self.gaussian(rng)).collect();
+        self.ddim_from_noise(&noise, steps)
+    }
+
+    /// Maps the given `x_T` to samples with deterministic DDIM updates
+    /// `x_{t'} = √ᾱ_{t'} x̂_0 + √(1 - ᾱ_{t'}) ε_θ`, where
+    /// `x̂_0 = (x_t - √(1 - ᾱ_t) ε_θ) / √ᾱ_t`. The same noise always yields the
+    /// same samples.
+    pub fn ddim_from_noise(&mut self, noise: &[Vector], steps: usize) -> Vec<Vector> {
+        let total = self.schedule.steps();
+        assert!(steps > 0 && steps <= total, "DDIM steps must be between 1 and the schedule length");
+        // Evenly spaced time steps, always ending at the last one
+        let mut times: Vec<usize> = (0..steps).map(|i| i * total / steps).collect();
+        times[steps - 1] = total - 1;
+        times.dedup();
+
+        let mut samples = noise.to_vec();
+        for (i, &t) in times.iter().enumerate().rev() {
+            let eps = self.predict_noise(&samples, t);
+            let alpha_bar = self.schedule.alpha_bars[t];
+            let alpha_bar_prev = if i > 0 { self.schedule.alpha_bars[times[i - 1]] } else { 1.0 };
+            for (x, e) in samples.iter_mut().zip(&eps) {
+                let mut x0 = x.clone();
+                x0.add_assign(e, -(1.0 - alpha_bar).sqrt());
+                x0.scale_assign(1.0 / alpha_bar.sqrt());
+                *x = x0.scale(alpha_bar_prev.sqrt());
+                x.add_assign(e, (1.0 - alpha_bar_prev).sqrt());
+            }
+        }
+        samples
+    }
+}
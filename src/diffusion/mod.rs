@@ -0,0 +1,5 @@
+pub mod schedule;
+pub use schedule::NoiseSchedule;
+
+pub mod ddpm;
+pub use ddpm::DDPM;
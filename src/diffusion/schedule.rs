@@ -0,0 +1,60 @@
+/// Variance schedule `β_1..β_T` of the forward (noising) process, with the
+/// derived `α_t = 1 - β_t` and `ᾱ_t = Π_{s<=t} α_s`. Index `t` runs from
+/// `0` to `steps - 1`.
+#[derive(Debug, Clone)]
+pub struct NoiseSchedule {
+    pub betas: Vec<f64>,
+    pub alphas: Vec<f64>,
+    pub alpha_bars: Vec<f64>,
+}
+
+impl NoiseSchedule {
+    /// Builds a schedule from its betas. Each beta must lie in (0, 1).
+    pub fn from_betas(betas: Vec<f64>) -> Self {
+        assert!(!betas.is_empty(), "Schedule needs at least one step");
+        assert!(betas.iter().all(|&b| b > 0.0 && b < 1.0), "Betas must lie in (0, 1)");
+        let alphas: Vec<f64> = betas.iter().map(|b| 1.0 - b).collect();
+        let alpha_bars = alphas
+            .iter()
+            .scan(1.0, |product, a| {
+                *product *= a;
+                Some(*product)
+            })
+            .collect();
+        Self { betas, alphas, alpha_bars }
+    }
+
+    /// Betas increasing linearly from `beta_start` to `beta_end` (Ho et al.
+    /// use 1e-4 to 0.02 over 1000 steps).
+    pub fn linear(steps: usize, beta_start: f64, beta_end: f64) -> Self {
+        let betas = (0..steps)
+            .map(|t| {
+                let fraction = if steps > 1 { t as f64 / (steps - 1) as f64 } else { 0.0 };
+                beta_start + fraction * (beta_end - beta_start)
+            })
+            .collect();
+        Self::from_betas(betas)
+    }
+
+    /// Cosine schedule of Nichol & Dhariwal: `ᾱ(t) ∝ cos²((t/T + s) / (1 + s) · π/2)`
+    /// with offset `s = 0.008`, betas clipped to 0.999.
+    pub fn cosine(steps: usize) -> Self {
+        let s = 0.008;
+        let f = |t: usize| ((t as f64 / steps as f64 + s) / (1.0 + s) * std::f64::consts::FRAC_PI_2).cos().powi(2);
+        let betas = (0..steps).map(|t| (1.0 - f(t + 1) / f(t)).clamp(1e-8, 0.999)).collect();
+        Self::from_betas(betas)
+    }
+
+    pub fn steps(&self) -> usize {
+        self.betas.len()
+    }
+
+    /// Variance of the posterior `q(x_{t-1} | x_t, x_0)`:
+    /// `β̃_t = (1 - ᾱ_{t-1}) / (1 - ᾱ_t) β_t`, with `β̃_0 = 0`.
+    pub fn posterior_variance(&self, t: usize) -> f64 {
+        if t == 0 {
+            return 0.0;
+        }
+        (1.0 - self.alpha_bars[t - 1]) / (1.0 - self.alpha_bars[t]) * self.betas[t]
+    }
+}
@@ -0,0 +1,233 @@
+use crate::math::{Matrix, Vector};
+use crate::neuralnetwork::{Layer, Loss, NeuralNetwork, Sequential};
+
+/// Step size used for central differences.
+const EPSILON: f64 = 1e-5;
+
+/// Relative errors below this denominator are measured as absolute errors,
+/// so parameters whose true gradient is zero do not blow up the ratio.
+const FLOOR: f64 = 1e-6;
+
+/// Maximum relative error between analytic and numerical gradients,
+/// one entry per parameter tensor.
+#[derive(Debug, Clone)]
+pub struct GradCheckReport {
+    pub errors: Vec<(String, f64)>, // (tensor name, max relative error)
+}
+
+impl GradCheckReport {
+    /// The largest error over all tensors.
+    pub fn max_error(&self) -> f64 {
+        self.errors.iter().map(|(_, e)| *e).fold(0.0, f64::max)
+    }
+
+    /// True when every tensor's error is below `tolerance`.
+    pub fn passed(&self, tolerance: f64) -> bool {
+        self.max_error() < tolerance
+    }
+}
+
+fn relative_error(analytic: f64, numeric: f64) -> f64 {
+    (analytic - numeric).abs() / analytic.abs().max(numeric.abs()).max(FLOOR)
+}
+
+/// Compares `analytic` against central differences of `f` at `x`.
+/// Returns the maximum relative error over all coordinates.
+pub fn check_closure<F: FnMut(&Vector) -> f64>(mut f: F, x: &Vector, analytic: &Vector) -> f64 {
+    assert_eq!(x.len(), analytic.len(), "Gradient must have the same length as the point");
+    let mut max_error: f64 = 0.0;
+    let mut point = x.clone();
+    for i in 0..x.len() {
+        point[i] = x[i] + EPSILON;
+        let plus = f(&point);
+        point[i] = x[i] - EPSILON;
+        let minus = f(&point);
+        point[i] = x[i];
+        let numeric = (plus - minus) / (2.0 * EPSILON);
+        max_error = max_error.max(relative_error(analytic[i], numeric));
+    }
+    max_error
+}
+
+/// Checks a set of matrix-valued parameters. `loss` is evaluated with a
+/// perturbed copy of the parameters; `analytic[k]` is the gradient of the
+/// loss w.r.t. `parameters[k]`.
+pub fn check_matrices<F: FnMut(&[Matrix]) -> f64>(
+    mut loss: F,
+    parameters: &[Matrix],
+    analytic: &[Matrix],
+    names: &[String],
+) -> GradCheckReport {
+    assert_eq!(parameters.len(), analytic.len(), "Expected one gradient per parameter tensor");
+    let mut errors = Vec::with_capacity(parameters.len());
+    let mut perturbed = parameters.to_vec();
+    for k in 0..parameters.len() {
+        let mut max_error: f64 = 0.0;
+        for i in 0..parameters[k].row_count() {
+            for j in 0..parameters[k].col_count() {
+                let original = parameters[k][(i, j)];
+                perturbed[k][(i, j)] = original + EPSILON;
+                let plus = loss(&perturbed);
+                perturbed[k][(i, j)] = original - EPSILON;
+                let minus = loss(&perturbed);
+                perturbed[k][(i, j)] = original;
+                let numeric = (plus - minus) / (2.0 * EPSILON);
+                max_error = max_error.max(relative_error(analytic[k][(i, j)], numeric));
+            }
+        }
+        let name = names.get(k).cloned().unwrap_or_else(|| format!("param{}", k));
+        errors.push((name, max_error));
+    }
+    GradCheckReport { errors }
+}
+
+/// Fixed pseudo-random weights used to reduce a layer's output to a scalar.
+fn projection(rows: usize, cols: usize) -> Matrix {
+    Matrix::new(
+        (0..rows)
+            .map(|i| (0..cols).map(|j| ((i * cols + j) as f64 * 0.7 + 0.3).sin()).collect())
+            .collect(),
+    )
+}
+
+fn weighted_sum(output: &Matrix, weights: &Matrix) -> f64 {
+    output.rows.iter().zip(&weights.rows).map(|(o, w)| o.dot(w)).sum()
+}
+
+/// Checks a layer's `backward` against central differences of the scalar
+/// `Σ c ⊙ forward(input)` for fixed coefficients `c`. The report contains
+/// one entry for the input gradient followed by one per parameter tensor.
+/// The forward pass runs in training mode, so stochastic layers such as
+/// dropout must be disabled. The layer's state (e.g. running statistics) is
+/// restored after every evaluation, leaving the checked layer unchanged.
+pub fn check_layer(layer: &mut dyn Layer, input: &Matrix) -> GradCheckReport {
+    let state = layer.state();
+    let output = layer.forward(input, true);
+    layer.set_state(&state);
+    let coefficients = projection(output.row_count(), output.col_count());
+    let grad_input = layer.backward(&coefficients);
+    let parameters = layer.parameters();
+    let gradients = layer.gradients();
+
+    // Input gradient
+    let mut input_report = check_matrices(
+        |x| {
+            let value = weighted_sum(&layer.forward(&x[0], true), &coefficients);
+            layer.set_state(&state);
+            value
+        },
+        std::slice::from_ref(input),
+        &[grad_input],
+        &["input".to_string()],
+    );
+
+    // Parameter gradients
+    let names: Vec<String> = (0..parameters.len()).map(|k| format!("{} param{}", layer.name(), k)).collect();
+    let report = check_matrices(
+        |params| {
+            layer.set_parameters(params);
+            let value = weighted_sum(&layer.forward(input, true), &coefficients);
+            layer.set_state(&state);
+            value
+        },
+        &parameters,
+        &gradients,
+        &names,
+    );
+    layer.set_parameters(&parameters);
+
+    input_report.errors.extend(report.errors);
+    input_report
+}
+
+/// Checks the parameter gradients of every layer of a `Sequential` model
+/// under the given loss. Layer states are restored after every evaluation.
+pub fn check_sequential(model: &mut Sequential, inputs: &Matrix, targets: &Matrix, loss: Loss) -> GradCheckReport {
+    let states: Vec<Vec<Matrix>> = model.layers.iter().map(|layer| layer.state()).collect();
+    let restore = |model: &mut Sequential| {
+        for (layer, state) in model.layers.iter_mut().zip(&states) {
+            layer.set_state(state);
+        }
+    };
+    let output = model.forward(inputs, true);
+    restore(model);
+    let (_, grad) = loss.compute(&output, targets);
+    model.backward(&grad);
+
+    let mut errors = Vec::new();
+    for l in 0..model.layers.len() {
+        let parameters = model.layers[l].parameters();
+        if parameters.is_empty() {
+            continue;
+        }
+        let gradients = model.layers[l].gradients();
+        let names: Vec<String> = (0..parameters.len())
+            .map(|k| format!("layer{} {} param{}", l, model.layers[l].name(), k))
+            .collect();
+        let report = check_matrices(
+            |params| {
+                model.layers[l].set_parameters(params);
+                let value = loss.compute(&model.forward(inputs, true), targets).0;
+                restore(model);
+                value
+            },
+            &parameters,
+            &gradients,
+            &names,
+        );
+        model.layers[l].set_parameters(&parameters);
+        errors.extend(report.errors);
+    }
+    GradCheckReport { errors }
+}
+
+/// Checks the backpropagation of a `NeuralNetwork` (the weight gradients
+/// used by `train`/`train_batch` and the gamma/beta gradients of its
+/// normalization layers) against central differences of `batch_loss`.
+/// Normalization states are restored after every evaluation.
+pub fn check_network(network: &mut NeuralNetwork, inputs: &[Vector], targets: &[Vector]) -> GradCheckReport {
+    let states: Vec<Vec<Matrix>> = network.normalizations.iter().map(|norm| norm.as_ref().map_or(Vec::new(), |n| n.as_layer().state())).collect();
+    let restore = |network: &mut NeuralNetwork| {
+        for (norm, state) in network.normalizations.iter_mut().zip(&states) {
+            if let Some(norm) = norm {
+                norm.as_layer_mut().set_state(state);
+            }
+        }
+    };
+
+    let mut analytic = network.gradients(inputs, targets);
+    restore(network);
+    let mut parameters: Vec<Matrix> = network.layers.iter().map(|layer| layer.weights.clone()).collect();
+    let mut names: Vec<String> = (0..parameters.len()).map(|l| format!("layer{} weights", l)).collect();
+    for (l, norm) in network.normalizations.iter().enumerate() {
+        if let Some(norm) = norm {
+            parameters.extend(norm.as_layer().parameters());
+            analytic.extend(norm.as_layer().gradients());
+            names.extend(["gamma", "beta"].iter().map(|p| format!("layer{} {}", l, p)));
+        }
+    }
+
+    let set_parameters = |network: &mut NeuralNetwork, params: &[Matrix]| {
+        let (weights, mut normalization) = params.split_at(network.layers.len());
+        for (layer, p) in network.layers.iter_mut().zip(weights) {
+            layer.weights = p.clone();
+        }
+        for norm in network.normalizations.iter_mut().flatten() {
+            norm.as_layer_mut().set_parameters(&normalization[..2]);
+            normalization = &normalization[2..];
+        }
+    };
+    let report = check_matrices(
+        |params| {
+            set_parameters(network, params);
+            let value = network.batch_loss(inputs, targets);
+            restore(network);
+            value
+        },
+        &parameters,
+        &analytic,
+        &names,
+    );
+    set_parameters(network, &parameters);
+    report
+}
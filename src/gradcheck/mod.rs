@@ -0,0 +1,4 @@
+#[allow(clippy::module_inception)]
+pub mod gradcheck;
+
+pub use gradcheck::{check_closure, check_layer, check_matrices, check_network, check_sequential, GradCheckReport};
@@ -0,0 +1,154 @@
+use crate::math::{Matrix, Vector};
+use rand::Rng;
+use rand::seq::SliceRandom;
+
+/// How the weights are computed from the stored patterns.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LearningRule {
+    /// `W = 1/n Σ ξ ξᵀ` with a zero diagonal. Stores about `0.14 n` random patterns.
+    Hebbian,
+    /// Storkey's incremental rule, which accounts for the local fields of the
+    /// patterns already stored and has a higher capacity than Hebbian learning.
+    Storkey,
+}
+
+/// How the neurons are updated during recall.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UpdateRule {
+    /// All neurons at once from the previous state. May end in a 2-cycle.
+    Synchronous,
+    /// One neuron at a time in random order. Never increases the energy.
+    Asynchronous,
+}
+
+/// Classical binary Hopfield network with bipolar (`-1`/`+1`) states.
+pub struct HopfieldNetwork {
+    pub weights: Matrix,    // Symmetric, zero diagonal
+    pub thresholds: Vector, // Neuron i turns on when Σ_j w_ij s_j > θ_i
+}
+
+impl HopfieldNetwork {
+    /// Creates a network of `size` neurons with no stored patterns.
+    pub fn new(size: usize) -> Self {
+        Self { weights: Matrix::zeros(size, size), thresholds: Vector::zeros(size) }
+    }
+
+    pub fn size(&self) -> usize {
+        self.weights.row_count()
+    }
+
+    /// Stores bipolar patterns in addition to those already stored.
+    pub fn train(&mut self, patterns: &[Vector], rule: LearningRule) {
+        let n = self.size();
+        for pattern in patterns {
+            assert_eq!(pattern.len(), n, "Pattern size must match the number of neurons");
+            assert!(pattern.iter().all(|&x| x == 1.0 || x == -1.0), "Patterns must be bipolar (-1/+1)");
+        }
+        match rule {
+            LearningRule::Hebbian => {
+                for pattern in patterns {
+                    let mut update = pattern.outer_product(pattern);
+                    update.scale(1.0 / n as f64);
+                    self.weights.add_assign(&update);
+                }
+            }
+            LearningRule::Storkey => {
+                for pattern in patterns {
+                    // Local field h_ij = Σ_{k≠i,j} w_ik ξ_k (the diagonal is zero)
+                    let field = self.weights.gemv(pattern);
+                    let mut update = Matrix::zeros(n, n);
+                    for i in 0..n {
+                        for j in 0..n {
+                            let h_ij = field[i] - self.weights[(i, j)] * pattern[j];
+                            let h_ji = field[j] - self.weights[(j, i)] * pattern[i];
+                            update[(i, j)] = pattern[i] * pattern[j] - pattern[i] * h_ji - h_ij * pattern[j];
+                        }
+                    }
+                    update.scale(1.0 / n as f64);
+                    self.weights.add_assign(&update);
+                    self.clear_diagonal();
+                }
+            }
+        }
+        self.clear_diagonal();
+    }
+
+    fn clear_diagonal(&mut self) {
+        for i in 0..self.size() {
+            self.weights[(i, i)] = 0.0;
+        }
+    }
+
+    /// `E(s) = -½ sᵀ W s + θᵀ s`
+    pub fn energy(&self, state: &Vector) -> f64 {
+        -0.5 * state.dot(&self.weights.gemv(state)) + self.thresholds.dot(state)
+    }
+
+    fn activate(field: f64, threshold: f64, previous: f64) -> f64 {
+        if field > threshold {
+            1.0
+        } else if field < threshold {
+            -1.0
+        } else {
+            previous
+        }
+    }
+
+    /// Updates every neuron from the current state at once.
+    pub fn step_synchronous(&self, state: &Vector) -> Vector {
+        let field = self.weights.gemv(state);
+        Vector::new((0..self.size()).map(|i| Self::activate(field[i], self.thresholds[i], state[i])).collect())
+    }
+
+    /// Updates every neuron once, one at a time in random order.
+    /// Returns the number of neurons that changed.
+    pub fn step_asynchronous<R: Rng + ?Sized>(&self, state: &mut Vector, rng: &mut R) -> usize {
+        let mut order: Vec<usize> = (0..self.size()).collect();
+        order.shuffle(rng);
+        let mut changed = 0;
+        for i in order {
+            let field = self.weights[i].dot(state);
+            let next = Self::activate(field, self.thresholds[i], state[i]);
+            if next != state[i] {
+                state[i] = next;
+                changed += 1;
+            }
+        }
+        changed
+    }
+
+    /// Runs the dynamics from `probe` until a fixed point is reached or
+    /// `max_steps` sweeps have been made, and returns the final state.
+    pub fn recall(&self, probe: &Vector, rule: UpdateRule, max_steps: usize) -> Vector {
+        self.recall_with_rng(probe, rule, max_steps, &mut rand::rng())
+    }
+
+    /// `recall` with an explicit random number generator for the
+    /// asynchronous update order.
+    pub fn recall_with_rng<R: Rng + ?Sized>(&self, probe: &Vector, rule: UpdateRule, max_steps: usize, rng: &mut R) -> Vector {
+        assert_eq!(probe.len(), self.size(), "Probe size must match the number of neurons");
+        let mut state = probe.clone();
+        for _ in 0..max_steps {
+            match rule {
+                UpdateRule::Synchronous => {
+                    let next = self.step_synchronous(&state);
+                    if next == state {
+                        break;
+                    }
+                    state = next;
+                }
+                UpdateRule::Asynchronous => {
+                    if self.step_asynchronous(&mut state, rng) == 0 {
+                        break;
+                    }
+                }
+            }
+        }
+        state
+    }
+
+    /// True if `state` is unchanged by a synchronous update.
+    pub fn is_fixed_point(&self, state: &Vector) -> bool {
+        self.step_synchronous(state) == *state
+    }
+}
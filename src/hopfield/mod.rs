@@ -0,0 +1,6 @@
+#[allow(clippy::module_inception)]
+pub mod hopfield;
+pub use hopfield::{HopfieldNetwork, LearningRule, UpdateRule};
+
+pub mod modern_hopfield;
+pub use modern_hopfield::ModernHopfield;
@@ -0,0 +1,118 @@
+use crate::math::{Matrix, Vector};
+use crate::neuralnetwork::Layer;
+
+/// Modern (dense) Hopfield network with continuous states.
+///
+/// The update `ξ ← Xᵀ softmax(β X ξ)` retrieves the stored pattern closest
+/// to `ξ` in a single step and can store exponentially many patterns. As a
+/// `Layer` each input row is a query and the stored patterns are learnable.
+pub struct ModernHopfield {
+    pub patterns: Matrix,            // One stored pattern per row
+    pub beta: f64,                   // Inverse temperature
+    pub grad_patterns: Matrix,       // Gradient of the loss w.r.t. the patterns
+    cache: Option<(Matrix, Matrix)>, // (queries, association weights)
+}
+
+impl ModernHopfield {
+    /// Stores `patterns` with inverse temperature `beta`.
+    pub fn new(patterns: &[Vector], beta: f64) -> Self {
+        assert!(!patterns.is_empty(), "At least one pattern is required");
+        assert!(beta > 0.0, "Beta must be positive");
+        let patterns = Matrix::from_vector(patterns.to_vec());
+        let grad_patterns = Matrix::zeros(patterns.row_count(), patterns.cols);
+        Self { patterns, beta, grad_patterns, cache: None }
+    }
+
+    /// Softmax of `β X ξ` for every query row: how strongly each query
+    /// is associated with each stored pattern.
+    pub fn associations(&self, queries: &Matrix) -> Matrix {
+        let mut scores = queries.gemm(&self.patterns.transpose());
+        for row in &mut scores.rows {
+            let max = row.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+            let exp: Vec<f64> = row.iter().map(|s| (self.beta * (s - max)).exp()).collect();
+            let sum: f64 = exp.iter().sum();
+            *row = Vector::new(exp.into_iter().map(|e| e / sum).collect());
+        }
+        scores
+    }
+
+    /// One update step `Xᵀ softmax(β X ξ)`.
+    pub fn retrieve(&self, state: &Vector) -> Vector {
+        let weights = self.associations(&Matrix::from_vector(vec![state.clone()]));
+        weights.gemm(&self.patterns)[0].clone()
+    }
+
+    /// Repeats `retrieve` until the state moves less than `tolerance`
+    /// (Euclidean norm) or `max_steps` updates have been made.
+    pub fn recall(&self, probe: &Vector, max_steps: usize, tolerance: f64) -> Vector {
+        let mut state = probe.clone();
+        for _ in 0..max_steps {
+            let next = self.retrieve(&state);
+            let change = next.add(&state.scale(-1.0)).norm();
+            state = next;
+            if change < tolerance {
+                break;
+            }
+        }
+        state
+    }
+
+    /// `E(ξ) = -1/β log Σ_i exp(β x_iᵀ ξ) + ½ ξᵀξ + 1/β log N + ½ M²`,
+    /// where `M` is the largest pattern norm. `retrieve` never increases it.
+    pub fn energy(&self, state: &Vector) -> f64 {
+        let scores = self.patterns.gemv(state);
+        let max = scores.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        let lse = max + scores.iter().map(|s| (self.beta * (s - max)).exp()).sum::<f64>().ln() / self.beta;
+        let n = self.patterns.row_count() as f64;
+        let m = self.patterns.rows.iter().map(|p| p.norm()).fold(0.0, f64::max);
+        -lse + 0.5 * state.dot(state) + n.ln() / self.beta + 0.5 * m * m
+    }
+}
+
+impl Layer for ModernHopfield {
+    fn name(&self) -> String {
+        format!("ModernHopfield({} patterns x {})", self.patterns.row_count(), self.patterns.cols)
+    }
+
+    fn output_size(&self, input_size: usize) -> Result<usize, String> {
+        if input_size != self.patterns.cols {
+            return Err(format!("expects {} input features but receives {}", self.patterns.cols, input_size));
+        }
+        Ok(input_size)
+    }
+
+    fn forward(&mut self, input: &Matrix, _training: bool) -> Matrix {
+        let weights = self.associations(input);
+        let output = weights.gemm(&self.patterns);
+        self.cache = Some((input.clone(), weights));
+        output
+    }
+
+    fn backward(&mut self, grad_output: &Matrix) -> Matrix {
+        let (queries, weights) = self.cache.as_ref().expect("ModernHopfield::backward called before forward");
+        let grad_weights = grad_output.gemm(&self.patterns.transpose());
+        // Softmax Jacobian per row, then the β scale
+        let mut grad_scores = weights.hadamard(&grad_weights);
+        for (row, (a, da)) in grad_scores.rows.iter_mut().zip(weights.rows.iter().zip(&grad_weights.rows)) {
+            let dot = a.dot(da);
+            for (g, &w) in row.iter_mut().zip(a.iter()) {
+                *g = self.beta * (*g - w * dot);
+            }
+        }
+        self.grad_patterns = weights.transpose().gemm(grad_output);
+        self.grad_patterns.add_assign(&grad_scores.transpose().gemm(queries));
+        grad_scores.gemm(&self.patterns)
+    }
+
+    fn parameters(&self) -> Vec<Matrix> {
+        vec![self.patterns.clone()]
+    }
+
+    fn gradients(&self) -> Vec<Matrix> {
+        vec![self.grad_patterns.clone()]
+    }
+
+    fn set_parameters(&mut self, parameters: &[Matrix]) {
+        self.patterns = parameters[0].clone();
+    }
+}
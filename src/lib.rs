@@ -1,10 +1,20 @@
 pub mod math;
 pub mod perceptron;
-mod utils;
+pub mod utils;
 pub mod neuralnetwork;
 pub mod linear_regression;
 pub mod logistic_regression;
 pub mod svm;
+pub mod autograd;
+pub mod gradcheck;
+pub mod datasets;
+pub mod hopfield;
+pub mod rbm;
+pub mod diffusion;
+pub mod autoencoder;
+pub mod optimize;
+pub mod preprocessing;
+pub mod pipeline;
 // Re-export key components for easier access
 pub use math::{Vector, Matrix};
 pub use perceptron::{Perceptron, MultiClassPerceptron};
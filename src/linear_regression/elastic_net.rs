@@ -0,0 +1,197 @@
+use crate::linear_regression::ridge::center;
+use crate::math::{Matrix, Vector};
+use serde::{Deserialize, Serialize};
+use std::ops::{Deref, DerefMut};
+
+/// Linear regression with a combined L1/L2 penalty, fitted by cyclic
+/// coordinate descent with soft-thresholding.
+///
+/// Minimizes `(1 / 2n) Σ (y - w·x - b)² + α ρ ||w||₁ + (α (1 - ρ) / 2) ||w||²`
+/// where `ρ` is `l1_ratio`; the bias is not penalized. The L1 term drives
+/// coefficients to exactly zero.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ElasticNet {
+    pub weights: Vector, // Model parameters (including bias)
+    pub alpha: f64,      // Overall regularization strength
+    pub l1_ratio: f64,   // Share of the L1 penalty, in [0, 1]
+    pub max_iter: usize, // Maximum number of passes over the coefficients
+    pub tol: f64,        // Stop when the largest update is below tol * max |w|
+    pub n_iter: usize,   // Passes used by the last fit
+    pub converged: bool, // Whether the last fit reached the tolerance
+}
+
+impl ElasticNet {
+    /// Creates an unfitted Elastic Net model with up to 1000 passes and a tolerance of 1e-4.
+    pub fn new(alpha: f64, l1_ratio: f64) -> Self {
+        assert!(alpha >= 0.0, "Regularization strength must be non-negative");
+        assert!((0.0..=1.0).contains(&l1_ratio), "l1_ratio must be in [0, 1]");
+        Self { weights: Vector::new(vec![]), alpha, l1_ratio, max_iter: 1000, tol: 1e-4, n_iter: 0, converged: false }
+    }
+
+    pub fn with_max_iter(mut self, max_iter: usize) -> Self {
+        self.max_iter = max_iter;
+        self
+    }
+
+    pub fn with_tolerance(mut self, tol: f64) -> Self {
+        self.tol = tol;
+        self
+    }
+
+    /// Fits the model by coordinate descent. If the model is already fitted
+    /// on data with the same number of features, the current coefficients
+    /// are used as a warm start.
+    pub fn fit(&mut self, inputs: &[Vector], targets: &Vector) {
+        (self.n_iter, self.converged) =
+            coordinate_descent(&mut self.weights, inputs, targets, self.alpha, self.l1_ratio, self.max_iter, self.tol);
+    }
+
+    /// Fits the model for each `alpha` in turn, warm starting from the
+    /// previous solution, and returns the weights (bias first) along the path.
+    /// Pass the alphas in decreasing order, e.g. from `alpha_grid`. The model
+    /// is left fitted with the last alpha.
+    pub fn path(&mut self, inputs: &[Vector], targets: &Vector, alphas: &[f64]) -> Vec<Vector> {
+        alphas
+            .iter()
+            .map(|&alpha| {
+                self.alpha = alpha;
+                self.fit(inputs, targets);
+                self.weights.clone()
+            })
+            .collect()
+    }
+
+    /// `count` log-spaced alphas from the smallest alpha that zeroes every
+    /// coefficient down to `ratio` times that value.
+    pub fn alpha_grid(inputs: &[Vector], targets: &Vector, l1_ratio: f64, count: usize, ratio: f64) -> Vec<f64> {
+        assert!(l1_ratio > 0.0, "The alpha grid needs an L1 penalty");
+        assert!(count > 0 && ratio > 0.0 && ratio < 1.0, "Need a positive count and a ratio in (0, 1)");
+        let (centered, _, y_mean) = center(inputs, targets);
+        let n = inputs.len() as f64;
+        let y_centered = Vector::new(targets.iter().map(|y| y - y_mean).collect());
+        let correlations = Matrix::from_vector(centered).transpose().gemv(&y_centered);
+        let alpha_max = correlations.iter().fold(0.0f64, |m, c| m.max(c.abs())) / (n * l1_ratio);
+        if count == 1 {
+            return vec![alpha_max];
+        }
+        (0..count).map(|k| alpha_max * ratio.powf(k as f64 / (count - 1) as f64)).collect()
+    }
+
+    /// Predict outputs for given inputs.
+    pub fn predict(&self, input: &Vector) -> f64 {
+        predict(&self.weights, input)
+    }
+}
+
+/// Linear regression with an L1 penalty: an `ElasticNet` with `l1_ratio = 1`.
+///
+/// Minimizes `(1 / 2n) Σ (y - w·x - b)² + α ||w||₁`. Dereferences to the
+/// wrapped `ElasticNet` for its fields, `fit`, `path` and `predict`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Lasso(pub ElasticNet);
+
+impl Lasso {
+    /// Creates an unfitted Lasso model with up to 1000 passes and a tolerance of 1e-4.
+    pub fn new(alpha: f64) -> Self {
+        Self(ElasticNet::new(alpha, 1.0))
+    }
+
+    pub fn with_max_iter(self, max_iter: usize) -> Self {
+        Self(self.0.with_max_iter(max_iter))
+    }
+
+    pub fn with_tolerance(self, tol: f64) -> Self {
+        Self(self.0.with_tolerance(tol))
+    }
+
+    /// See `ElasticNet::alpha_grid`.
+    pub fn alpha_grid(inputs: &[Vector], targets: &Vector, count: usize, ratio: f64) -> Vec<f64> {
+        ElasticNet::alpha_grid(inputs, targets, 1.0, count, ratio)
+    }
+}
+
+impl Deref for Lasso {
+    type Target = ElasticNet;
+
+    fn deref(&self) -> &ElasticNet {
+        &self.0
+    }
+}
+
+impl DerefMut for Lasso {
+    fn deref_mut(&mut self) -> &mut ElasticNet {
+        &mut self.0
+    }
+}
+
+fn predict(weights: &Vector, input: &Vector) -> f64 {
+    weights[0] + weights.data[1..].iter().zip(input.iter()).map(|(w, x)| w * x).sum::<f64>()
+}
+
+/// `sign(z) * max(|z| - threshold, 0)`
+fn soft_threshold(z: f64, threshold: f64) -> f64 {
+    if z > threshold {
+        z - threshold
+    } else if z < -threshold {
+        z + threshold
+    } else {
+        0.0
+    }
+}
+
+/// Cyclic coordinate descent on centered data. `weights` (bias first) is
+/// used as the starting point when it has the right size and is replaced
+/// by the solution. Returns the number of passes and whether it converged.
+fn coordinate_descent(
+    weights: &mut Vector,
+    inputs: &[Vector],
+    targets: &Vector,
+    alpha: f64,
+    l1_ratio: f64,
+    max_iter: usize,
+    tol: f64,
+) -> (usize, bool) {
+    assert!(inputs.len() == targets.len(), "Mismatched input and target sizes!");
+    let (centered, x_mean, y_mean) = center(inputs, targets);
+    let n = inputs.len() as f64;
+    let d = x_mean.len();
+    let columns = Matrix::from_vector(centered).transpose().rows;
+    let squared_norms: Vec<f64> = columns.iter().map(|c| c.dot(c)).collect();
+    let l1 = n * alpha * l1_ratio;
+    let l2 = n * alpha * (1.0 - l1_ratio);
+
+    let mut w = if weights.len() == d + 1 { Vector::new(weights.data[1..].to_vec()) } else { Vector::zeros(d) };
+    // residual = y - Xw on centered data
+    let mut residual = Vector::new(targets.iter().map(|y| y - y_mean).collect());
+    for (j, column) in columns.iter().enumerate() {
+        residual.add_assign(column, -w[j]);
+    }
+
+    let mut iterations = 0;
+    let mut converged = false;
+    while iterations < max_iter && !converged {
+        iterations += 1;
+        let mut max_delta: f64 = 0.0;
+        let mut max_weight: f64 = 0.0;
+        for j in 0..d {
+            let old = w[j];
+            let new = if squared_norms[j] == 0.0 {
+                0.0
+            } else {
+                let rho = columns[j].dot(&residual) + squared_norms[j] * old;
+                soft_threshold(rho, l1) / (squared_norms[j] + l2)
+            };
+            if new != old {
+                residual.add_assign(&columns[j], old - new);
+                w[j] = new;
+            }
+            max_delta = max_delta.max((new - old).abs());
+            max_weight = max_weight.max(new.abs());
+        }
+        converged = max_delta <= tol * max_weight || max_delta == 0.0;
+    }
+
+    let bias = y_mean - w.dot(&x_mean);
+    *weights = Vector::new(std::iter::once(bias).chain(w.data).collect());
+    (iterations, converged)
+}
@@ -1,5 +1,7 @@
 use crate::math::{Matrix, Vector};
 use rand::prelude::SliceRandom;
+use serde::{Deserialize, Serialize};
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LinearRegression {
     pub weights: Vector, // Model parameters (including bias)
 }
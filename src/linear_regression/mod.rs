@@ -1,3 +1,7 @@
 pub mod linear_regression;
+pub mod ridge;
+pub mod elastic_net;
 
-pub use linear_regression::LinearRegression;
\ No newline at end of file
+pub use linear_regression::LinearRegression;
+pub use ridge::Ridge;
+pub use elastic_net::{ElasticNet, Lasso};
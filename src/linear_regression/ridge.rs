@@ -0,0 +1,83 @@
+use crate::math::{Matrix, Vector};
+use rand::prelude::SliceRandom;
+use serde::{Deserialize, Serialize};
+
+/// Linear regression with an L2 penalty on the coefficients.
+///
+/// Minimizes `(1 / 2n) Σ (y - w·x - b)² + (λ / 2) ||w||²`; the bias is not penalized.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Ridge {
+    pub weights: Vector, // Model parameters (including bias)
+    pub lambda: f64,     // L2 regularization strength
+}
+
+impl Ridge {
+    /// Creates an unfitted Ridge model.
+    pub fn new(lambda: f64) -> Self {
+        assert!(lambda >= 0.0, "Regularization strength must be non-negative");
+        Self { weights: Vector::new(vec![]), lambda }
+    }
+
+    /// Closed-form fit: solves `(XᵀX + nλI) w = Xᵀy` on centered data with a
+    /// Cholesky decomposition, then recovers the bias from the means.
+    /// Fails if `XᵀX` is singular and `lambda` is zero.
+    pub fn fit(&mut self, inputs: &[Vector], targets: &Vector) -> Result<(), String> {
+        assert!(inputs.len() == targets.len(), "Mismatched input and target sizes!");
+        let (centered, x_mean, y_mean) = center(inputs, targets);
+        let n = inputs.len() as f64;
+
+        let x_matrix = Matrix::from_vector(centered);
+        let mut gram = x_matrix.transpose().gemm(&x_matrix);
+        for j in 0..gram.row_count() {
+            gram[(j, j)] += n * self.lambda;
+        }
+        let y_centered = Vector::new(targets.iter().map(|y| y - y_mean).collect());
+        let coefficients = gram.cholesky_solve(&x_matrix.transpose().gemv(&y_centered))?;
+
+        let bias = y_mean - coefficients.dot(&x_mean);
+        self.weights = Vector::new(std::iter::once(bias).chain(coefficients.data).collect());
+        Ok(())
+    }
+
+    /// Trains the model using Stochastic Gradient Descent (SGD), shrinking
+    /// the coefficients (not the bias) by `learning_rate * lambda` at every step.
+    pub fn fit_sgd(&mut self, inputs: &[Vector], targets: &Vector, learning_rate: f64, epochs: usize) {
+        assert!(inputs.len() == targets.len(), "Mismatched input and target sizes!");
+        let mut rng = rand::rng();
+        let n = inputs.len();
+        self.weights = Vector::zeros(inputs[0].len() + 1);
+
+        for _ in 0..epochs {
+            let mut indices: Vec<usize> = (0..n).collect();
+            indices.shuffle(&mut rng);
+            for &i in indices.iter() {
+                let error = targets[i] - self.predict(&inputs[i]);
+                self.weights[0] += learning_rate * error;
+                for j in 1..self.weights.len() {
+                    let w = self.weights[j];
+                    self.weights[j] += learning_rate * (error * inputs[i][j - 1] - self.lambda * w);
+                }
+            }
+        }
+    }
+
+    /// Predict outputs for given inputs.
+    pub fn predict(&self, input: &Vector) -> f64 {
+        self.weights[0] + self.weights.data[1..].iter().zip(input.iter()).map(|(w, x)| w * x).sum::<f64>()
+    }
+}
+
+/// Subtracts the column means from the inputs and returns the centered
+/// inputs, the input means and the target mean. Fitting on centered data
+/// keeps the bias out of the penalty.
+pub(super) fn center(inputs: &[Vector], targets: &Vector) -> (Vec<Vector>, Vector, f64) {
+    assert!(!inputs.is_empty(), "Cannot fit on an empty dataset");
+    let n = inputs.len() as f64;
+    let mut x_mean = Vector::zeros(inputs[0].len());
+    for x in inputs {
+        x_mean.add_assign(x, 1.0 / n);
+    }
+    let centered = inputs.iter().map(|x| x.add(&x_mean.scale(-1.0))).collect();
+    let y_mean = targets.iter().sum::<f64>() / n;
+    (centered, x_mean, y_mean)
+}
@@ -1,7 +1,29 @@
 use crate::math::{Vector, Matrix};
 use crate::utils::activation::sigmoid;
 use rand::{prelude::SliceRandom, Rng};
+use crate::optimize::{minimize_with_options, Method, Options};
+use serde::{Deserialize, Serialize};
 
+/// Outcome of an iterative solver.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Convergence {
+    pub converged: bool,    // Whether the gradient tolerance was reached
+    pub iterations: usize,  // Iterations performed
+    pub gradient_norm: f64, // Largest absolute component of the final gradient
+}
+
+impl Convergence {
+    fn new(gradient: &Vector, tol: f64, iterations: usize) -> Self {
+        let gradient_norm = max_norm(gradient);
+        Self { converged: gradient_norm < tol, iterations, gradient_norm }
+    }
+}
+
+fn max_norm(v: &Vector) -> f64 {
+    v.iter().fold(0.0, |m, x| m.max(x.abs()))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LogisticRegression {
     pub weights: Vector, // Model parameters (including bias)
     pub l1_lambda: f64,  // L1 regularization strength
@@ -30,21 +52,129 @@ impl LogisticRegression {
             indices.shuffle(&mut rng);
             
             for &i in indices.iter() {
-                let mut x = inputs[i].clone();
+                let gradient = self.gradient(std::slice::from_ref(&inputs[i]), &Vector::new(vec![targets[i]]));
+                self.weights.add_assign(&gradient, -learning_rate);
+            }
+        }
+    }
+
+    /// Trains the model with Newton's method (iteratively reweighted least
+    /// squares), solving the Newton system with a Cholesky decomposition and
+    /// halving the step until the loss decreases. Starts from zero weights,
+    /// so the result is deterministic.
+    ///
+    /// Stops once the largest gradient component is below `tol`. Only the L2
+    /// penalty is supported; scikit-learn's `C` corresponds to
+    /// `l2_lambda = 1 / (2 n C)`. Fails if the Hessian is singular or no
+    /// step along the Newton direction decreases the loss, e.g. on
+    /// separable data without an L2 penalty.
+    pub fn fit_newton(&mut self, inputs: &[Vector], targets: &Vector, tol: f64, max_iter: usize) -> Result<Convergence, String> {
+        assert!(inputs.len() == targets.len(), "Mismatched input and target sizes!");
+        assert!(self.l1_lambda == 0.0, "Newton's method requires a smooth loss; set l1_lambda to 0");
+        let n = inputs.len() as f64;
+        self.weights = Vector::zeros(inputs[0].len() + 1);
+
+        let mut gradient = self.gradient(inputs, targets);
+        let mut iterations = 0;
+        while iterations < max_iter && max_norm(&gradient) >= tol {
+            // H = Xᵀ diag(p (1 - p)) X / n + 2λ (bias excluded)
+            let d = self.weights.len();
+            let mut hessian = Matrix::zeros(d, d);
+            for input in inputs {
+                let mut x = input.clone();
                 x.data.insert(0, 1.0); // Add bias term
-                
-                let prediction = sigmoid(self.weights.dot(&x));
-                let error = targets[i] - prediction;
-                let mut gradient = x.scale(error * learning_rate);
+                let p = sigmoid(self.weights.dot(&x));
+                let mut outer = x.outer_product(&x);
+                outer.scale(p * (1.0 - p) / n);
+                hessian.add_assign(&outer);
+            }
+            for j in 1..d {
+                hessian[(j, j)] += 2.0 * self.l2_lambda;
+            }
+            let step = hessian
+                .cholesky_solve(&gradient)
+                .map_err(|e| format!("Newton step failed at iteration {}: {}", iterations, e))?;
+            if !self.line_search(inputs, targets, &gradient, &step.scale(-1.0)) {
+                return Err(format!("Line search failed at iteration {}", iterations));
+            }
+            gradient = self.gradient(inputs, targets);
+            iterations += 1;
+        }
+        Ok(Convergence::new(&gradient, tol, iterations))
+    }
 
-                // Apply L1 (Lasso) and L2 (Ridge) regularization
-                for j in 1..self.weights.len() { // Skip bias term
-                    gradient[j] -= learning_rate * (self.l1_lambda * self.weights[j].signum() + 2.0 * self.l2_lambda * self.weights[j]);
-                }
+    /// Trains the model with L-BFGS (memory of 10 updates) from
+    /// `optimize::minimize`. Starts from zero weights, so the result is
+    /// deterministic. Stops once the largest gradient component is below
+    /// `tol`. Only the L2 penalty is supported.
+    pub fn fit_lbfgs(&mut self, inputs: &[Vector], targets: &Vector, tol: f64, max_iter: usize) -> Convergence {
+        assert!(inputs.len() == targets.len(), "Mismatched input and target sizes!");
+        assert!(self.l1_lambda == 0.0, "L-BFGS requires a smooth loss; set l1_lambda to 0");
+        let l2_lambda = self.l2_lambda;
+        let model = |weights: &Vector| Self { weights: weights.clone(), l1_lambda: 0.0, l2_lambda };
+        let result = minimize_with_options(
+            |w| model(w).loss(inputs, targets),
+            |w| model(w).gradient(inputs, targets),
+            &Vector::zeros(inputs[0].len() + 1),
+            Method::LBFGS { memory: 10 },
+            Options { max_iter, tol },
+        );
+        let converged = result.converged();
+        self.weights = result.x;
+        Convergence { converged, iterations: result.iterations, gradient_norm: result.gradient_norm }
+    }
 
-                self.weights = self.weights.add(&gradient);
+    /// Backtracking (Armijo) line search along `direction` starting from a
+    /// unit step; updates the weights in place. Returns false, with the
+    /// weights unchanged, if no step of at least 2⁻⁴⁹ decreases the loss enough.
+    fn line_search(&mut self, inputs: &[Vector], targets: &Vector, gradient: &Vector, direction: &Vector) -> bool {
+        let start = self.weights.clone();
+        let loss = self.loss(inputs, targets);
+        let slope = gradient.dot(direction);
+        let mut t = 1.0;
+        for _ in 0..50 {
+            self.weights = start.add(&direction.scale(t));
+            if self.loss(inputs, targets) <= loss + 1e-4 * t * slope {
+                return true;
             }
+            t *= 0.5;
+        }
+        self.weights = start;
+        false
+    }
+
+    /// Mean negative log-likelihood plus the L1/L2 penalties (bias excluded).
+    pub fn loss(&self, inputs: &[Vector], targets: &Vector) -> f64 {
+        assert!(inputs.len() == targets.len(), "Mismatched input and target sizes!");
+        let mut loss = 0.0;
+        for (x, &y) in inputs.iter().zip(targets.iter()) {
+            let p = self.predict_proba(x).clamp(1e-15, 1.0 - 1e-15);
+            loss -= y * p.ln() + (1.0 - y) * (1.0 - p).ln();
         }
+        loss /= inputs.len() as f64;
+        for j in 1..self.weights.len() { // Skip bias term
+            loss += self.l1_lambda * self.weights[j].abs() + self.l2_lambda * self.weights[j].powi(2);
+        }
+        loss
+    }
+
+    /// Gradient of `loss` with respect to the weights (bias first).
+    pub fn gradient(&self, inputs: &[Vector], targets: &Vector) -> Vector {
+        assert!(inputs.len() == targets.len(), "Mismatched input and target sizes!");
+        let n = inputs.len() as f64;
+        let mut gradient = Vector::zeros(self.weights.len());
+        for (input, &y) in inputs.iter().zip(targets.iter()) {
+            let mut x = input.clone();
+            x.data.insert(0, 1.0); // Add bias term
+            let error = y - sigmoid(self.weights.dot(&x));
+            gradient.add_assign(&x, -error / n);
+        }
+
+        // Apply L1 (Lasso) and L2 (Ridge) regularization
+        for j in 1..self.weights.len() { // Skip bias term
+            gradient[j] += self.l1_lambda * self.weights[j].signum() + 2.0 * self.l2_lambda * self.weights[j];
+        }
+        gradient
     }
 
     /// Predicts the probability of class 1
@@ -90,22 +220,44 @@ impl SoftmaxRegression {
             indices.shuffle(&mut rng);
             
             for &i in indices.iter() {
-                let mut x = inputs[i].clone();
-                x.data.insert(0, 1.0); // Add bias term
-                
-                let logits = self.weights.gemv(&x);
-                let probabilities = Self::softmax(&logits);
-                
-                let mut error = probabilities;
-                error[targets[i]] -= 1.0; // One-hot encoding error adjustment
-                
-                let mut gradient = error.outer_product(&x);
-                gradient.scale(-1.0 * learning_rate);
-                self.weights = self.weights.add(&gradient);
+                let mut gradient = self.gradient(std::slice::from_ref(&inputs[i]), &targets[i..=i]);
+                gradient.scale(-learning_rate);
+                self.weights.add_assign(&gradient);
             }
         }
     }
 
+    /// Mean cross-entropy loss.
+    pub fn loss(&self, inputs: &[Vector], targets: &[usize]) -> f64 {
+        assert!(inputs.len() == targets.len(), "Mismatched input and target sizes!");
+        let total: f64 = inputs
+            .iter()
+            .zip(targets)
+            .map(|(x, &t)| -self.predict_proba(x)[t].max(1e-15).ln())
+            .sum();
+        total / inputs.len() as f64
+    }
+
+    /// Gradient of `loss` with respect to the weight matrix.
+    pub fn gradient(&self, inputs: &[Vector], targets: &[usize]) -> Matrix {
+        assert!(inputs.len() == targets.len(), "Mismatched input and target sizes!");
+        let n = inputs.len() as f64;
+        let mut gradient = Matrix::zeros(self.weights.row_count(), self.weights.cols);
+        for (input, &target) in inputs.iter().zip(targets) {
+            let mut x = input.clone();
+            x.data.insert(0, 1.0); // Add bias term
+
+            let logits = self.weights.gemv(&x);
+            let mut error = Self::softmax(&logits);
+            error[target] -= 1.0; // One-hot encoding error adjustment
+
+            let mut sample_gradient = error.outer_product(&x);
+            sample_gradient.scale(1.0 / n);
+            gradient.add_assign(&sample_gradient);
+        }
+        gradient
+    }
+
     /// Predicts class probabilities
     pub fn predict_proba(&self, input: &Vector) -> Vector {
         let mut extended_input = input.clone();
@@ -1,3 +1,3 @@
 pub mod logistic_regression;
 
-pub use logistic_regression::{LogisticRegression, SoftmaxRegression};
\ No newline at end of file
+pub use logistic_regression::{Convergence, LogisticRegression, SoftmaxRegression};
\ No newline at end of file
@@ -4,8 +4,9 @@ use std::ops::IndexMut;
 use std::fmt;
 use rand::Rng;
 use std::cmp::PartialEq;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 
 
 pub struct Matrix {
@@ -83,6 +84,45 @@ impl Matrix {
         }
     }
 
+    // Returns a new matrix difference
+    pub fn sub(&self, other: &Self) -> Self {
+        assert!(self.row_count() == other.row_count() && self.col_count() == other.col_count(),
+                "Dimension mismatch in matrix subtraction");
+        Self {
+            rows: self.rows.iter().zip(&other.rows).map(|(a, b)| a.add(&b.scale(-1.0))).collect(),
+            cols: self.cols,
+        }
+    }
+
+    /// Element-wise (Hadamard) product
+    pub fn hadamard(&self, other: &Self) -> Self {
+        assert!(self.row_count() == other.row_count() && self.col_count() == other.col_count(),
+                "Dimension mismatch in element-wise product");
+        Self {
+            rows: self.rows.iter().zip(&other.rows)
+                .map(|(a, b)| Vector::new(a.iter().zip(b).map(|(x, y)| x * y).collect()))
+                .collect(),
+            cols: self.cols,
+        }
+    }
+
+    /// Applies `f` to every element, returning a new matrix
+    pub fn map<F: Fn(f64) -> f64>(&self, f: F) -> Self {
+        Self {
+            rows: self.rows.iter().map(|row| Vector::new(row.iter().map(|&x| f(x)).collect())).collect(),
+            cols: self.cols,
+        }
+    }
+
+    /// Sums each column, returning a vector of length `cols`
+    pub fn column_sums(&self) -> Vector {
+        let mut sums = Vector::zeros(self.cols);
+        for row in &self.rows {
+            sums.add_assign(row, 1.0);
+        }
+        sums
+    }
+
     // Matrix-vector multiplication: y = Ax + y
     pub fn gemv(&self, x: &Vector) -> Vector {
         assert_eq!(self.col_count(), x.len(), "Incompatible matrix dimensions for multiplication");
@@ -302,6 +342,21 @@ impl Matrix {
         Vector::new((0..self.row_count()).map(|i| self[(i, col)]).collect())
     }
 
+    /// Returns columns `start..end` as a new matrix
+    pub fn columns(&self, start: usize, end: usize) -> Matrix {
+        assert!(start <= end && end <= self.cols, "Column range out of bounds.");
+        Matrix { rows: self.rows.iter().map(|row| Vector::new(row.data[start..end].to_vec())).collect(), cols: end - start }
+    }
+
+    /// Overwrites the columns starting at `start` with `block`
+    pub fn set_columns(&mut self, start: usize, block: &Matrix) {
+        assert_eq!(self.row_count(), block.row_count(), "Block must have the same number of rows.");
+        assert!(start + block.cols <= self.cols, "Column range out of bounds.");
+        for (row, values) in self.rows.iter_mut().zip(&block.rows) {
+            row.data[start..start + block.cols].copy_from_slice(&values.data);
+        }
+    }
+
     /// Sets a column in the matrix from a Vector
     // fn set_column(&mut self, col: usize, v: &Vector) {
     //     assert_eq!(self.row_count(), v.len(), "Vector length must match matrix row count.");
@@ -384,6 +439,80 @@ impl Matrix {
         Ok(x)
     }
 
+    /// Cholesky decomposition of a symmetric positive definite matrix.
+    /// Returns the lower triangular L such that A = L * L^T.
+    pub fn cholesky(&self) -> Result<Matrix, &'static str> {
+        let n = self.row_count();
+        if self.cols != n {
+            return Err("Matrix must be square");
+        }
+        let mut l = Matrix::zeros(n, n);
+        for i in 0..n {
+            for j in 0..=i {
+                let sum: f64 = (0..j).map(|k| l[(i, k)] * l[(j, k)]).sum();
+                if i == j {
+                    let diagonal = self[(i, i)] - sum;
+                    if diagonal <= 1e-12 {
+                        return Err("Matrix is not positive definite");
+                    }
+                    l[(i, i)] = diagonal.sqrt();
+                } else {
+                    l[(i, j)] = (self[(i, j)] - sum) / l[(j, j)];
+                }
+            }
+        }
+        Ok(l)
+    }
+
+    /// Solves A x = b for a symmetric positive definite A using its
+    /// Cholesky decomposition and forward/back substitution.
+    pub fn cholesky_solve(&self, b: &Vector) -> Result<Vector, &'static str> {
+        let l = self.cholesky()?;
+        let n = l.row_count();
+        if b.len() != n {
+            return Err("Right-hand side has the wrong length");
+        }
+        // Forward substitution: L y = b
+        let mut y = vec![0.0; n];
+        for i in 0..n {
+            let sum: f64 = (0..i).map(|k| l[(i, k)] * y[k]).sum();
+            y[i] = (b[i] - sum) / l[(i, i)];
+        }
+        // Back substitution: L^T x = y
+        let mut x = vec![0.0; n];
+        for i in (0..n).rev() {
+            let sum: f64 = (i + 1..n).map(|k| l[(k, i)] * x[k]).sum();
+            x[i] = (y[i] - sum) / l[(i, i)];
+        }
+        Ok(Vector::new(x))
+    }
+
+    /// LU factorization with partial pivoting, `PA = LU`, which can be reused
+    /// to solve several systems with `A` or `A^T`.
+    pub fn lu_factorization(&self) -> Result<LuFactorization, &'static str> {
+        let n = self.row_count();
+        if self.cols != n {
+            return Err("Matrix must be square");
+        }
+        let mut lu = self.clone();
+        let mut permutation: Vec<usize> = (0..n).collect();
+        for k in 0..n {
+            let pivot_row = (k..n).fold(k, |best, i| if lu[(i, k)].abs() > lu[(best, k)].abs() { i } else { best });
+            if lu[(pivot_row, k)].abs() < 1e-10 {
+                return Err("Singular matrix; no unique solution exists");
+            }
+            lu.swap_rows(k, pivot_row);
+            permutation.swap(k, pivot_row);
+            for i in k + 1..n {
+                lu[(i, k)] /= lu[(k, k)];
+                for j in k + 1..n {
+                    lu[(i, j)] -= lu[(i, k)] * lu[(k, j)];
+                }
+            }
+        }
+        Ok(LuFactorization { lu, permutation })
+    }
+
  /// Implements the Gram–Schmidt process to perform QR decomposition.
     /// Returns (Q, R) such that A = Q * R.
     pub fn gram_schmidt(&self) -> (Matrix, Matrix) {
@@ -464,6 +593,57 @@ impl Matrix {
 }
 
 
+/// `PA = LU` with unit lower triangular `L` and upper triangular `U` stored
+/// in one matrix; row `k` of `PA` is row `permutation[k]` of `A`.
+#[derive(Debug, Clone)]
+pub struct LuFactorization {
+    lu: Matrix,
+    permutation: Vec<usize>,
+}
+
+impl LuFactorization {
+    /// Solves `A x = b`.
+    pub fn solve(&self, b: &Vector) -> Vector {
+        let n = self.permutation.len();
+        assert!(b.len() == n, "Right-hand side has the wrong length");
+        // Forward substitution: L y = P b
+        let mut x = vec![0.0; n];
+        for i in 0..n {
+            let sum: f64 = (0..i).map(|k| self.lu[(i, k)] * x[k]).sum();
+            x[i] = b[self.permutation[i]] - sum;
+        }
+        // Back substitution: U x = y
+        for i in (0..n).rev() {
+            let sum: f64 = (i + 1..n).map(|k| self.lu[(i, k)] * x[k]).sum();
+            x[i] = (x[i] - sum) / self.lu[(i, i)];
+        }
+        Vector::new(x)
+    }
+
+    /// Solves `A^T x = b`, using `A^T = U^T L^T P`.
+    pub fn solve_transpose(&self, b: &Vector) -> Vector {
+        let n = self.permutation.len();
+        assert!(b.len() == n, "Right-hand side has the wrong length");
+        // Forward substitution: U^T w = b
+        let mut w = vec![0.0; n];
+        for i in 0..n {
+            let sum: f64 = (0..i).map(|k| self.lu[(k, i)] * w[k]).sum();
+            w[i] = (b[i] - sum) / self.lu[(i, i)];
+        }
+        // Back substitution: L^T v = w
+        for i in (0..n).rev() {
+            let sum: f64 = (i + 1..n).map(|k| self.lu[(k, i)] * w[k]).sum();
+            w[i] -= sum;
+        }
+        // P x = v
+        let mut x = vec![0.0; n];
+        for (k, &row) in self.permutation.iter().enumerate() {
+            x[row] = w[k];
+        }
+        Vector::new(x)
+    }
+}
+
 impl Index<usize> for Matrix {
     type Output = Vector;
 
@@ -1,5 +1,7 @@
 pub mod vector;
 pub mod matrix;
+pub mod sparse;
 
 pub use vector::Vector;
-pub use matrix::Matrix;
+pub use matrix::{LuFactorization, Matrix};
+pub use sparse::SparseMatrix;
@@ -0,0 +1,90 @@
+use super::{Matrix, Vector};
+use serde::{Deserialize, Serialize};
+
+/// Sparse matrix in compressed sparse row (CSR) form: the non-zeros of row
+/// `i` are `values[indptr[i]..indptr[i + 1]]` in the columns
+/// `indices[indptr[i]..indptr[i + 1]]`, sorted by column.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SparseMatrix {
+    pub indptr: Vec<usize>,
+    pub indices: Vec<usize>,
+    pub values: Vec<f64>,
+    pub cols: usize,
+}
+
+impl SparseMatrix {
+    /// Creates a matrix with no rows and `cols` columns.
+    pub fn new(cols: usize) -> Self {
+        Self { indptr: vec![0], indices: Vec::new(), values: Vec::new(), cols }
+    }
+
+    /// Keeps the non-zero entries of a dense matrix.
+    pub fn from_dense(matrix: &Matrix) -> Self {
+        let mut sparse = Self::new(matrix.col_count());
+        for row in &matrix.rows {
+            sparse.push_row(row.iter().copied().enumerate().filter(|&(_, v)| v != 0.0));
+        }
+        sparse
+    }
+
+    /// Appends a row given as `(column, value)` pairs in any order.
+    pub fn push_row(&mut self, entries: impl IntoIterator<Item = (usize, f64)>) {
+        let mut entries: Vec<(usize, f64)> = entries.into_iter().collect();
+        entries.sort_by_key(|&(j, _)| j);
+        assert!(entries.iter().all(|&(j, _)| j < self.cols), "Column index out of bounds");
+        assert!(entries.windows(2).all(|w| w[0].0 != w[1].0), "Duplicate column index in row");
+        for (j, v) in entries {
+            self.indices.push(j);
+            self.values.push(v);
+        }
+        self.indptr.push(self.indices.len());
+    }
+
+    pub fn row_count(&self) -> usize {
+        self.indptr.len() - 1
+    }
+
+    pub fn col_count(&self) -> usize {
+        self.cols
+    }
+
+    /// Number of stored entries.
+    pub fn nnz(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Column indices and values of row `i`.
+    pub fn row(&self, i: usize) -> (&[usize], &[f64]) {
+        let range = self.indptr[i]..self.indptr[i + 1];
+        (&self.indices[range.clone()], &self.values[range])
+    }
+
+    pub fn get(&self, i: usize, j: usize) -> f64 {
+        let (indices, values) = self.row(i);
+        indices.binary_search(&j).map_or(0.0, |k| values[k])
+    }
+
+    /// Matrix-vector product.
+    pub fn gemv(&self, v: &Vector) -> Vector {
+        assert_eq!(v.len(), self.cols, "Vector length must match matrix column count");
+        Vector::new(
+            (0..self.row_count())
+                .map(|i| {
+                    let (indices, values) = self.row(i);
+                    indices.iter().zip(values).map(|(&j, x)| x * v[j]).sum()
+                })
+                .collect(),
+        )
+    }
+
+    pub fn to_dense(&self) -> Matrix {
+        let mut dense = Matrix::zeros(self.row_count(), self.cols);
+        for i in 0..self.row_count() {
+            let (indices, values) = self.row(i);
+            for (&j, &v) in indices.iter().zip(values) {
+                dense[(i, j)] = v;
+            }
+        }
+        dense
+    }
+}
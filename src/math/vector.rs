@@ -2,8 +2,9 @@ use rand::Rng; // Import random number generator
 use std::ops::IndexMut;
 use std::ops::Index;
 use super::Matrix;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Vector {
     pub data: Vec<f64>,
 }
@@ -0,0 +1,464 @@
+use crate::math::{Matrix, Vector};
+use crate::neuralnetwork::layers::{Dense, Layer, ReLU, TimeDistributed};
+use crate::neuralnetwork::normalization::LayerNorm;
+use crate::utils::initializer::Initializer;
+use rand::Rng;
+
+/// Restricts which positions a query may attend to.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Mask {
+    /// Position `i` attends only to positions `j <= i`.
+    Causal,
+    /// `allowed[i][j]` tells whether position `i` may attend to position `j`.
+    Custom(Vec<Vec<bool>>),
+}
+
+impl Mask {
+    pub fn allows(&self, i: usize, j: usize) -> bool {
+        match self {
+            Mask::Causal => j <= i,
+            Mask::Custom(allowed) => allowed[i][j],
+        }
+    }
+}
+
+/// Scaled dot-product attention `softmax(Q Kᵀ / √d) V` for one sequence.
+///
+/// `query` and `key` have one row per position and `d` columns. Returns the
+/// attended values and the attention weights (one row per query). A query
+/// whose every key is masked receives zero weights.
+pub fn scaled_dot_product_attention(query: &Matrix, key: &Matrix, value: &Matrix, mask: Option<&Mask>) -> (Matrix, Matrix) {
+    assert_eq!(query.col_count(), key.col_count(), "Queries and keys must have the same dimension");
+    assert_eq!(key.row_count(), value.row_count(), "Every key needs a value");
+    let scale = 1.0 / (query.col_count() as f64).sqrt();
+    let mut scores = query.gemm(&key.transpose());
+    for (i, row) in scores.rows.iter_mut().enumerate() {
+        let allowed: Vec<bool> = (0..row.len()).map(|j| mask.is_none_or(|m| m.allows(i, j))).collect();
+        let max = row.iter().zip(&allowed).filter(|(_, a)| **a).map(|(s, _)| *s).fold(f64::NEG_INFINITY, f64::max);
+        let exp: Vec<f64> =
+            row.iter().zip(&allowed).map(|(s, &a)| if a { ((s - max) * scale).exp() } else { 0.0 }).collect();
+        let sum: f64 = exp.iter().sum();
+        *row = Vector::new(exp.into_iter().map(|e| if sum > 0.0 { e / sum } else { 0.0 }).collect());
+    }
+    (scores.gemm(value), scores)
+}
+
+/// Backward pass of `scaled_dot_product_attention` given its attention
+/// weights. Returns the gradients w.r.t. query, key and value.
+fn attention_backward(query: &Matrix, key: &Matrix, value: &Matrix, weights: &Matrix, grad_output: &Matrix) -> (Matrix, Matrix, Matrix) {
+    let scale = 1.0 / (query.col_count() as f64).sqrt();
+    let grad_value = weights.transpose().gemm(grad_output);
+    let grad_weights = grad_output.gemm(&value.transpose());
+    // Softmax Jacobian per row: dS = A ⊙ (dA - Σ_j dA_j A_j), then the 1/√d scale
+    let mut grad_scores = weights.hadamard(&grad_weights);
+    for (row, (a, da)) in grad_scores.rows.iter_mut().zip(weights.rows.iter().zip(&grad_weights.rows)) {
+        let dot = a.dot(da);
+        for (g, &w) in row.iter_mut().zip(a.iter()) {
+            *g = (*g - w * dot) * scale;
+        }
+    }
+    (grad_scores.gemm(key), grad_scores.transpose().gemm(query), grad_value)
+}
+
+/// `x W + b`
+fn affine(x: &Matrix, weights: &Matrix, bias: &Matrix) -> Matrix {
+    let mut output = x.gemm(weights);
+    for row in &mut output.rows {
+        row.add_assign(&bias[0], 1.0);
+    }
+    output
+}
+
+/// Values saved by the forward pass for one sequence.
+struct AttentionCache {
+    input: Matrix,
+    query: Matrix,
+    key: Matrix,
+    value: Matrix,
+    weights: Vec<Matrix>, // Attention weights of each head
+    heads: Matrix,        // Concatenated head outputs
+}
+
+/// Multi-head self-attention over sequences of `model_dim` features.
+///
+/// Input rows hold one sequence each (`T x model_dim` values, see
+/// `pack_sequences`); the output has the same shape. The projections are
+/// stored in the order query, key, value, output.
+pub struct MultiHeadAttention {
+    pub model_dim: usize,
+    pub heads: usize,
+    pub mask: Option<Mask>,
+    pub weights: Vec<Matrix>,      // Four model_dim x model_dim projections
+    pub biases: Vec<Matrix>,       // Four 1 x model_dim biases
+    pub grad_weights: Vec<Matrix>, // Gradients of the loss w.r.t. the projections
+    pub grad_biases: Vec<Matrix>,  // Gradients of the loss w.r.t. the biases
+    cache: Option<Vec<AttentionCache>>,
+}
+
+impl MultiHeadAttention {
+    /// Creates an attention layer with Xavier-uniform projections and zero biases.
+    /// `model_dim` must be divisible by `heads`.
+    pub fn new(model_dim: usize, heads: usize) -> Self {
+        Self::with_initializer(model_dim, heads, &Initializer::XavierUniform, &mut rand::rng())
+    }
+
+    /// Creates an attention layer whose projections are drawn from `weights`.
+    pub fn with_initializer<R: Rng + ?Sized>(model_dim: usize, heads: usize, weights: &Initializer, rng: &mut R) -> Self {
+        assert!(heads > 0 && model_dim.is_multiple_of(heads), "Model dimension must be divisible by the number of heads");
+        Self {
+            model_dim,
+            heads,
+            mask: None,
+            weights: (0..4).map(|_| weights.initialize(model_dim, model_dim, rng).transpose()).collect(),
+            biases: vec![Matrix::zeros(1, model_dim); 4],
+            grad_weights: vec![Matrix::zeros(model_dim, model_dim); 4],
+            grad_biases: vec![Matrix::zeros(1, model_dim); 4],
+            cache: None,
+        }
+    }
+
+    pub fn with_mask(mut self, mask: Mask) -> Self {
+        self.mask = Some(mask);
+        self
+    }
+
+    /// Attention weights of every head for each sequence of the last forward pass.
+    pub fn attention_weights(&self) -> Option<Vec<Vec<Matrix>>> {
+        self.cache.as_ref().map(|cache| cache.iter().map(|c| c.weights.clone()).collect())
+    }
+
+    /// Self-attention over one sequence (`T x model_dim`).
+    fn attend(&self, input: Matrix) -> (Matrix, AttentionCache) {
+        let head_dim = self.model_dim / self.heads;
+        let query = affine(&input, &self.weights[0], &self.biases[0]);
+        let key = affine(&input, &self.weights[1], &self.biases[1]);
+        let value = affine(&input, &self.weights[2], &self.biases[2]);
+
+        let mut heads = Matrix::zeros(input.row_count(), self.model_dim);
+        let mut weights = Vec::with_capacity(self.heads);
+        for h in 0..self.heads {
+            let (start, end) = (h * head_dim, (h + 1) * head_dim);
+            let (output, w) = scaled_dot_product_attention(
+                &query.columns(start, end),
+                &key.columns(start, end),
+                &value.columns(start, end),
+                self.mask.as_ref(),
+            );
+            heads.set_columns(start, &output);
+            weights.push(w);
+        }
+        let output = affine(&heads, &self.weights[3], &self.biases[3]);
+        (output, AttentionCache { input, query, key, value, weights, heads })
+    }
+
+    fn steps(&self, width: usize) -> usize {
+        assert!(width.is_multiple_of(self.model_dim), "Input is not a whole number of time steps");
+        width / self.model_dim
+    }
+}
+
+/// Splits a packed row into a `T x features` matrix.
+fn unpack(row: &Vector, features: usize) -> Matrix {
+    Matrix::new(row.data.chunks(features).map(|step| step.to_vec()).collect())
+}
+
+/// Flattens a `T x features` matrix back into a packed row.
+fn pack(m: Matrix) -> Vector {
+    Vector::new(m.rows.into_iter().flat_map(|r| r.data).collect())
+}
+
+impl Layer for MultiHeadAttention {
+    fn name(&self) -> String {
+        format!("MultiHeadAttention({}, {} heads)", self.model_dim, self.heads)
+    }
+
+    fn output_size(&self, input_size: usize) -> Result<usize, String> {
+        if input_size == 0 || !input_size.is_multiple_of(self.model_dim) {
+            return Err(format!(
+                "expects a multiple of {} input features (one block per time step) but receives {}",
+                self.model_dim, input_size
+            ));
+        }
+        Ok(input_size)
+    }
+
+    fn forward(&mut self, input: &Matrix, _training: bool) -> Matrix {
+        self.steps(input.col_count());
+        let (output, cache): (Vec<Vector>, Vec<AttentionCache>) = input
+            .rows
+            .iter()
+            .map(|row| {
+                let (output, cache) = self.attend(unpack(row, self.model_dim));
+                (pack(output), cache)
+            })
+            .unzip();
+        self.cache = Some(cache);
+        Matrix::from_vector(output)
+    }
+
+    fn backward(&mut self, grad_output: &Matrix) -> Matrix {
+        let cache = self.cache.take().expect("MultiHeadAttention::backward called before forward");
+        let head_dim = self.model_dim / self.heads;
+        let d = self.model_dim;
+        self.grad_weights = vec![Matrix::zeros(d, d); 4];
+        self.grad_biases = vec![Matrix::zeros(1, d); 4];
+
+        let mut grad_input = Vec::with_capacity(cache.len());
+        for (row, c) in grad_output.rows.iter().zip(&cache) {
+            let grad = unpack(row, d);
+            self.grad_weights[3].add_assign(&c.heads.transpose().gemm(&grad));
+            self.grad_biases[3][0].add_assign(&grad.column_sums(), 1.0);
+            let grad_heads = grad.gemm(&self.weights[3].transpose());
+
+            let steps = grad.row_count();
+            let mut grads = vec![Matrix::zeros(steps, d); 3]; // query, key, value
+            for h in 0..self.heads {
+                let (start, end) = (h * head_dim, (h + 1) * head_dim);
+                let (dq, dk, dv) = attention_backward(
+                    &c.query.columns(start, end),
+                    &c.key.columns(start, end),
+                    &c.value.columns(start, end),
+                    &c.weights[h],
+                    &grad_heads.columns(start, end),
+                );
+                grads[0].set_columns(start, &dq);
+                grads[1].set_columns(start, &dk);
+                grads[2].set_columns(start, &dv);
+            }
+
+            let mut grad_x = Matrix::zeros(steps, d);
+            for (p, g) in grads.iter().enumerate() {
+                self.grad_weights[p].add_assign(&c.input.transpose().gemm(g));
+                self.grad_biases[p][0].add_assign(&g.column_sums(), 1.0);
+                grad_x.add_assign(&g.gemm(&self.weights[p].transpose()));
+            }
+            grad_input.push(pack(grad_x));
+        }
+        self.cache = Some(cache);
+        Matrix::from_vector(grad_input)
+    }
+
+    fn parameters(&self) -> Vec<Matrix> {
+        self.weights.iter().zip(&self.biases).flat_map(|(w, b)| [w.clone(), b.clone()]).collect()
+    }
+
+    fn gradients(&self) -> Vec<Matrix> {
+        self.grad_weights.iter().zip(&self.grad_biases).flat_map(|(w, b)| [w.clone(), b.clone()]).collect()
+    }
+
+    fn set_parameters(&mut self, parameters: &[Matrix]) {
+        for p in 0..4 {
+            self.weights[p] = parameters[2 * p].clone();
+            self.biases[p] = parameters[2 * p + 1].clone();
+        }
+    }
+}
+
+/// Adds the fixed sinusoidal position signal of "Attention Is All You Need":
+/// `PE(t, 2i) = sin(t / 10000^(2i/d))`, `PE(t, 2i+1) = cos(t / 10000^(2i/d))`.
+pub struct SinusoidalEncoding {
+    pub model_dim: usize,
+}
+
+impl SinusoidalEncoding {
+    pub fn new(model_dim: usize) -> Self {
+        Self { model_dim }
+    }
+
+    /// The encoding of position `t`.
+    pub fn encoding(&self, t: usize) -> Vector {
+        Vector::new(
+            (0..self.model_dim)
+                .map(|j| {
+                    let angle = t as f64 / 10000f64.powf((j - j % 2) as f64 / self.model_dim as f64);
+                    if j % 2 == 0 { angle.sin() } else { angle.cos() }
+                })
+                .collect(),
+        )
+    }
+}
+
+impl Layer for SinusoidalEncoding {
+    fn name(&self) -> String {
+        format!("SinusoidalEncoding({})", self.model_dim)
+    }
+
+    fn output_size(&self, input_size: usize) -> Result<usize, String> {
+        if input_size == 0 || !input_size.is_multiple_of(self.model_dim) {
+            return Err(format!("expects a multiple of {} input features but receives {}", self.model_dim, input_size));
+        }
+        Ok(input_size)
+    }
+
+    fn forward(&mut self, input: &Matrix, _training: bool) -> Matrix {
+        let steps = input.col_count() / self.model_dim;
+        let signal = Vector::new((0..steps).flat_map(|t| self.encoding(t).data).collect());
+        let mut output = input.clone();
+        for row in &mut output.rows {
+            row.add_assign(&signal, 1.0);
+        }
+        output
+    }
+
+    fn backward(&mut self, grad_output: &Matrix) -> Matrix {
+        grad_output.clone()
+    }
+}
+
+/// Adds a learned embedding for each position up to `max_len`.
+pub struct LearnedPositionalEncoding {
+    pub embeddings: Matrix,      // Dimensions: max_len x model_dim
+    pub grad_embeddings: Matrix, // Gradient of the loss w.r.t. the embeddings
+}
+
+impl LearnedPositionalEncoding {
+    /// Embeddings start as U(-0.05, 0.05).
+    pub fn new(max_len: usize, model_dim: usize) -> Self {
+        let init = Initializer::Uniform { low: -0.05, high: 0.05 };
+        Self {
+            embeddings: init.initialize(model_dim, max_len, &mut rand::rng()),
+            grad_embeddings: Matrix::zeros(max_len, model_dim),
+        }
+    }
+}
+
+impl Layer for LearnedPositionalEncoding {
+    fn name(&self) -> String {
+        format!("LearnedPositionalEncoding({} x {})", self.embeddings.row_count(), self.embeddings.cols)
+    }
+
+    fn output_size(&self, input_size: usize) -> Result<usize, String> {
+        let (max_len, d) = (self.embeddings.row_count(), self.embeddings.cols);
+        if input_size == 0 || !input_size.is_multiple_of(d) || input_size / d > max_len {
+            return Err(format!(
+                "expects up to {} time steps of {} features but receives {} input features",
+                max_len, d, input_size
+            ));
+        }
+        Ok(input_size)
+    }
+
+    fn forward(&mut self, input: &Matrix, _training: bool) -> Matrix {
+        let d = self.embeddings.cols;
+        let steps = input.col_count() / d;
+        assert!(steps <= self.embeddings.row_count(), "Sequence is longer than the learned positions");
+        let signal = Vector::new((0..steps).flat_map(|t| self.embeddings[t].data.clone()).collect());
+        let mut output = input.clone();
+        for row in &mut output.rows {
+            row.add_assign(&signal, 1.0);
+        }
+        output
+    }
+
+    fn backward(&mut self, grad_output: &Matrix) -> Matrix {
+        let d = self.embeddings.cols;
+        self.grad_embeddings = Matrix::zeros(self.embeddings.row_count(), d);
+        let sums = grad_output.column_sums();
+        for (t, step) in sums.data.chunks(d).enumerate() {
+            self.grad_embeddings.rows[t] = Vector::new(step.to_vec());
+        }
+        grad_output.clone()
+    }
+
+    fn parameters(&self) -> Vec<Matrix> {
+        vec![self.embeddings.clone()]
+    }
+
+    fn gradients(&self) -> Vec<Matrix> {
+        vec![self.grad_embeddings.clone()]
+    }
+
+    fn set_parameters(&mut self, parameters: &[Matrix]) {
+        self.embeddings = parameters[0].clone();
+    }
+}
+
+/// A post-norm Transformer encoder block:
+///
+/// `y = LayerNorm(x + Attention(x))`, `out = LayerNorm(y + W₂ ReLU(W₁ y))`,
+/// with the layer norms and the feed-forward network applied per time step.
+pub struct TransformerEncoderBlock {
+    pub attention: MultiHeadAttention,
+    pub attention_norm: TimeDistributed,
+    pub hidden: TimeDistributed,
+    pub activation: ReLU,
+    pub output: TimeDistributed,
+    pub output_norm: TimeDistributed,
+}
+
+impl TransformerEncoderBlock {
+    /// A block over `model_dim` features with `heads` attention heads and a
+    /// feed-forward network with `ff_dim` hidden units.
+    pub fn new(model_dim: usize, heads: usize, ff_dim: usize) -> Self {
+        Self {
+            attention: MultiHeadAttention::new(model_dim, heads),
+            attention_norm: TimeDistributed::new(LayerNorm::new(model_dim), model_dim),
+            hidden: TimeDistributed::new(Dense::new(model_dim, ff_dim), model_dim),
+            activation: ReLU::new(),
+            output: TimeDistributed::new(Dense::new(ff_dim, model_dim), ff_dim),
+            output_norm: TimeDistributed::new(LayerNorm::new(model_dim), model_dim),
+        }
+    }
+
+    /// Restricts the self-attention, e.g. with `Mask::Causal`.
+    pub fn with_mask(mut self, mask: Mask) -> Self {
+        self.attention.mask = Some(mask);
+        self
+    }
+
+    fn sublayers(&self) -> [&dyn Layer; 5] {
+        [&self.attention, &self.attention_norm, &self.hidden, &self.output, &self.output_norm]
+    }
+
+    fn sublayers_mut(&mut self) -> [&mut dyn Layer; 5] {
+        [&mut self.attention, &mut self.attention_norm, &mut self.hidden, &mut self.output, &mut self.output_norm]
+    }
+}
+
+impl Layer for TransformerEncoderBlock {
+    fn name(&self) -> String {
+        format!(
+            "TransformerEncoderBlock({}, {} heads, {} hidden)",
+            self.attention.model_dim,
+            self.attention.heads,
+            self.output.step_size
+        )
+    }
+
+    fn output_size(&self, input_size: usize) -> Result<usize, String> {
+        self.attention.output_size(input_size)
+    }
+
+    fn forward(&mut self, input: &Matrix, training: bool) -> Matrix {
+        let attended = input.add(&self.attention.forward(input, training));
+        let normalized = self.attention_norm.forward(&attended, training);
+        let hidden = self.activation.forward(&self.hidden.forward(&normalized, training), training);
+        let residual = normalized.add(&self.output.forward(&hidden, training));
+        self.output_norm.forward(&residual, training)
+    }
+
+    fn backward(&mut self, grad_output: &Matrix) -> Matrix {
+        let grad_residual = self.output_norm.backward(grad_output);
+        let grad_hidden = self.activation.backward(&self.output.backward(&grad_residual));
+        let grad_normalized = grad_residual.add(&self.hidden.backward(&grad_hidden));
+        let grad_attended = self.attention_norm.backward(&grad_normalized);
+        grad_attended.add(&self.attention.backward(&grad_attended))
+    }
+
+    fn parameters(&self) -> Vec<Matrix> {
+        self.sublayers().iter().flat_map(|layer| layer.parameters()).collect()
+    }
+
+    fn gradients(&self) -> Vec<Matrix> {
+        self.sublayers().iter().flat_map(|layer| layer.gradients()).collect()
+    }
+
+    fn set_parameters(&mut self, parameters: &[Matrix]) {
+        let mut offset = 0;
+        for layer in self.sublayers_mut() {
+            let count = layer.parameters().len();
+            layer.set_parameters(&parameters[offset..offset + count]);
+            offset += count;
+        }
+    }
+}
@@ -0,0 +1,502 @@
+use crate::math::{Matrix, Vector};
+use crate::neuralnetwork::layers::Layer;
+use crate::utils::initializer::Initializer;
+use rand::Rng;
+
+/// Shape of a single image as (channels, height, width).
+///
+/// Images travel between layers as flattened rows in channel-major order,
+/// i.e. element `(c, y, x)` is stored at `(c * height + y) * width + x`.
+pub type Shape = (usize, usize, usize);
+
+fn shape_size(shape: Shape) -> usize {
+    shape.0 * shape.1 * shape.2
+}
+
+fn check_input(shape: Shape, input_size: usize) -> Result<(), String> {
+    if input_size != shape_size(shape) {
+        return Err(format!(
+            "expects {}x{}x{} = {} input features but receives {}",
+            shape.0,
+            shape.1,
+            shape.2,
+            shape_size(shape),
+            input_size
+        ));
+    }
+    Ok(())
+}
+
+/// Output length of a sliding window along one axis.
+fn window_count(size: usize, kernel: usize, stride: usize, padding: usize, dilation: usize) -> Result<usize, String> {
+    let span = dilation * (kernel - 1) + 1;
+    let padded = size + 2 * padding;
+    if padded < span {
+        return Err(format!("has a window spanning {} but the (padded) input is only {} wide", span, padded));
+    }
+    Ok((padded - span) / stride + 1)
+}
+
+/// Formats an output shape for layer names, or `?` if the window does not fit.
+fn describe(shape: Result<Shape, String>) -> String {
+    match shape {
+        Ok((c, h, w)) => format!("{}x{}x{}", c, h, w),
+        Err(_) => "?".to_string(),
+    }
+}
+
+/// 2-D convolution over multi-channel images, implemented with im2col and
+/// `Matrix::gemm`.
+///
+/// ```ignore
+/// let conv = Conv2D::new((1, 28, 28), 6, 5).with_padding(2);
+/// ```
+pub struct Conv2D {
+    pub input_shape: Shape,
+    pub filters: usize,
+    pub kernel_size: usize,
+    pub stride: usize,
+    pub padding: usize,           // Zero padding added to every border
+    pub dilation: usize,          // Spacing between kernel taps
+    pub weights: Matrix,          // Dimensions: (channels * kernel_size²) x filters
+    pub bias: Matrix,             // Dimensions: 1 x filters
+    pub grad_weights: Matrix,     // Gradient of the loss w.r.t. the weights
+    pub grad_bias: Matrix,        // Gradient of the loss w.r.t. the bias
+    columns: Option<Vec<Matrix>>, // im2col matrix of each sample from the last forward pass
+}
+
+impl Conv2D {
+    /// Creates a convolution with `filters` square kernels of size `kernel_size`,
+    /// stride 1, no padding and no dilation. Weights are He-uniform, biases zero.
+    pub fn new(input_shape: Shape, filters: usize, kernel_size: usize) -> Self {
+        let mut rng = rand::rng();
+        Self::with_initializer(input_shape, filters, kernel_size, &Initializer::HeUniform, &Initializer::Zeros, &mut rng)
+    }
+
+    /// Creates a convolution whose weights and biases are drawn from the given initializers.
+    pub fn with_initializer<R: Rng + ?Sized>(
+        input_shape: Shape,
+        filters: usize,
+        kernel_size: usize,
+        weights: &Initializer,
+        biases: &Initializer,
+        rng: &mut R,
+    ) -> Self {
+        assert!(kernel_size > 0 && filters > 0, "Kernel size and filter count must be positive");
+        let fan_in = input_shape.0 * kernel_size * kernel_size;
+        Self {
+            input_shape,
+            filters,
+            kernel_size,
+            stride: 1,
+            padding: 0,
+            dilation: 1,
+            weights: weights.initialize(fan_in, filters, rng).transpose(),
+            bias: biases.initialize_bias(filters, rng),
+            grad_weights: Matrix::zeros(fan_in, filters),
+            grad_bias: Matrix::zeros(1, filters),
+            columns: None,
+        }
+    }
+
+    pub fn with_stride(mut self, stride: usize) -> Self {
+        assert!(stride > 0, "Stride must be positive");
+        self.stride = stride;
+        self
+    }
+
+    pub fn with_padding(mut self, padding: usize) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    pub fn with_dilation(mut self, dilation: usize) -> Self {
+        assert!(dilation > 0, "Dilation must be positive");
+        self.dilation = dilation;
+        self
+    }
+
+    /// Shape of the feature maps produced for one image, or an error if the
+    /// kernel does not fit into the padded input.
+    pub fn output_shape(&self) -> Result<Shape, String> {
+        let (_, height, width) = self.input_shape;
+        Ok((
+            self.filters,
+            window_count(height, self.kernel_size, self.stride, self.padding, self.dilation)?,
+            window_count(width, self.kernel_size, self.stride, self.padding, self.dilation)?,
+        ))
+    }
+
+    /// Output shape of a layer already validated by `output_size`.
+    fn shape(&self) -> Shape {
+        self.output_shape().unwrap_or_else(|e| panic!("{} {}", self.name(), e))
+    }
+
+    /// Input coordinate read by output position `out` and kernel tap `k`,
+    /// or `None` if it falls into the padding.
+    fn source(&self, out: usize, k: usize, size: usize) -> Option<usize> {
+        let position = (out * self.stride + k * self.dilation) as isize - self.padding as isize;
+        if position < 0 || position as usize >= size { None } else { Some(position as usize) }
+    }
+
+    /// Unfolds one image into a matrix with one row per output position and
+    /// one column per (channel, kernel row, kernel column).
+    fn im2col(&self, image: &Vector) -> Matrix {
+        let (channels, height, width) = self.input_shape;
+        let (_, out_h, out_w) = self.shape();
+        let k = self.kernel_size;
+        let mut columns = Matrix::zeros(out_h * out_w, channels * k * k);
+        for oy in 0..out_h {
+            for ox in 0..out_w {
+                let row = &mut columns.rows[oy * out_w + ox];
+                for c in 0..channels {
+                    for ky in 0..k {
+                        let Some(y) = self.source(oy, ky, height) else { continue };
+                        for kx in 0..k {
+                            if let Some(x) = self.source(ox, kx, width) {
+                                row[(c * k + ky) * k + kx] = image[(c * height + y) * width + x];
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        columns
+    }
+
+    /// Folds a gradient w.r.t. the im2col matrix back onto the image, summing
+    /// the contributions of overlapping windows.
+    fn col2im(&self, columns: &Matrix) -> Vector {
+        let (channels, height, width) = self.input_shape;
+        let (_, out_h, out_w) = self.shape();
+        let k = self.kernel_size;
+        let mut image = Vector::zeros(shape_size(self.input_shape));
+        for oy in 0..out_h {
+            for ox in 0..out_w {
+                let row = &columns[oy * out_w + ox];
+                for c in 0..channels {
+                    for ky in 0..k {
+                        let Some(y) = self.source(oy, ky, height) else { continue };
+                        for kx in 0..k {
+                            if let Some(x) = self.source(ox, kx, width) {
+                                image[(c * height + y) * width + x] += row[(c * k + ky) * k + kx];
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        image
+    }
+}
+
+impl Layer for Conv2D {
+    fn name(&self) -> String {
+        format!("Conv2D({}x{}, {} -> {})", self.kernel_size, self.kernel_size, self.input_shape.0, describe(self.output_shape()))
+    }
+
+    fn output_size(&self, input_size: usize) -> Result<usize, String> {
+        check_input(self.input_shape, input_size)?;
+        Ok(shape_size(self.output_shape()?))
+    }
+
+    fn forward(&mut self, input: &Matrix, _training: bool) -> Matrix {
+        let mut columns = Vec::with_capacity(input.row_count());
+        let mut output = Vec::with_capacity(input.row_count());
+        for image in &input.rows {
+            let cols = self.im2col(image);
+            let mut maps = cols.gemm(&self.weights); // (out_h * out_w) x filters
+            for row in &mut maps.rows {
+                row.add_assign(&self.bias[0], 1.0);
+            }
+            // Channel-major output: one feature map after the other
+            output.push(Vector::new(maps.transpose().rows.into_iter().flat_map(|r| r.data).collect()));
+            columns.push(cols);
+        }
+        self.columns = Some(columns);
+        Matrix::from_vector(output)
+    }
+
+    fn backward(&mut self, grad_output: &Matrix) -> Matrix {
+        let columns = self.columns.as_ref().expect("Conv2D::backward called before forward");
+        let (_, out_h, out_w) = self.shape();
+        let positions = out_h * out_w;
+        self.grad_weights = Matrix::zeros(self.weights.row_count(), self.filters);
+        self.grad_bias = Matrix::zeros(1, self.filters);
+
+        let mut grad_input = Vec::with_capacity(grad_output.row_count());
+        for (grad, cols) in grad_output.rows.iter().zip(columns) {
+            // Reshape the flat gradient to (out_h * out_w) x filters
+            let maps = Matrix::new(
+                (0..self.filters).map(|f| grad.data[f * positions..(f + 1) * positions].to_vec()).collect(),
+            )
+            .transpose();
+            self.grad_weights.add_assign(&cols.transpose().gemm(&maps));
+            self.grad_bias[0].add_assign(&maps.column_sums(), 1.0);
+            grad_input.push(self.col2im(&maps.gemm(&self.weights.transpose())));
+        }
+        Matrix::from_vector(grad_input)
+    }
+
+    fn parameters(&self) -> Vec<Matrix> {
+        vec![self.weights.clone(), self.bias.clone()]
+    }
+
+    fn gradients(&self) -> Vec<Matrix> {
+        vec![self.grad_weights.clone(), self.grad_bias.clone()]
+    }
+
+    fn set_parameters(&mut self, parameters: &[Matrix]) {
+        self.weights = parameters[0].clone();
+        self.bias = parameters[1].clone();
+    }
+}
+
+/// Shared window geometry of the pooling layers.
+struct Pooling {
+    input_shape: Shape,
+    pool_size: usize,
+    stride: usize,
+}
+
+impl Pooling {
+    fn output_shape(&self) -> Result<Shape, String> {
+        let (channels, height, width) = self.input_shape;
+        Ok((channels, window_count(height, self.pool_size, self.stride, 0, 1)?, window_count(width, self.pool_size, self.stride, 0, 1)?))
+    }
+
+    /// Flat input indices covered by each output element, in output order.
+    fn windows(&self) -> Vec<Vec<usize>> {
+        let (channels, height, width) = self.input_shape;
+        let (_, out_h, out_w) = self.output_shape().expect("Pooling window was validated by output_size");
+        let mut windows = Vec::with_capacity(channels * out_h * out_w);
+        for c in 0..channels {
+            for oy in 0..out_h {
+                for ox in 0..out_w {
+                    let mut window = Vec::with_capacity(self.pool_size * self.pool_size);
+                    for y in oy * self.stride..oy * self.stride + self.pool_size {
+                        for x in ox * self.stride..ox * self.stride + self.pool_size {
+                            window.push((c * height + y) * width + x);
+                        }
+                    }
+                    windows.push(window);
+                }
+            }
+        }
+        windows
+    }
+}
+
+/// Max pooling over square windows of each channel.
+pub struct MaxPool2D {
+    pooling: Pooling,
+    argmax: Option<Vec<Vec<usize>>>, // Input index of each selected maximum, per sample
+}
+
+impl MaxPool2D {
+    /// Creates a max pooling layer with non-overlapping `pool_size` windows.
+    pub fn new(input_shape: Shape, pool_size: usize) -> Self {
+        assert!(pool_size > 0, "Pool size must be positive");
+        Self { pooling: Pooling { input_shape, pool_size, stride: pool_size }, argmax: None }
+    }
+
+    pub fn with_stride(mut self, stride: usize) -> Self {
+        assert!(stride > 0, "Stride must be positive");
+        self.pooling.stride = stride;
+        self
+    }
+
+    /// Shape of the pooled maps for one image, or an error if the window
+    /// does not fit into the input.
+    pub fn output_shape(&self) -> Result<Shape, String> {
+        self.pooling.output_shape()
+    }
+}
+
+impl Layer for MaxPool2D {
+    fn name(&self) -> String {
+        format!("MaxPool2D({} -> {})", self.pooling.pool_size, describe(self.output_shape()))
+    }
+
+    fn output_size(&self, input_size: usize) -> Result<usize, String> {
+        check_input(self.pooling.input_shape, input_size)?;
+        Ok(shape_size(self.output_shape()?))
+    }
+
+    fn forward(&mut self, input: &Matrix, _training: bool) -> Matrix {
+        let windows = self.pooling.windows();
+        let mut argmax = Vec::with_capacity(input.row_count());
+        let mut output = Vec::with_capacity(input.row_count());
+        for image in &input.rows {
+            let selected: Vec<usize> = windows
+                .iter()
+                .map(|window| *window.iter().max_by(|&&a, &&b| image[a].total_cmp(&image[b])).unwrap())
+                .collect();
+            output.push(Vector::new(selected.iter().map(|&i| image[i]).collect()));
+            argmax.push(selected);
+        }
+        self.argmax = Some(argmax);
+        Matrix::from_vector(output)
+    }
+
+    fn backward(&mut self, grad_output: &Matrix) -> Matrix {
+        let argmax = self.argmax.as_ref().expect("MaxPool2D::backward called before forward");
+        let size = shape_size(self.pooling.input_shape);
+        let grad_input = grad_output
+            .rows
+            .iter()
+            .zip(argmax)
+            .map(|(grad, selected)| {
+                let mut g = Vector::zeros(size);
+                for (&i, &value) in selected.iter().zip(grad.iter()) {
+                    g[i] += value;
+                }
+                g
+            })
+            .collect();
+        Matrix::from_vector(grad_input)
+    }
+}
+
+/// Average pooling over square windows of each channel.
+pub struct AvgPool2D {
+    pooling: Pooling,
+}
+
+impl AvgPool2D {
+    /// Creates an average pooling layer with non-overlapping `pool_size` windows.
+    pub fn new(input_shape: Shape, pool_size: usize) -> Self {
+        assert!(pool_size > 0, "Pool size must be positive");
+        Self { pooling: Pooling { input_shape, pool_size, stride: pool_size } }
+    }
+
+    pub fn with_stride(mut self, stride: usize) -> Self {
+        assert!(stride > 0, "Stride must be positive");
+        self.pooling.stride = stride;
+        self
+    }
+
+    /// Shape of the pooled maps for one image, or an error if the window
+    /// does not fit into the input.
+    pub fn output_shape(&self) -> Result<Shape, String> {
+        self.pooling.output_shape()
+    }
+}
+
+impl Layer for AvgPool2D {
+    fn name(&self) -> String {
+        format!("AvgPool2D({} -> {})", self.pooling.pool_size, describe(self.output_shape()))
+    }
+
+    fn output_size(&self, input_size: usize) -> Result<usize, String> {
+        check_input(self.pooling.input_shape, input_size)?;
+        Ok(shape_size(self.output_shape()?))
+    }
+
+    fn forward(&mut self, input: &Matrix, _training: bool) -> Matrix {
+        let windows = self.pooling.windows();
+        let area = (self.pooling.pool_size * self.pooling.pool_size) as f64;
+        let output = input
+            .rows
+            .iter()
+            .map(|image| Vector::new(windows.iter().map(|w| w.iter().map(|&i| image[i]).sum::<f64>() / area).collect()))
+            .collect();
+        Matrix::from_vector(output)
+    }
+
+    fn backward(&mut self, grad_output: &Matrix) -> Matrix {
+        let windows = self.pooling.windows();
+        let area = (self.pooling.pool_size * self.pooling.pool_size) as f64;
+        let size = shape_size(self.pooling.input_shape);
+        let grad_input = grad_output
+            .rows
+            .iter()
+            .map(|grad| {
+                let mut g = Vector::zeros(size);
+                for (window, &value) in windows.iter().zip(grad.iter()) {
+                    for &i in window {
+                        g[i] += value / area;
+                    }
+                }
+                g
+            })
+            .collect();
+        Matrix::from_vector(grad_input)
+    }
+}
+
+/// Averages each channel over all spatial positions: (C, H, W) -> C.
+pub struct GlobalAvgPool {
+    pub input_shape: Shape,
+}
+
+impl GlobalAvgPool {
+    pub fn new(input_shape: Shape) -> Self {
+        Self { input_shape }
+    }
+}
+
+impl Layer for GlobalAvgPool {
+    fn name(&self) -> String {
+        format!("GlobalAvgPool({})", self.input_shape.0)
+    }
+
+    fn output_size(&self, input_size: usize) -> Result<usize, String> {
+        check_input(self.input_shape, input_size)?;
+        Ok(self.input_shape.0)
+    }
+
+    fn forward(&mut self, input: &Matrix, _training: bool) -> Matrix {
+        let area = self.input_shape.1 * self.input_shape.2;
+        let output = input
+            .rows
+            .iter()
+            .map(|image| Vector::new(image.data.chunks(area).map(|c| c.iter().sum::<f64>() / area as f64).collect()))
+            .collect();
+        Matrix::from_vector(output)
+    }
+
+    fn backward(&mut self, grad_output: &Matrix) -> Matrix {
+        let area = self.input_shape.1 * self.input_shape.2;
+        let grad_input = grad_output
+            .rows
+            .iter()
+            .map(|grad| Vector::new(grad.iter().flat_map(|&g| std::iter::repeat_n(g / area as f64, area)).collect()))
+            .collect();
+        Matrix::from_vector(grad_input)
+    }
+}
+
+/// Marks the transition from feature maps to dense layers.
+///
+/// Images are already stored as flattened rows, so this layer only checks the
+/// incoming shape and passes values and gradients through unchanged.
+pub struct Flatten {
+    pub input_shape: Shape,
+}
+
+impl Flatten {
+    pub fn new(input_shape: Shape) -> Self {
+        Self { input_shape }
+    }
+}
+
+impl Layer for Flatten {
+    fn name(&self) -> String {
+        let (c, h, w) = self.input_shape;
+        format!("Flatten({}x{}x{})", c, h, w)
+    }
+
+    fn output_size(&self, input_size: usize) -> Result<usize, String> {
+        check_input(self.input_shape, input_size)?;
+        Ok(input_size)
+    }
+
+    fn forward(&mut self, input: &Matrix, _training: bool) -> Matrix {
+        input.clone()
+    }
+
+    fn backward(&mut self, grad_output: &Matrix) -> Matrix {
+        grad_output.clone()
+    }
+}
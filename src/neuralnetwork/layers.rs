@@ -0,0 +1,391 @@
+use crate::math::{Matrix, Vector};
+use crate::neuralnetwork::normalization::{BatchNorm, LayerNorm, Normalization};
+use crate::neuralnetwork::regularization::Dropout;
+use crate::utils::activation::{relu, relu_derivative, sigmoid, sigmoid_derivative, tanh_derivative};
+use crate::utils::initializer::Initializer;
+use rand::Rng;
+
+/// A building block of a `Sequential` model.
+///
+/// Layers operate on mini-batches stored as a `Matrix` with one sample per
+/// row. `forward` caches whatever `backward` needs, `backward` receives the
+/// gradient of the loss w.r.t. the layer's output, stores the gradients of
+/// the layer's parameters and returns the gradient w.r.t. its input.
+pub trait Layer {
+    /// Short description used in summaries and error messages.
+    fn name(&self) -> String;
+
+    /// Number of output features for `input_size` input features,
+    /// or a readable error if the layer cannot accept that many.
+    fn output_size(&self, input_size: usize) -> Result<usize, String>;
+
+    /// Forward pass over a batch. `training` enables dropout, batch statistics, etc.
+    fn forward(&mut self, input: &Matrix, training: bool) -> Matrix;
+
+    /// Backward pass for the most recent `forward` call.
+    fn backward(&mut self, grad_output: &Matrix) -> Matrix;
+
+    /// The layer's trainable parameters, in a fixed order.
+    fn parameters(&self) -> Vec<Matrix> {
+        Vec::new()
+    }
+
+    /// Gradients of the loss w.r.t. `parameters()` from the last backward pass.
+    fn gradients(&self) -> Vec<Matrix> {
+        Vec::new()
+    }
+
+    /// Replaces the trainable parameters (same order and shapes as `parameters()`).
+    fn set_parameters(&mut self, _parameters: &[Matrix]) {}
+
+    /// Non-trainable buffers that training-mode forward passes update,
+    /// such as batch normalization's running statistics.
+    fn state(&self) -> Vec<Matrix> {
+        Vec::new()
+    }
+
+    /// Replaces the buffers returned by `state()`.
+    fn set_state(&mut self, _state: &[Matrix]) {}
+}
+
+/// Fully connected layer: `y = x W + b`.
+pub struct Dense {
+    pub weights: Matrix,      // Dimensions: input_size x output_size
+    pub bias: Matrix,         // Dimensions: 1 x output_size
+    pub grad_weights: Matrix, // Gradient of the loss w.r.t. the weights
+    pub grad_bias: Matrix,    // Gradient of the loss w.r.t. the bias
+    input: Option<Matrix>,
+}
+
+impl Dense {
+    /// Creates a dense layer with Xavier-uniform weights and zero biases.
+    pub fn new(input_size: usize, output_size: usize) -> Self {
+        let mut rng = rand::rng();
+        Self::with_initializer(input_size, output_size, &Initializer::XavierUniform, &Initializer::Zeros, &mut rng)
+    }
+
+    /// Creates a dense layer whose weights and biases are drawn from the given initializers.
+    pub fn with_initializer<R: Rng + ?Sized>(
+        input_size: usize,
+        output_size: usize,
+        weights: &Initializer,
+        biases: &Initializer,
+        rng: &mut R,
+    ) -> Self {
+        Self {
+            weights: weights.initialize(input_size, output_size, rng).transpose(),
+            bias: biases.initialize_bias(output_size, rng),
+            grad_weights: Matrix::zeros(input_size, output_size),
+            grad_bias: Matrix::zeros(1, output_size),
+            input: None,
+        }
+    }
+
+    pub fn input_size(&self) -> usize {
+        self.weights.row_count()
+    }
+}
+
+impl Layer for Dense {
+    fn name(&self) -> String {
+        format!("Dense({} -> {})", self.input_size(), self.weights.cols)
+    }
+
+    fn output_size(&self, input_size: usize) -> Result<usize, String> {
+        if input_size != self.input_size() {
+            return Err(format!("expects {} input features but receives {}", self.input_size(), input_size));
+        }
+        Ok(self.weights.cols)
+    }
+
+    fn forward(&mut self, input: &Matrix, _training: bool) -> Matrix {
+        let mut output = input.gemm(&self.weights);
+        for row in &mut output.rows {
+            row.add_assign(&self.bias[0], 1.0);
+        }
+        self.input = Some(input.clone());
+        output
+    }
+
+    fn backward(&mut self, grad_output: &Matrix) -> Matrix {
+        let input = self.input.as_ref().expect("Dense::backward called before forward");
+        self.grad_weights = input.transpose().gemm(grad_output);
+        self.grad_bias = Matrix::from_vector(vec![grad_output.column_sums()]);
+        grad_output.gemm(&self.weights.transpose())
+    }
+
+    fn parameters(&self) -> Vec<Matrix> {
+        vec![self.weights.clone(), self.bias.clone()]
+    }
+
+    fn gradients(&self) -> Vec<Matrix> {
+        vec![self.grad_weights.clone(), self.grad_bias.clone()]
+    }
+
+    fn set_parameters(&mut self, parameters: &[Matrix]) {
+        self.weights = parameters[0].clone();
+        self.bias = parameters[1].clone();
+    }
+}
+
+/// Generates a parameter-free element-wise activation layer. The derivative
+/// is computed from the cached input (`$from_input = true`) or output.
+macro_rules! activation_layer {
+    ($(#[$doc:meta])* $name:ident, $f:expr, $df:expr, $from_input:expr) => {
+        $(#[$doc])*
+        pub struct $name {
+            cache: Option<Matrix>,
+        }
+
+        impl $name {
+            pub fn new() -> Self {
+                Self { cache: None }
+            }
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl Layer for $name {
+            fn name(&self) -> String {
+                stringify!($name).to_string()
+            }
+
+            fn output_size(&self, input_size: usize) -> Result<usize, String> {
+                Ok(input_size)
+            }
+
+            fn forward(&mut self, input: &Matrix, _training: bool) -> Matrix {
+                let output = input.map($f);
+                self.cache = Some(if $from_input { input.clone() } else { output.clone() });
+                output
+            }
+
+            fn backward(&mut self, grad_output: &Matrix) -> Matrix {
+                let cache = self.cache.as_ref().expect(concat!(stringify!($name), "::backward called before forward"));
+                grad_output.hadamard(&cache.map($df))
+            }
+        }
+    };
+}
+
+activation_layer!(
+    /// Rectified linear unit: `max(0, x)`.
+    ReLU, relu, relu_derivative, true
+);
+activation_layer!(
+    /// Logistic sigmoid: `1 / (1 + e^-x)`.
+    Sigmoid, sigmoid, sigmoid_derivative, false
+);
+activation_layer!(
+    /// Hyperbolic tangent.
+    Tanh, f64::tanh, tanh_derivative, false
+);
+
+impl Layer for Dropout {
+    fn name(&self) -> String {
+        format!("Dropout({})", self.rate)
+    }
+
+    fn output_size(&self, input_size: usize) -> Result<usize, String> {
+        Ok(input_size)
+    }
+
+    fn forward(&mut self, input: &Matrix, training: bool) -> Matrix {
+        if !training || self.rate == 0.0 {
+            self.cache = None;
+            return input.clone();
+        }
+        let mask = Matrix::from_vector(input.rows.iter().map(|row| self.mask(row.len())).collect());
+        let output = input.hadamard(&mask);
+        self.cache = Some(mask);
+        output
+    }
+
+    fn backward(&mut self, grad_output: &Matrix) -> Matrix {
+        match &self.cache {
+            Some(mask) => grad_output.hadamard(mask),
+            None => grad_output.clone(),
+        }
+    }
+}
+
+/// Wraps a vector parameter as a 1 x n matrix.
+fn row_matrix(v: &Vector) -> Matrix {
+    Matrix::from_vector(vec![v.clone()])
+}
+
+impl Layer for BatchNorm {
+    fn name(&self) -> String {
+        format!("BatchNorm({})", self.gamma.len())
+    }
+
+    fn output_size(&self, input_size: usize) -> Result<usize, String> {
+        if input_size != self.gamma.len() {
+            return Err(format!("normalizes {} features but receives {}", self.gamma.len(), input_size));
+        }
+        Ok(input_size)
+    }
+
+    fn forward(&mut self, input: &Matrix, training: bool) -> Matrix {
+        Matrix::from_vector(BatchNorm::forward(self, &input.rows, training))
+    }
+
+    fn backward(&mut self, grad_output: &Matrix) -> Matrix {
+        Matrix::from_vector(BatchNorm::backward(self, &grad_output.rows))
+    }
+
+    fn parameters(&self) -> Vec<Matrix> {
+        vec![row_matrix(&self.gamma), row_matrix(&self.beta)]
+    }
+
+    fn gradients(&self) -> Vec<Matrix> {
+        vec![row_matrix(&self.grad_gamma), row_matrix(&self.grad_beta)]
+    }
+
+    fn set_parameters(&mut self, parameters: &[Matrix]) {
+        self.gamma = parameters[0][0].clone();
+        self.beta = parameters[1][0].clone();
+    }
+
+    fn state(&self) -> Vec<Matrix> {
+        vec![row_matrix(&self.running_mean), row_matrix(&self.running_var)]
+    }
+
+    fn set_state(&mut self, state: &[Matrix]) {
+        self.running_mean = state[0][0].clone();
+        self.running_var = state[1][0].clone();
+    }
+}
+
+impl Layer for LayerNorm {
+    fn name(&self) -> String {
+        format!("LayerNorm({})", self.gamma.len())
+    }
+
+    fn output_size(&self, input_size: usize) -> Result<usize, String> {
+        if input_size != self.gamma.len() {
+            return Err(format!("normalizes {} features but receives {}", self.gamma.len(), input_size));
+        }
+        Ok(input_size)
+    }
+
+    fn forward(&mut self, input: &Matrix, training: bool) -> Matrix {
+        Matrix::from_vector(LayerNorm::forward(self, &input.rows, training))
+    }
+
+    fn backward(&mut self, grad_output: &Matrix) -> Matrix {
+        Matrix::from_vector(LayerNorm::backward(self, &grad_output.rows))
+    }
+
+    fn parameters(&self) -> Vec<Matrix> {
+        vec![row_matrix(&self.gamma), row_matrix(&self.beta)]
+    }
+
+    fn gradients(&self) -> Vec<Matrix> {
+        vec![row_matrix(&self.grad_gamma), row_matrix(&self.grad_beta)]
+    }
+
+    fn set_parameters(&mut self, parameters: &[Matrix]) {
+        self.gamma = parameters[0][0].clone();
+        self.beta = parameters[1][0].clone();
+    }
+}
+
+impl Normalization {
+    /// The normalization as a `Layer`, exposing its gamma/beta parameters
+    /// and gradients and its state.
+    pub fn as_layer(&self) -> &dyn Layer {
+        match self {
+            Normalization::Batch(norm) => norm,
+            Normalization::Layer(norm) => norm,
+        }
+    }
+
+    pub fn as_layer_mut(&mut self) -> &mut dyn Layer {
+        match self {
+            Normalization::Batch(norm) => norm,
+            Normalization::Layer(norm) => norm,
+        }
+    }
+}
+
+/// Applies a layer independently to every time step of a sequence.
+///
+/// Input rows hold `T` steps of `step_size` features each; the wrapped layer
+/// sees them as a batch of `batch * T` rows and its parameters are shared
+/// across time steps.
+pub struct TimeDistributed {
+    pub inner: Box<dyn Layer>,
+    pub step_size: usize,
+    output_step: usize,
+}
+
+impl TimeDistributed {
+    /// Panics if `layer` cannot accept `step_size` features.
+    pub fn new<L: Layer + 'static>(layer: L, step_size: usize) -> Self {
+        let output_step = layer.output_size(step_size).unwrap_or_else(|e| panic!("{} {}", layer.name(), e));
+        Self { inner: Box::new(layer), step_size, output_step }
+    }
+
+    /// Splits each row into its time steps: `batch x (T * size)` -> `(batch * T) x size`.
+    fn unfold(m: &Matrix, size: usize) -> Matrix {
+        Matrix::new(m.rows.iter().flat_map(|row| row.data.chunks(size).map(|step| step.to_vec())).collect())
+    }
+
+    /// Inverse of `unfold` for `steps` time steps per row.
+    fn fold(m: &Matrix, steps: usize) -> Matrix {
+        Matrix::new(m.rows.chunks(steps).map(|rows| rows.iter().flat_map(|r| r.data.iter().copied()).collect()).collect())
+    }
+}
+
+impl Layer for TimeDistributed {
+    fn name(&self) -> String {
+        format!("TimeDistributed({})", self.inner.name())
+    }
+
+    fn output_size(&self, input_size: usize) -> Result<usize, String> {
+        if input_size == 0 || !input_size.is_multiple_of(self.step_size) {
+            return Err(format!(
+                "expects a multiple of {} input features (one block per time step) but receives {}",
+                self.step_size, input_size
+            ));
+        }
+        Ok(input_size / self.step_size * self.output_step)
+    }
+
+    fn forward(&mut self, input: &Matrix, training: bool) -> Matrix {
+        let steps = input.col_count() / self.step_size;
+        let output = self.inner.forward(&Self::unfold(input, self.step_size), training);
+        Self::fold(&output, steps)
+    }
+
+    fn backward(&mut self, grad_output: &Matrix) -> Matrix {
+        let steps = grad_output.col_count() / self.output_step;
+        let grad = self.inner.backward(&Self::unfold(grad_output, self.output_step));
+        Self::fold(&grad, steps)
+    }
+
+    fn parameters(&self) -> Vec<Matrix> {
+        self.inner.parameters()
+    }
+
+    fn gradients(&self) -> Vec<Matrix> {
+        self.inner.gradients()
+    }
+
+    fn set_parameters(&mut self, parameters: &[Matrix]) {
+        self.inner.set_parameters(parameters);
+    }
+
+    fn state(&self) -> Vec<Matrix> {
+        self.inner.state()
+    }
+
+    fn set_state(&mut self, state: &[Matrix]) {
+        self.inner.set_state(state);
+    }
+}
@@ -0,0 +1,85 @@
+use crate::math::Matrix;
+
+/// Loss functions for `Sequential` models. Losses are averaged over the batch.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Loss {
+    /// Mean over the batch of `Σ (output - target)²`.
+    MeanSquaredError,
+    /// Softmax followed by cross-entropy. The model outputs raw logits and
+    /// targets are one-hot (or probability) rows.
+    SoftmaxCrossEntropy,
+    /// Binary cross-entropy on probabilities in (0, 1), e.g. after a `Sigmoid` layer.
+    BinaryCrossEntropy,
+    /// Softmax cross-entropy applied separately to every block of `classes`
+    /// logits, i.e. to each time step of a sequence-tagging model.
+    /// Averaged over the batch and the time steps.
+    SequenceSoftmaxCrossEntropy { classes: usize },
+}
+
+impl Loss {
+    /// Returns the loss value and its gradient w.r.t. `output`.
+    pub fn compute(&self, output: &Matrix, target: &Matrix) -> (f64, Matrix) {
+        assert!(
+            output.row_count() == target.row_count() && output.col_count() == target.col_count(),
+            "Output and target dimensions must match"
+        );
+        let n = output.row_count() as f64;
+        match self {
+            Loss::MeanSquaredError => {
+                let diff = output.sub(target);
+                let loss = diff.rows.iter().map(|row| row.dot(row)).sum::<f64>() / n;
+                (loss, diff.map(|d| 2.0 * d / n))
+            }
+            Loss::SoftmaxCrossEntropy => {
+                let probabilities = Self::softmax(output);
+                let mut loss = 0.0;
+                for (p, t) in probabilities.rows.iter().zip(&target.rows) {
+                    for (pi, ti) in p.iter().zip(t) {
+                        if *ti > 0.0 {
+                            loss -= ti * pi.max(f64::EPSILON).ln();
+                        }
+                    }
+                }
+                (loss / n, probabilities.sub(target).map(|g| g / n))
+            }
+            Loss::BinaryCrossEntropy => {
+                let mut loss = 0.0;
+                let mut grad = Matrix::zeros(output.row_count(), output.col_count());
+                for i in 0..output.row_count() {
+                    for j in 0..output.col_count() {
+                        let p = output[(i, j)].clamp(1e-12, 1.0 - 1e-12);
+                        let t = target[(i, j)];
+                        loss -= t * p.ln() + (1.0 - t) * (1.0 - p).ln();
+                        grad[(i, j)] = (p - t) / (p * (1.0 - p)) / n;
+                    }
+                }
+                (loss / n, grad)
+            }
+            Loss::SequenceSoftmaxCrossEntropy { classes } => {
+                assert!(output.col_count().is_multiple_of(*classes), "Output is not a whole number of time steps");
+                let steps = output.col_count() / classes;
+                let blocks = |m: &Matrix| {
+                    Matrix::new(m.rows.iter().flat_map(|row| row.data.chunks(*classes).map(|c| c.to_vec())).collect())
+                };
+                let (loss, grad) = Loss::SoftmaxCrossEntropy.compute(&blocks(output), &blocks(target));
+                let grad = Matrix::new(grad.rows.chunks(steps).map(|r| r.iter().flat_map(|g| g.data.clone()).collect()).collect());
+                (loss, grad)
+            }
+        }
+    }
+
+    /// Row-wise softmax.
+    pub fn softmax(logits: &Matrix) -> Matrix {
+        let rows = logits
+            .rows
+            .iter()
+            .map(|row| {
+                let max = row.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+                let exp: Vec<f64> = row.iter().map(|&z| (z - max).exp()).collect();
+                let sum: f64 = exp.iter().sum();
+                exp.into_iter().map(|e| e / sum).collect()
+            })
+            .collect();
+        Matrix::new(rows)
+    }
+}
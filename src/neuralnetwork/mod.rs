@@ -1,3 +1,27 @@
+pub mod attention;
+pub mod convolution;
+pub mod layers;
+pub mod loss;
 pub mod neuralnetwork;
+pub mod normalization;
+pub mod optimizer;
+pub mod recurrent;
+pub mod regularization;
+pub mod sequential;
 
-pub use neuralnetwork::NeuralNetwork;
\ No newline at end of file
+pub use attention::{
+    scaled_dot_product_attention, LearnedPositionalEncoding, Mask, MultiHeadAttention, SinusoidalEncoding,
+    TransformerEncoderBlock,
+};
+pub use convolution::{AvgPool2D, Conv2D, Flatten, GlobalAvgPool, MaxPool2D, Shape};
+pub use layers::{Dense, Layer, ReLU, Sigmoid, Tanh, TimeDistributed};
+pub use loss::Loss;
+pub use neuralnetwork::NeuralNetwork;
+pub use normalization::{BatchNorm, LayerNorm, Normalization};
+pub use optimizer::{Adam, Optimizer, SGD};
+pub use recurrent::{
+    pack_sequences, unpack_sequence, Bidirectional, Cell, GRUCell, LSTMCell, RNNCell, Recurrent, GRU, LSTM, RNN,
+};
+pub use regularization::{Dropout, Regularizer};
+pub use sequential::{Sequential, SequentialBuilder};
+pub use crate::utils::initializer::Initializer;
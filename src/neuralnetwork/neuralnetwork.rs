@@ -1,9 +1,19 @@
 use crate::utils::layer::Layer;
+use crate::utils::initializer::Initializer;
 use crate::utils::activation::{sigmoid, sigmoid_derivative};
-use crate::math::Vector;
+use crate::math::{Matrix, Vector};
+use crate::neuralnetwork::normalization::{BatchNorm, LayerNorm, Normalization};
+use crate::neuralnetwork::regularization::{Dropout, Regularizer};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::ops::Range;
 /// A multi-layer perceptron neural network.
 pub struct NeuralNetwork {
     pub layers: Vec<Layer>,
+    pub regularizers: Vec<Regularizer>, // One per layer, applied to non-bias weights
+    pub dropouts: Vec<Option<Dropout>>, // One per layer, applied to that layer's output
+    pub normalizations: Vec<Option<Normalization>>, // One per layer, applied before the activation
+    pub training: bool,                 // Dropout is only active in training mode
 }
 
 impl NeuralNetwork {
@@ -23,28 +33,154 @@ impl NeuralNetwork {
             let output_dim = layer_sizes[i + 1];
             layers.push(Layer::new(input_dim, output_dim));
         }
-        Self { layers }
+        let n_layers = layers.len();
+        Self {
+            layers,
+            regularizers: vec![Regularizer::none(); n_layers],
+            dropouts: vec![None; n_layers],
+            normalizations: (0..n_layers).map(|_| None).collect(),
+            training: false,
+        }
+    }
+
+    /// Re-initializes every layer from the given weight and bias initializers,
+    /// drawing from an RNG seeded with `seed` for reproducibility.
+    pub fn with_initializer(mut self, weights: Initializer, biases: Initializer, seed: u64) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        for l in 0..self.layers.len() {
+            self.initialize_layer(l, &weights, &biases, &mut rng);
+        }
+        self
+    }
+
+    /// Re-initializes a single layer from the given weight and bias initializers.
+    pub fn initialize_layer<R: Rng + ?Sized>(
+        &mut self,
+        layer: usize,
+        weights: &Initializer,
+        biases: &Initializer,
+        rng: &mut R,
+    ) {
+        assert!(layer < self.layers.len(), "Layer index out of bounds");
+        let output_dim = self.layers[layer].weights.row_count();
+        let input_dim = self.layers[layer].weights.cols - 1;
+        self.layers[layer] = Layer::with_initializer(input_dim, output_dim, weights, biases, rng);
+    }
+
+    /// Applies L1/L2 weight decay to every layer (bias columns excluded).
+    pub fn with_weight_decay(mut self, l1: f64, l2: f64) -> Self {
+        for regularizer in &mut self.regularizers {
+            let max_norm = regularizer.max_norm;
+            *regularizer = Regularizer::new(l1, l2);
+            regularizer.max_norm = max_norm;
+        }
+        self
+    }
+
+    /// Constrains the incoming weight norm of every neuron in every layer.
+    pub fn with_max_norm(mut self, max_norm: f64) -> Self {
+        assert!(max_norm > 0.0, "Max-norm constraint must be positive");
+        for regularizer in &mut self.regularizers {
+            regularizer.max_norm = Some(max_norm);
+        }
+        self
+    }
+
+    /// Applies inverted dropout to the outputs of every hidden layer.
+    pub fn with_dropout(mut self, rate: f64) -> Self {
+        let last = self.layers.len() - 1;
+        for dropout in &mut self.dropouts[..last] {
+            *dropout = Some(Dropout::new(rate));
+        }
+        self
+    }
+
+    /// Inserts batch normalization between every hidden layer's weighted sum
+    /// and its activation.
+    pub fn with_batch_norm(mut self) -> Self {
+        for l in 0..self.layers.len() - 1 {
+            let features = self.layers[l].weights.row_count();
+            self.normalizations[l] = Some(Normalization::Batch(BatchNorm::new(features)));
+        }
+        self
+    }
+
+    /// Inserts layer normalization between every hidden layer's weighted sum
+    /// and its activation.
+    pub fn with_layer_norm(mut self) -> Self {
+        for l in 0..self.layers.len() - 1 {
+            let features = self.layers[l].weights.row_count();
+            self.normalizations[l] = Some(Normalization::Layer(LayerNorm::new(features)));
+        }
+        self
+    }
+
+    /// Overrides the normalization applied to the weighted sums of a single layer.
+    pub fn set_normalization(&mut self, layer: usize, normalization: Option<Normalization>) {
+        assert!(layer < self.layers.len(), "Layer index out of bounds");
+        self.normalizations[layer] = normalization;
+    }
+
+    /// Overrides the regularizer of a single layer.
+    pub fn set_regularizer(&mut self, layer: usize, regularizer: Regularizer) {
+        assert!(layer < self.layers.len(), "Layer index out of bounds");
+        self.regularizers[layer] = regularizer;
+    }
+
+    /// Overrides the dropout applied to the output of a single layer.
+    pub fn set_dropout(&mut self, layer: usize, dropout: Option<Dropout>) {
+        assert!(layer < self.layers.len(), "Layer index out of bounds");
+        self.dropouts[layer] = dropout;
+    }
+
+    /// Switches to training mode: `forward` and `predict` apply dropout.
+    pub fn train_mode(&mut self) {
+        self.training = true;
+    }
+
+    /// Switches to evaluation mode: dropout is disabled.
+    pub fn eval_mode(&mut self) {
+        self.training = false;
+    }
+
+    /// Sum of the weight penalties of all layers.
+    pub fn penalty(&self) -> f64 {
+        self.layers
+            .iter()
+            .zip(&self.regularizers)
+            .map(|(layer, regularizer)| regularizer.penalty(&layer.weights))
+            .sum()
     }
 
     /// Perform a forward pass through the network.
     /// Returns a vector of activations for each layer.
     /// The last element in the returned vector is the final output.
+    /// Dropout is applied only when the network is in training mode;
+    /// batch normalization always uses its running statistics here.
     pub fn forward(&self, input: &Vector) -> Vec<Vector> {
+        self.forward_layers(input, 0..self.layers.len())
+    }
+
+    /// Forward pass through a contiguous range of layers only, e.g. the
+    /// encoder half of an autoencoder. `input` must match the size expected
+    /// by the first layer of the range.
+    pub fn forward_layers(&self, input: &Vector, layers: Range<usize>) -> Vec<Vector> {
+        assert!(layers.end <= self.layers.len(), "Layer range out of bounds");
         let mut activations = Vec::new();
         let mut current_input = input.clone();
-        for layer in &self.layers {
-            // Extend input with bias
-            let extended = Layer::extend_with_bias(&current_input);
-            // Compute layer output: for each neuron, compute dot(weight, extended_input)
-            let mut layer_output = Vector::zeros(layer.weights.row_count());
-            for i in 0..layer.weights.row_count() {
-                let mut sum = 0.0;
-                // For each weight (including bias weight)
-                for j in 0..layer.weights.cols {
-                    sum += layer.weights[(i, j)] * extended.data[j];
+        for l in layers {
+            let mut pre_activation = self.pre_activation(l, &current_input);
+            if let Some(norm) = &self.normalizations[l] {
+                pre_activation = norm.normalize(&pre_activation);
+            }
+            // Apply activation function
+            let mut layer_output = Vector::new(pre_activation.iter().map(|&z| sigmoid(z)).collect());
+            // Apply inverted dropout to the layer output
+            if let (Some(dropout), true) = (&self.dropouts[l], self.training) {
+                let mask = dropout.mask(layer_output.len());
+                for (o, m) in layer_output.iter_mut().zip(&mask) {
+                    *o *= m;
                 }
-                // Apply activation function
-                layer_output.data[i] = sigmoid(sum);
             }
             activations.push(layer_output.clone());
             current_input = layer_output;
@@ -52,7 +188,65 @@ impl NeuralNetwork {
         activations
     }
 
-    /// Train the neural network using backpropagation.
+    /// Computes `W * [1, input]` for layer `l`.
+    fn pre_activation(&self, l: usize, input: &Vector) -> Vector {
+        let layer = &self.layers[l];
+        // Extend input with bias
+        let extended = Layer::extend_with_bias(input);
+        // Compute layer output: for each neuron, compute dot(weight, extended_input)
+        let mut output = Vector::zeros(layer.weights.row_count());
+        for i in 0..layer.weights.row_count() {
+            let mut sum = 0.0;
+            // For each weight (including bias weight)
+            for j in 0..layer.weights.cols {
+                sum += layer.weights[(i, j)] * extended.data[j];
+            }
+            output.data[i] = sum;
+        }
+        output
+    }
+
+    /// Training-mode forward pass over a mini-batch. Updates the
+    /// normalization caches and returns, per layer, the sigmoid activations
+    /// before dropout, the dropout masks and the (masked) layer outputs.
+    fn forward_batch(&mut self, batch: &[Vector]) -> BatchActivations {
+        let mut cache = BatchActivations {
+            activations: Vec::with_capacity(self.layers.len()),
+            masks: Vec::with_capacity(self.layers.len()),
+            outputs: Vec::with_capacity(self.layers.len()),
+        };
+        let mut current_inputs = batch.to_vec();
+        for l in 0..self.layers.len() {
+            let mut pre_activations: Vec<Vector> = current_inputs.iter().map(|x| self.pre_activation(l, x)).collect();
+            if let Some(norm) = &mut self.normalizations[l] {
+                pre_activations = norm.forward(&pre_activations, true);
+            }
+            let activations: Vec<Vector> = pre_activations
+                .iter()
+                .map(|z| Vector::new(z.iter().map(|&v| sigmoid(v)).collect()))
+                .collect();
+            let masks: Vec<Option<Vector>> = activations
+                .iter()
+                .map(|a| self.dropouts[l].as_ref().map(|dropout| dropout.mask(a.len())))
+                .collect();
+            let outputs: Vec<Vector> = activations
+                .iter()
+                .zip(&masks)
+                .map(|(a, mask)| match mask {
+                    Some(mask) => Vector::new(a.iter().zip(mask).map(|(x, m)| x * m).collect()),
+                    None => a.clone(),
+                })
+                .collect();
+            current_inputs = outputs.clone();
+            cache.activations.push(activations);
+            cache.masks.push(masks);
+            cache.outputs.push(outputs);
+        }
+        cache
+    }
+
+    /// Train the neural network using backpropagation, updating the weights
+    /// after every sample. Equivalent to `train_batch` with a batch size of 1.
     ///
     /// - `inputs`: A vector of input vectors.
     /// - `targets`: A vector of target output vectors (same ordering as inputs).
@@ -65,70 +259,185 @@ impl NeuralNetwork {
         learning_rate: f64,
         epochs: usize,
     ) {
-        assert_eq!(inputs.len(), targets.len(), "Number of inputs and targets must match");
+        self.train_batch(inputs, targets, learning_rate, 1, epochs);
+    }
 
+    /// Train the neural network using mini-batch backpropagation.
+    /// Gradients are averaged over each batch of `batch_size` samples.
+    /// Dropout, normalization layers and weight penalties configured on the
+    /// network are applied during training; max-norm constraints are
+    /// enforced after each update. Batch normalization needs `batch_size > 1`,
+    /// so `train` panics on a network with a `BatchNorm` layer, and a final
+    /// batch of a single sample is merged into the one before it.
+    pub fn train_batch(
+        &mut self,
+        inputs: &[Vector],
+        targets: &[Vector],
+        learning_rate: f64,
+        batch_size: usize,
+        epochs: usize,
+    ) {
+        assert_eq!(inputs.len(), targets.len(), "Number of inputs and targets must match");
+        let batches = self.batches(inputs.len(), batch_size);
         for epoch in 0..epochs {
             let mut total_error = 0.0;
-            // Iterate over each training sample.
-            for (input, target) in inputs.iter().zip(targets.iter()) {
-                // Forward pass: compute activations for each layer.
-                let activations = self.forward(input);
-                let output = activations.last().unwrap();
-                // Compute error (target - output) for output layer.
-                let mut output_error = Vector::zeros(output.data.len());
-                for i in 0..output.data.len() {
-                    output_error.data[i] = target.data[i] - output.data[i];
-                    total_error += output_error.data[i].powi(2);
-                }
+            for batch in batches.iter().cloned() {
+                total_error += self.train_step(&inputs[batch.clone()], &targets[batch], learning_rate);
+            }
+            println!("Epoch {}: Total Error = {}", epoch + 1, total_error + self.penalty());
+        }
+    }
 
-                // Backpropagation: compute deltas for each layer.
-                // We'll store deltas in a vector corresponding to each layer.
-                let mut deltas: Vec<Vector> = Vec::with_capacity(self.layers.len());
-                // Compute delta for output layer.
-                let mut delta = Vector::zeros(output.data.len());
-                for i in 0..output.data.len() {
-                    // Using the output value to compute derivative.
-                    delta.data[i] = output_error.data[i] * sigmoid_derivative(output.data[i]);
-                }
-                deltas.push(delta);
-
-                // Compute deltas for hidden layers (backwards).
-                // We iterate from second-last layer down to first layer.
-                for l in (0..self.layers.len()-1).rev() {
-                    let current_activation = &activations[l];
-                    let next_layer = &self.layers[l+1];
-                    let next_delta = &deltas[0]; // most recent delta (for layer l+1)
-                    let mut delta_hidden = Vector::zeros(current_activation.data.len());
-                    // For each neuron in the current layer:
-                    for i in 0..current_activation.data.len() {
-                        let mut sum = 0.0;
-                        // Sum over neurons in next layer.
-                        // Note: weight index 0 in the next layer corresponds to bias, so skip that.
-                        for k in 0..next_layer.weights.row_count() {
-                            // i+1 because next layer's weight column 0 is for bias.
-                            sum += next_layer.weights[(k, i + 1)] * next_delta.data[k];
-                        }
-                        delta_hidden.data[i] = sum * sigmoid_derivative(current_activation.data[i]);
-                    }
-                    deltas.insert(0, delta_hidden); // Prepend to maintain correct order.
+    /// Index ranges of the mini-batches of `len` samples. With batch
+    /// normalization a lone trailing sample (whose variance would be zero)
+    /// joins the previous batch.
+    pub(crate) fn batches(&self, len: usize, batch_size: usize) -> Vec<Range<usize>> {
+        assert!(batch_size > 0, "Batch size must be positive");
+        let batch_norm = self.normalizations.iter().any(|n| matches!(n, Some(Normalization::Batch(_))));
+        assert!(!batch_norm || (batch_size > 1 && len != 1), "Batch normalization needs a batch size above 1");
+        let mut batches: Vec<Range<usize>> = (0..len).step_by(batch_size).map(|start| start..len.min(start + batch_size)).collect();
+        if batch_norm && batches.len() > 1 && batches.last().is_some_and(|last| last.len() == 1) {
+            let last = batches.pop().unwrap();
+            batches.last_mut().unwrap().end = last.end;
+        }
+        batches
+    }
+
+    /// Performs one gradient descent step on a mini-batch and returns its
+    /// summed squared error.
+    pub(crate) fn train_step(&mut self, inputs: &[Vector], targets: &[Vector], learning_rate: f64) -> f64 {
+        let (total_error, weight_grads) = self.backpropagate(inputs, targets);
+        self.apply_gradients(&weight_grads, learning_rate);
+        total_error
+    }
+
+    /// Gradient descent update of every layer from the given weight
+    /// gradients, applying weight decay, max-norm constraints and the
+    /// normalization parameter updates of the last backward pass.
+    pub fn apply_gradients(&mut self, weight_grads: &[Matrix], learning_rate: f64) {
+        assert_eq!(weight_grads.len(), self.layers.len(), "One gradient per layer is required");
+        for (l, (layer, grad_w)) in self.layers.iter_mut().zip(weight_grads).enumerate() {
+            let regularizer = &self.regularizers[l];
+            for i in 0..layer.weights.row_count() {
+                // Weight decay skips the bias column
+                layer.weights[(i, 0)] -= learning_rate * grad_w[(i, 0)];
+                for j in 1..layer.weights.cols {
+                    layer.weights[(i, j)] = regularizer.update(layer.weights[(i, j)], grad_w[(i, j)], learning_rate);
                 }
+            }
+            regularizer.apply_max_norm(&mut layer.weights);
+            if let Some(norm) = &mut self.normalizations[l] {
+                norm.update(learning_rate);
+            }
+        }
+    }
+
+    /// Training-mode loss of a mini-batch: `Σ ||target - output||² / (2 * batch_size)`,
+    /// the objective whose gradient `gradients` returns (weight penalties excluded).
+    pub fn batch_loss(&mut self, inputs: &[Vector], targets: &[Vector]) -> f64 {
+        let cache = self.forward_batch(inputs);
+        let outputs = cache.outputs.last().unwrap();
+        let total_error: f64 = outputs
+            .iter()
+            .zip(targets)
+            .map(|(output, target)| output.iter().zip(target).map(|(o, t)| (t - o).powi(2)).sum::<f64>())
+            .sum();
+        total_error / (2.0 * inputs.len() as f64)
+    }
 
-                // Update weights for each layer.
-                // The input for layer 0 is the training input, extended with bias.
-                let mut layer_input = Layer::extend_with_bias(input);
-                for (l, layer) in self.layers.iter_mut().enumerate() {
-                    for i in 0..layer.weights.row_count() {
-                        for j in 0..layer.weights.cols {
-                            // Update rule: w_ij += learning_rate * delta_i * input_j
-                            layer.weights[(i, j)] += learning_rate * deltas[l].data[i] * layer_input.data[j];
-                        }
-                    }
-                    // For next layer, the input is the activation from the current layer, extended with bias.
-                    layer_input = Layer::extend_with_bias(&activations[l]);
+    /// Gradients of `batch_loss` w.r.t. each layer's weight matrix, computed
+    /// by backpropagation. Dropout must be disabled for the result to match
+    /// a deterministic loss.
+    pub fn gradients(&mut self, inputs: &[Vector], targets: &[Vector]) -> Vec<Matrix> {
+        assert_eq!(inputs.len(), targets.len(), "Number of inputs and targets must match");
+        self.backpropagate(inputs, targets).1
+    }
+
+    /// Forward and backward pass over a mini-batch. Returns the summed
+    /// squared error and the weight gradient of every layer.
+    fn backpropagate(&mut self, inputs: &[Vector], targets: &[Vector]) -> (f64, Vec<Matrix>) {
+        let batch_size = inputs.len() as f64;
+        let (total_error, weight_grads, _) = self.backpropagate_with(inputs, |outputs| {
+            // Gradient of the mean squared error w.r.t. the network outputs.
+            let mut total_error = 0.0;
+            let mut grad_outputs: Vec<Vector> = Vec::with_capacity(outputs.len());
+            for (output, target) in outputs.iter().zip(targets) {
+                let mut grad = Vector::zeros(output.len());
+                for i in 0..output.len() {
+                    let error = target[i] - output[i];
+                    total_error += error.powi(2);
+                    grad[i] = -error / batch_size;
                 }
+                grad_outputs.push(grad);
             }
-            println!("Epoch {}: Total Error = {}", epoch + 1, total_error);
+            (total_error, grad_outputs)
+        });
+        (total_error, weight_grads)
+    }
+
+    /// Training-mode forward pass over a mini-batch followed by
+    /// backpropagation of an arbitrary loss. `loss` receives the network
+    /// outputs and returns the loss value and its gradient w.r.t. each output.
+    ///
+    /// Returns the loss value, the weight gradient of every layer and the
+    /// gradient w.r.t. every input, so networks can be chained (e.g. the
+    /// decoder and encoder of a variational autoencoder).
+    pub fn backpropagate_with<F>(&mut self, inputs: &[Vector], loss: F) -> (f64, Vec<Matrix>, Vec<Vector>)
+    where
+        F: FnOnce(&[Vector]) -> (f64, Vec<Vector>),
+    {
+        // Forward pass: compute activations for each layer.
+        let cache = self.forward_batch(inputs);
+        let (value, mut grad_outputs) = loss(cache.outputs.last().unwrap());
+        assert_eq!(grad_outputs.len(), inputs.len(), "One output gradient per input is required");
+
+        // Backpropagation, from the output layer down to the first layer.
+        let mut weight_grads: Vec<Matrix> = Vec::with_capacity(self.layers.len());
+        for l in (0..self.layers.len()).rev() {
+            // Through dropout and the sigmoid: dL/dz = dL/dy * mask * a * (1 - a)
+            let mut grad_pre: Vec<Vector> = grad_outputs
+                .iter()
+                .zip(&cache.activations[l])
+                .zip(&cache.masks[l])
+                .map(|((g, a), mask)| {
+                    Vector::new(
+                        (0..g.len())
+                            .map(|i| {
+                                let m = mask.as_ref().map_or(1.0, |mask| mask[i]);
+                                g[i] * m * sigmoid_derivative(a[i])
+                            })
+                            .collect(),
+                    )
+                })
+                .collect();
+            if let Some(norm) = &mut self.normalizations[l] {
+                grad_pre = norm.backward(&grad_pre);
+            }
+
+            // Accumulate weight gradients: dL/dW = sum over batch of dL/dz (x) [1, input]
+            let layer = &self.layers[l];
+            let mut grad_w = Matrix::zeros(layer.weights.row_count(), layer.weights.cols);
+            for (s, g) in grad_pre.iter().enumerate() {
+                let layer_input = if l == 0 { &inputs[s] } else { &cache.outputs[l - 1][s] };
+                grad_w.add_assign(&g.outer_product(&Layer::extend_with_bias(layer_input)));
+            }
+            weight_grads.push(grad_w);
+
+            // Propagate to the previous layer's outputs (or the inputs).
+            // Column 0 of the weights corresponds to the bias, so skip it.
+            grad_outputs = grad_pre
+                .iter()
+                .map(|g| {
+                    Vector::new(
+                        (0..layer.weights.cols - 1)
+                            .map(|i| (0..g.len()).map(|k| layer.weights[(k, i + 1)] * g[k]).sum())
+                            .collect(),
+                    )
+                })
+                .collect();
         }
+        weight_grads.reverse();
+        (value, weight_grads, grad_outputs)
     }
 
     /// Perform a prediction for a given input.
@@ -137,4 +446,11 @@ impl NeuralNetwork {
         let activations = self.forward(input);
         activations.last().unwrap().clone()
     }
-}
\ No newline at end of file
+}
+
+/// Per-layer values from a training-mode forward pass over a mini-batch.
+struct BatchActivations {
+    activations: Vec<Vec<Vector>>,   // Sigmoid outputs before dropout
+    masks: Vec<Vec<Option<Vector>>>, // Dropout masks (None where dropout is disabled)
+    outputs: Vec<Vec<Vector>>,       // Layer outputs after dropout
+}
@@ -0,0 +1,266 @@
+use crate::math::Vector;
+
+/// Values saved by a training-mode forward pass for use in backpropagation.
+struct NormCache {
+    normalized: Vec<Vector>, // x_hat for each sample in the batch
+    inv_std: Vec<f64>,       // 1 / sqrt(var + epsilon), per feature (BatchNorm) or per sample (LayerNorm)
+}
+
+/// Batch normalization over the features of a mini-batch.
+///
+/// In training mode each feature is normalized with the statistics of the
+/// current batch and the running estimates are updated; in evaluation mode
+/// the running estimates are used instead.
+pub struct BatchNorm {
+    pub gamma: Vector,        // Learnable scale
+    pub beta: Vector,         // Learnable shift
+    pub running_mean: Vector, // Exponential moving average of batch means
+    pub running_var: Vector,  // Exponential moving average of batch variances
+    pub momentum: f64,        // Weight of the previous running estimate
+    pub epsilon: f64,         // Added to the variance for numerical stability
+    pub grad_gamma: Vector,   // Gradient of the loss w.r.t. gamma from the last backward pass
+    pub grad_beta: Vector,    // Gradient of the loss w.r.t. beta from the last backward pass
+    cache: Option<NormCache>,
+}
+
+impl BatchNorm {
+    /// Creates a batch normalization layer for `features` inputs
+    /// with unit scale, zero shift, momentum 0.9 and epsilon 1e-5.
+    pub fn new(features: usize) -> Self {
+        Self {
+            gamma: Vector::new(vec![1.0; features]),
+            beta: Vector::zeros(features),
+            running_mean: Vector::zeros(features),
+            running_var: Vector::new(vec![1.0; features]),
+            momentum: 0.9,
+            epsilon: 1e-5,
+            grad_gamma: Vector::zeros(features),
+            grad_beta: Vector::zeros(features),
+            cache: None,
+        }
+    }
+
+    /// Normalizes a single input with the running statistics (inference).
+    pub fn normalize(&self, input: &Vector) -> Vector {
+        Vector::new(
+            (0..input.len())
+                .map(|j| {
+                    let x_hat = (input[j] - self.running_mean[j]) / (self.running_var[j] + self.epsilon).sqrt();
+                    self.gamma[j] * x_hat + self.beta[j]
+                })
+                .collect(),
+        )
+    }
+
+    /// Normalizes a mini-batch. In training mode the batch statistics are
+    /// used and cached for `backward`; otherwise this is `normalize` per sample.
+    pub fn forward(&mut self, batch: &[Vector], training: bool) -> Vec<Vector> {
+        if !training {
+            return batch.iter().map(|x| self.normalize(x)).collect();
+        }
+        assert!(!batch.is_empty(), "Batch normalization requires a non-empty batch");
+        let n = batch.len() as f64;
+        let features = self.gamma.len();
+
+        let mut mean = Vector::zeros(features);
+        for x in batch {
+            mean.add_assign(x, 1.0 / n);
+        }
+        let mut var = Vector::zeros(features);
+        for x in batch {
+            for j in 0..features {
+                var[j] += (x[j] - mean[j]).powi(2) / n;
+            }
+        }
+
+        let inv_std: Vec<f64> = var.iter().map(|v| 1.0 / (v + self.epsilon).sqrt()).collect();
+        let normalized: Vec<Vector> = batch
+            .iter()
+            .map(|x| Vector::new((0..features).map(|j| (x[j] - mean[j]) * inv_std[j]).collect()))
+            .collect();
+        let output = normalized
+            .iter()
+            .map(|x_hat| Vector::new((0..features).map(|j| self.gamma[j] * x_hat[j] + self.beta[j]).collect()))
+            .collect();
+
+        // Update running statistics
+        self.running_mean.scale_assign(self.momentum);
+        self.running_mean.add_assign(&mean, 1.0 - self.momentum);
+        self.running_var.scale_assign(self.momentum);
+        self.running_var.add_assign(&var, 1.0 - self.momentum);
+
+        self.cache = Some(NormCache { normalized, inv_std });
+        output
+    }
+
+    /// Backpropagates `grad_output` (dL/dy for each sample) through the last
+    /// training-mode forward pass. Stores the parameter gradients and returns dL/dx.
+    pub fn backward(&mut self, grad_output: &[Vector]) -> Vec<Vector> {
+        let cache = self.cache.as_ref().expect("BatchNorm::backward called before a training forward pass");
+        assert_eq!(grad_output.len(), cache.normalized.len(), "Gradient batch size does not match forward pass");
+        let n = grad_output.len() as f64;
+        let features = self.gamma.len();
+
+        self.grad_gamma = Vector::zeros(features);
+        self.grad_beta = Vector::zeros(features);
+        let mut sum_dx_hat = Vector::zeros(features);
+        let mut sum_dx_hat_x_hat = Vector::zeros(features);
+        for (g, x_hat) in grad_output.iter().zip(&cache.normalized) {
+            for j in 0..features {
+                self.grad_gamma[j] += g[j] * x_hat[j];
+                self.grad_beta[j] += g[j];
+                let dx_hat = g[j] * self.gamma[j];
+                sum_dx_hat[j] += dx_hat;
+                sum_dx_hat_x_hat[j] += dx_hat * x_hat[j];
+            }
+        }
+
+        grad_output
+            .iter()
+            .zip(&cache.normalized)
+            .map(|(g, x_hat)| {
+                Vector::new(
+                    (0..features)
+                        .map(|j| {
+                            let dx_hat = g[j] * self.gamma[j];
+                            cache.inv_std[j] / n * (n * dx_hat - sum_dx_hat[j] - x_hat[j] * sum_dx_hat_x_hat[j])
+                        })
+                        .collect(),
+                )
+            })
+            .collect()
+    }
+
+    /// Applies a gradient descent step to gamma and beta.
+    pub fn update(&mut self, learning_rate: f64) {
+        self.gamma.add_assign(&self.grad_gamma, -learning_rate);
+        self.beta.add_assign(&self.grad_beta, -learning_rate);
+    }
+}
+
+/// Layer normalization over the features of each individual sample.
+/// Behaves identically in training and evaluation mode.
+pub struct LayerNorm {
+    pub gamma: Vector,      // Learnable scale
+    pub beta: Vector,       // Learnable shift
+    pub epsilon: f64,       // Added to the variance for numerical stability
+    pub grad_gamma: Vector, // Gradient of the loss w.r.t. gamma from the last backward pass
+    pub grad_beta: Vector,  // Gradient of the loss w.r.t. beta from the last backward pass
+    cache: Option<NormCache>,
+}
+
+impl LayerNorm {
+    /// Creates a layer normalization layer for `features` inputs
+    /// with unit scale, zero shift and epsilon 1e-5.
+    pub fn new(features: usize) -> Self {
+        Self {
+            gamma: Vector::new(vec![1.0; features]),
+            beta: Vector::zeros(features),
+            epsilon: 1e-5,
+            grad_gamma: Vector::zeros(features),
+            grad_beta: Vector::zeros(features),
+            cache: None,
+        }
+    }
+
+    /// Returns (x_hat, 1 / std) for a single sample.
+    fn standardize(&self, input: &Vector) -> (Vector, f64) {
+        let d = input.len() as f64;
+        let mean = input.iter().sum::<f64>() / d;
+        let var = input.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / d;
+        let inv_std = 1.0 / (var + self.epsilon).sqrt();
+        (Vector::new(input.iter().map(|x| (x - mean) * inv_std).collect()), inv_std)
+    }
+
+    /// Normalizes a single input.
+    pub fn normalize(&self, input: &Vector) -> Vector {
+        let (x_hat, _) = self.standardize(input);
+        Vector::new((0..x_hat.len()).map(|j| self.gamma[j] * x_hat[j] + self.beta[j]).collect())
+    }
+
+    /// Normalizes each sample of a batch. In training mode the intermediate
+    /// values are cached for `backward`.
+    pub fn forward(&mut self, batch: &[Vector], training: bool) -> Vec<Vector> {
+        if !training {
+            return batch.iter().map(|x| self.normalize(x)).collect();
+        }
+        let (normalized, inv_std): (Vec<Vector>, Vec<f64>) = batch.iter().map(|x| self.standardize(x)).unzip();
+        let output = normalized
+            .iter()
+            .map(|x_hat| Vector::new((0..x_hat.len()).map(|j| self.gamma[j] * x_hat[j] + self.beta[j]).collect()))
+            .collect();
+        self.cache = Some(NormCache { normalized, inv_std });
+        output
+    }
+
+    /// Backpropagates `grad_output` through the last training-mode forward pass.
+    /// Stores the parameter gradients and returns dL/dx.
+    pub fn backward(&mut self, grad_output: &[Vector]) -> Vec<Vector> {
+        let cache = self.cache.as_ref().expect("LayerNorm::backward called before a training forward pass");
+        assert_eq!(grad_output.len(), cache.normalized.len(), "Gradient batch size does not match forward pass");
+        let features = self.gamma.len();
+        let d = features as f64;
+
+        self.grad_gamma = Vector::zeros(features);
+        self.grad_beta = Vector::zeros(features);
+        let mut grad_input = Vec::with_capacity(grad_output.len());
+        for ((g, x_hat), &inv_std) in grad_output.iter().zip(&cache.normalized).zip(&cache.inv_std) {
+            let dx_hat: Vec<f64> = (0..features).map(|j| g[j] * self.gamma[j]).collect();
+            let sum_dx_hat: f64 = dx_hat.iter().sum();
+            let sum_dx_hat_x_hat: f64 = dx_hat.iter().zip(x_hat).map(|(a, b)| a * b).sum();
+            for j in 0..features {
+                self.grad_gamma[j] += g[j] * x_hat[j];
+                self.grad_beta[j] += g[j];
+            }
+            grad_input.push(Vector::new(
+                (0..features)
+                    .map(|j| inv_std / d * (d * dx_hat[j] - sum_dx_hat - x_hat[j] * sum_dx_hat_x_hat))
+                    .collect(),
+            ));
+        }
+        grad_input
+    }
+
+    /// Applies a gradient descent step to gamma and beta.
+    pub fn update(&mut self, learning_rate: f64) {
+        self.gamma.add_assign(&self.grad_gamma, -learning_rate);
+        self.beta.add_assign(&self.grad_beta, -learning_rate);
+    }
+}
+
+/// A normalization step applied to the pre-activations of a dense layer.
+pub enum Normalization {
+    Batch(BatchNorm),
+    Layer(LayerNorm),
+}
+
+impl Normalization {
+    /// Normalizes a single input using inference-time statistics.
+    pub fn normalize(&self, input: &Vector) -> Vector {
+        match self {
+            Normalization::Batch(norm) => norm.normalize(input),
+            Normalization::Layer(norm) => norm.normalize(input),
+        }
+    }
+
+    pub fn forward(&mut self, batch: &[Vector], training: bool) -> Vec<Vector> {
+        match self {
+            Normalization::Batch(norm) => norm.forward(batch, training),
+            Normalization::Layer(norm) => norm.forward(batch, training),
+        }
+    }
+
+    pub fn backward(&mut self, grad_output: &[Vector]) -> Vec<Vector> {
+        match self {
+            Normalization::Batch(norm) => norm.backward(grad_output),
+            Normalization::Layer(norm) => norm.backward(grad_output),
+        }
+    }
+
+    pub fn update(&mut self, learning_rate: f64) {
+        match self {
+            Normalization::Batch(norm) => norm.update(learning_rate),
+            Normalization::Layer(norm) => norm.update(learning_rate),
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use crate::math::Matrix;
+use std::collections::HashMap;
+
+/// Updates parameters from their gradients.
+///
+/// `key` identifies the layer the parameters belong to, so stateful
+/// optimizers can keep separate moment estimates per layer.
+pub trait Optimizer {
+    fn step(&mut self, key: usize, parameters: &mut [Matrix], gradients: &[Matrix]);
+}
+
+/// Stochastic gradient descent with optional momentum.
+pub struct SGD {
+    pub learning_rate: f64,
+    pub momentum: f64,
+    velocity: HashMap<usize, Vec<Matrix>>,
+}
+
+impl SGD {
+    pub fn new(learning_rate: f64) -> Self {
+        Self::with_momentum(learning_rate, 0.0)
+    }
+
+    pub fn with_momentum(learning_rate: f64, momentum: f64) -> Self {
+        Self { learning_rate, momentum, velocity: HashMap::new() }
+    }
+}
+
+impl Optimizer for SGD {
+    fn step(&mut self, key: usize, parameters: &mut [Matrix], gradients: &[Matrix]) {
+        let velocity = self.velocity.entry(key).or_insert_with(|| {
+            gradients.iter().map(|g| Matrix::zeros(g.row_count(), g.col_count())).collect()
+        });
+        for ((param, grad), v) in parameters.iter_mut().zip(gradients).zip(velocity.iter_mut()) {
+            // v = momentum * v - lr * g; w = w + v
+            v.scale(self.momentum);
+            let mut step = grad.clone();
+            step.scale(-self.learning_rate);
+            v.add_assign(&step);
+            param.add_assign(v);
+        }
+    }
+}
+
+/// Adam: adaptive moment estimation with bias correction.
+pub struct Adam {
+    pub learning_rate: f64,
+    pub beta1: f64,
+    pub beta2: f64,
+    pub epsilon: f64,
+    moments: HashMap<usize, (usize, Vec<Matrix>, Vec<Matrix>)>, // (step count, first moments, second moments)
+}
+
+impl Adam {
+    /// Creates an Adam optimizer with the usual defaults (0.9, 0.999, 1e-8).
+    pub fn new(learning_rate: f64) -> Self {
+        Self { learning_rate, beta1: 0.9, beta2: 0.999, epsilon: 1e-8, moments: HashMap::new() }
+    }
+}
+
+impl Optimizer for Adam {
+    fn step(&mut self, key: usize, parameters: &mut [Matrix], gradients: &[Matrix]) {
+        let (t, m, v) = self.moments.entry(key).or_insert_with(|| {
+            let zeros: Vec<Matrix> = gradients.iter().map(|g| Matrix::zeros(g.row_count(), g.col_count())).collect();
+            (0, zeros.clone(), zeros)
+        });
+        *t += 1;
+        let correction1 = 1.0 - self.beta1.powi(*t as i32);
+        let correction2 = 1.0 - self.beta2.powi(*t as i32);
+        for (k, (param, grad)) in parameters.iter_mut().zip(gradients).enumerate() {
+            for i in 0..param.row_count() {
+                for j in 0..param.cols {
+                    let g = grad[(i, j)];
+                    m[k][(i, j)] = self.beta1 * m[k][(i, j)] + (1.0 - self.beta1) * g;
+                    v[k][(i, j)] = self.beta2 * v[k][(i, j)] + (1.0 - self.beta2) * g * g;
+                    let m_hat = m[k][(i, j)] / correction1;
+                    let v_hat = v[k][(i, j)] / correction2;
+                    param[(i, j)] -= self.learning_rate * m_hat / (v_hat.sqrt() + self.epsilon);
+                }
+            }
+        }
+    }
+}
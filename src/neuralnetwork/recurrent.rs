@@ -0,0 +1,605 @@
+use crate::math::{Matrix, Vector};
+use crate::neuralnetwork::layers::Layer;
+use crate::utils::activation::sigmoid;
+use crate::utils::initializer::Initializer;
+use rand::Rng;
+
+/// Packs equally long sequences into a batch: one row per sequence with the
+/// time steps laid out one after the other (`T x D` values per row).
+pub fn pack_sequences(sequences: &[Vec<Vector>]) -> Matrix {
+    Matrix::from_vector(
+        sequences
+            .iter()
+            .map(|sequence| Vector::new(sequence.iter().flat_map(|x| x.data.iter().copied()).collect()))
+            .collect(),
+    )
+}
+
+/// Splits a packed row back into its time steps of `features` values each.
+pub fn unpack_sequence(row: &Vector, features: usize) -> Vec<Vector> {
+    row.data.chunks(features).map(|x| Vector::new(x.to_vec())).collect()
+}
+
+/// Concatenates matrices with the same number of rows side by side.
+fn hstack(parts: &[&Matrix]) -> Matrix {
+    Matrix::from_vector(
+        (0..parts[0].row_count())
+            .map(|i| Vector::new(parts.iter().flat_map(|p| p[i].data.iter().copied()).collect()))
+            .collect(),
+    )
+}
+
+/// Input, recurrent and bias weights of a cell with `gates` stacked gates.
+pub struct GateWeights {
+    pub input: Matrix,       // Dimensions: input_size x (gates * hidden_size)
+    pub hidden: Matrix,      // Dimensions: hidden_size x (gates * hidden_size)
+    pub bias: Matrix,        // Dimensions: 1 x (gates * hidden_size)
+    pub grad_input: Matrix,  // Gradient of the loss w.r.t. the input weights
+    pub grad_hidden: Matrix, // Gradient of the loss w.r.t. the recurrent weights
+    pub grad_bias: Matrix,   // Gradient of the loss w.r.t. the bias
+}
+
+impl GateWeights {
+    fn new<R: Rng + ?Sized>(
+        input_size: usize,
+        hidden_size: usize,
+        gates: usize,
+        input: &Initializer,
+        hidden: &Initializer,
+        rng: &mut R,
+    ) -> Self {
+        let width = gates * hidden_size;
+        Self {
+            input: input.initialize(input_size, width, rng).transpose(),
+            hidden: hidden.initialize(hidden_size, width, rng).transpose(),
+            bias: Matrix::zeros(1, width),
+            grad_input: Matrix::zeros(input_size, width),
+            grad_hidden: Matrix::zeros(hidden_size, width),
+            grad_bias: Matrix::zeros(1, width),
+        }
+    }
+
+    /// `x W_input + b`
+    fn input_part(&self, x: &Matrix) -> Matrix {
+        let mut a = x.gemm(&self.input);
+        for row in &mut a.rows {
+            row.add_assign(&self.bias[0], 1.0);
+        }
+        a
+    }
+
+    /// `h W_hidden`
+    fn hidden_part(&self, h: &Matrix) -> Matrix {
+        h.gemm(&self.hidden)
+    }
+
+    /// Accumulates the gradients of `input_part` and returns dL/dx.
+    fn backward_input(&mut self, x: &Matrix, grad: &Matrix) -> Matrix {
+        self.grad_input.add_assign(&x.transpose().gemm(grad));
+        self.grad_bias[0].add_assign(&grad.column_sums(), 1.0);
+        grad.gemm(&self.input.transpose())
+    }
+
+    /// Accumulates the gradient of `hidden_part` and returns dL/dh.
+    fn backward_hidden(&mut self, h: &Matrix, grad: &Matrix) -> Matrix {
+        self.grad_hidden.add_assign(&h.transpose().gemm(grad));
+        grad.gemm(&self.hidden.transpose())
+    }
+
+    fn zero_gradients(&mut self) {
+        self.grad_input = Matrix::zeros(self.input.row_count(), self.input.cols);
+        self.grad_hidden = Matrix::zeros(self.hidden.row_count(), self.hidden.cols);
+        self.grad_bias = Matrix::zeros(1, self.bias.cols);
+    }
+}
+
+/// A single time step of a recurrent layer.
+///
+/// The state is a list of `batch x hidden_size` matrices whose first entry
+/// is the hidden state `h` exposed as the layer's output (an LSTM also
+/// carries its cell state).
+pub trait Cell {
+    /// Name used in layer summaries.
+    const NAME: &'static str;
+    /// Values saved by `step` for `step_backward`.
+    type Cache;
+
+    fn weights(&self) -> &GateWeights;
+    fn weights_mut(&mut self) -> &mut GateWeights;
+
+    /// Number of state matrices carried between time steps.
+    fn state_count(&self) -> usize {
+        1
+    }
+
+    /// Computes the next state from the input at one time step.
+    fn step(&self, input: &Matrix, state: &[Matrix]) -> (Vec<Matrix>, Self::Cache);
+
+    /// Given dL/d(next state), accumulates the weight gradients and returns
+    /// dL/d(input) and dL/d(previous state).
+    fn step_backward(&mut self, cache: &Self::Cache, grad_state: &[Matrix]) -> (Matrix, Vec<Matrix>);
+
+    fn input_size(&self) -> usize {
+        self.weights().input.row_count()
+    }
+
+    fn hidden_size(&self) -> usize {
+        self.weights().hidden.row_count()
+    }
+}
+
+/// Elman cell: `h' = tanh(x W_x + h W_h + b)`.
+pub struct RNNCell {
+    pub weights: GateWeights,
+}
+
+impl RNNCell {
+    pub fn with_initializer<R: Rng + ?Sized>(
+        input_size: usize,
+        hidden_size: usize,
+        input: &Initializer,
+        hidden: &Initializer,
+        rng: &mut R,
+    ) -> Self {
+        Self { weights: GateWeights::new(input_size, hidden_size, 1, input, hidden, rng) }
+    }
+}
+
+/// (x, h_prev, h')
+pub struct RNNCache(Matrix, Matrix, Matrix);
+
+impl Cell for RNNCell {
+    const NAME: &'static str = "RNN";
+    type Cache = RNNCache;
+
+    fn weights(&self) -> &GateWeights {
+        &self.weights
+    }
+
+    fn weights_mut(&mut self) -> &mut GateWeights {
+        &mut self.weights
+    }
+
+    fn step(&self, input: &Matrix, state: &[Matrix]) -> (Vec<Matrix>, RNNCache) {
+        let h = self.weights.input_part(input).add(&self.weights.hidden_part(&state[0])).map(f64::tanh);
+        (vec![h.clone()], RNNCache(input.clone(), state[0].clone(), h))
+    }
+
+    fn step_backward(&mut self, cache: &RNNCache, grad_state: &[Matrix]) -> (Matrix, Vec<Matrix>) {
+        let RNNCache(x, h_prev, h) = cache;
+        let da = grad_state[0].hadamard(&h.map(|v| 1.0 - v * v));
+        let dx = self.weights.backward_input(x, &da);
+        let dh = self.weights.backward_hidden(h_prev, &da);
+        (dx, vec![dh])
+    }
+}
+
+/// Long short-term memory cell with input, forget, cell and output gates
+/// (stacked in that order in the weight matrices).
+pub struct LSTMCell {
+    pub weights: GateWeights,
+}
+
+impl LSTMCell {
+    /// The forget gate bias starts at 1 so that the cell remembers by default.
+    pub fn with_initializer<R: Rng + ?Sized>(
+        input_size: usize,
+        hidden_size: usize,
+        input: &Initializer,
+        hidden: &Initializer,
+        rng: &mut R,
+    ) -> Self {
+        let mut weights = GateWeights::new(input_size, hidden_size, 4, input, hidden, rng);
+        for j in hidden_size..2 * hidden_size {
+            weights.bias[(0, j)] = 1.0;
+        }
+        Self { weights }
+    }
+}
+
+/// Inputs, previous state and gate activations of one LSTM step.
+pub struct LSTMCache {
+    x: Matrix,
+    h_prev: Matrix,
+    c_prev: Matrix,
+    i: Matrix,
+    f: Matrix,
+    g: Matrix,
+    o: Matrix,
+    tanh_c: Matrix,
+}
+
+impl Cell for LSTMCell {
+    const NAME: &'static str = "LSTM";
+    type Cache = LSTMCache;
+
+    fn weights(&self) -> &GateWeights {
+        &self.weights
+    }
+
+    fn weights_mut(&mut self) -> &mut GateWeights {
+        &mut self.weights
+    }
+
+    fn state_count(&self) -> usize {
+        2
+    }
+
+    fn step(&self, input: &Matrix, state: &[Matrix]) -> (Vec<Matrix>, LSTMCache) {
+        let n = self.hidden_size();
+        let a = self.weights.input_part(input).add(&self.weights.hidden_part(&state[0]));
+        let i = a.columns(0, n).map(sigmoid);
+        let f = a.columns(n, 2 * n).map(sigmoid);
+        let g = a.columns(2 * n, 3 * n).map(f64::tanh);
+        let o = a.columns(3 * n, 4 * n).map(sigmoid);
+        let c = f.hadamard(&state[1]).add(&i.hadamard(&g));
+        let tanh_c = c.map(f64::tanh);
+        let h = o.hadamard(&tanh_c);
+        let cache = LSTMCache { x: input.clone(), h_prev: state[0].clone(), c_prev: state[1].clone(), i, f, g, o, tanh_c };
+        (vec![h, c], cache)
+    }
+
+    fn step_backward(&mut self, cache: &LSTMCache, grad_state: &[Matrix]) -> (Matrix, Vec<Matrix>) {
+        let (dh, dc) = (&grad_state[0], &grad_state[1]);
+        let d_o = dh.hadamard(&cache.tanh_c);
+        let dc = dc.add(&dh.hadamard(&cache.o).hadamard(&cache.tanh_c.map(|t| 1.0 - t * t)));
+        let di = dc.hadamard(&cache.g);
+        let dg = dc.hadamard(&cache.i);
+        let df = dc.hadamard(&cache.c_prev);
+        let dc_prev = dc.hadamard(&cache.f);
+
+        let sigmoid_grad = |d: &Matrix, s: &Matrix| d.hadamard(&s.map(|v| v * (1.0 - v)));
+        let da = hstack(&[
+            &sigmoid_grad(&di, &cache.i),
+            &sigmoid_grad(&df, &cache.f),
+            &dg.hadamard(&cache.g.map(|v| 1.0 - v * v)),
+            &sigmoid_grad(&d_o, &cache.o),
+        ]);
+        let dx = self.weights.backward_input(&cache.x, &da);
+        let dh_prev = self.weights.backward_hidden(&cache.h_prev, &da);
+        (dx, vec![dh_prev, dc_prev])
+    }
+}
+
+/// Gated recurrent unit with update, reset and candidate gates:
+///
+/// `z = σ(x W_z + h U_z + b_z)`, `r = σ(x W_r + h U_r + b_r)`,
+/// `n = tanh(x W_n + b_n + r ⊙ (h U_n))`, `h' = (1 - z) ⊙ n + z ⊙ h`.
+pub struct GRUCell {
+    pub weights: GateWeights,
+}
+
+impl GRUCell {
+    pub fn with_initializer<R: Rng + ?Sized>(
+        input_size: usize,
+        hidden_size: usize,
+        input: &Initializer,
+        hidden: &Initializer,
+        rng: &mut R,
+    ) -> Self {
+        Self { weights: GateWeights::new(input_size, hidden_size, 3, input, hidden, rng) }
+    }
+}
+
+/// Inputs, previous state and gate activations of one GRU step.
+pub struct GRUCache {
+    x: Matrix,
+    h_prev: Matrix,
+    z: Matrix,
+    r: Matrix,
+    n: Matrix,
+    hidden_n: Matrix, // h U_n
+}
+
+impl Cell for GRUCell {
+    const NAME: &'static str = "GRU";
+    type Cache = GRUCache;
+
+    fn weights(&self) -> &GateWeights {
+        &self.weights
+    }
+
+    fn weights_mut(&mut self) -> &mut GateWeights {
+        &mut self.weights
+    }
+
+    fn step(&self, input: &Matrix, state: &[Matrix]) -> (Vec<Matrix>, GRUCache) {
+        let size = self.hidden_size();
+        let h_prev = &state[0];
+        let a_x = self.weights.input_part(input);
+        let a_h = self.weights.hidden_part(h_prev);
+        let z = a_x.columns(0, size).add(&a_h.columns(0, size)).map(sigmoid);
+        let r = a_x.columns(size, 2 * size).add(&a_h.columns(size, 2 * size)).map(sigmoid);
+        let hidden_n = a_h.columns(2 * size, 3 * size);
+        let n = a_x.columns(2 * size, 3 * size).add(&r.hadamard(&hidden_n)).map(f64::tanh);
+        let h = n.add(&z.hadamard(&h_prev.sub(&n)));
+        (vec![h], GRUCache { x: input.clone(), h_prev: h_prev.clone(), z, r, n, hidden_n })
+    }
+
+    fn step_backward(&mut self, cache: &GRUCache, grad_state: &[Matrix]) -> (Matrix, Vec<Matrix>) {
+        let dh = &grad_state[0];
+        let dn = dh.hadamard(&cache.z.map(|z| 1.0 - z));
+        let dz = dh.hadamard(&cache.h_prev.sub(&cache.n));
+        let da_n = dn.hadamard(&cache.n.map(|v| 1.0 - v * v));
+        let dr = da_n.hadamard(&cache.hidden_n);
+        let da_z = dz.hadamard(&cache.z.map(|v| v * (1.0 - v)));
+        let da_r = dr.hadamard(&cache.r.map(|v| v * (1.0 - v)));
+
+        let dx = self.weights.backward_input(&cache.x, &hstack(&[&da_z, &da_r, &da_n]));
+        let dh_prev = self
+            .weights
+            .backward_hidden(&cache.h_prev, &hstack(&[&da_z, &da_r, &da_n.hadamard(&cache.r)]))
+            .add(&dh.hadamard(&cache.z));
+        (dx, vec![dh_prev])
+    }
+}
+
+/// A recurrent layer that runs a `Cell` over sequences.
+///
+/// Each input row holds one sequence of `T` steps of `input_size` features
+/// (see `pack_sequences`); the number of steps is inferred from the row
+/// length. The layer outputs either the final hidden state (`hidden_size`
+/// values) or, with `return_sequences`, the hidden state of every step
+/// (`T x hidden_size` values). The initial state is zero.
+pub struct Recurrent<C: Cell> {
+    pub cell: C,
+    pub return_sequences: bool,
+    pub truncation: Option<usize>, // Truncated BPTT window length
+    pub reverse: bool,             // Process the sequence from the last step to the first
+    caches: Option<Vec<C::Cache>>, // One cache per processed step
+}
+
+/// Elman recurrent layer.
+pub type RNN = Recurrent<RNNCell>;
+/// Long short-term memory layer.
+pub type LSTM = Recurrent<LSTMCell>;
+/// Gated recurrent unit layer.
+pub type GRU = Recurrent<GRUCell>;
+
+impl RNN {
+    /// Xavier-uniform input weights, orthogonal recurrent weights, zero biases.
+    pub fn new(input_size: usize, hidden_size: usize) -> Self {
+        let orthogonal = Initializer::Orthogonal { gain: 1.0 };
+        Self::from_cell(RNNCell::with_initializer(input_size, hidden_size, &Initializer::XavierUniform, &orthogonal, &mut rand::rng()))
+    }
+}
+
+impl LSTM {
+    /// Xavier-uniform input weights, orthogonal recurrent weights, zero biases
+    /// except for the forget gate.
+    pub fn new(input_size: usize, hidden_size: usize) -> Self {
+        let orthogonal = Initializer::Orthogonal { gain: 1.0 };
+        Self::from_cell(LSTMCell::with_initializer(input_size, hidden_size, &Initializer::XavierUniform, &orthogonal, &mut rand::rng()))
+    }
+}
+
+impl GRU {
+    /// Xavier-uniform input weights, orthogonal recurrent weights, zero biases.
+    pub fn new(input_size: usize, hidden_size: usize) -> Self {
+        let orthogonal = Initializer::Orthogonal { gain: 1.0 };
+        Self::from_cell(GRUCell::with_initializer(input_size, hidden_size, &Initializer::XavierUniform, &orthogonal, &mut rand::rng()))
+    }
+}
+
+impl<C: Cell> Recurrent<C> {
+    /// Wraps a cell. By default only the final hidden state is returned and
+    /// gradients flow through the whole sequence.
+    pub fn from_cell(cell: C) -> Self {
+        Self { cell, return_sequences: false, truncation: None, reverse: false, caches: None }
+    }
+
+    /// Output the hidden state of every time step instead of only the last one.
+    pub fn with_return_sequences(mut self) -> Self {
+        self.return_sequences = true;
+        self
+    }
+
+    /// Truncated backpropagation through time: the gradient carried along
+    /// the recurrent state is cut every `steps` time steps.
+    pub fn with_truncation(mut self, steps: usize) -> Self {
+        assert!(steps > 0, "Truncation window must be positive");
+        self.truncation = Some(steps);
+        self
+    }
+
+    /// Process sequences from the last time step to the first. Outputs stay
+    /// aligned with the input time steps.
+    pub fn reversed(mut self) -> Self {
+        self.reverse = true;
+        self
+    }
+
+    fn steps(&self, width: usize) -> usize {
+        width / self.cell.input_size()
+    }
+
+    /// Time step processed at position `s` of the recurrence.
+    fn time(&self, s: usize, steps: usize) -> usize {
+        if self.reverse { steps - 1 - s } else { s }
+    }
+
+    /// Runs a single sequence in inference mode and returns the hidden state
+    /// of every step (or just the final one, as a single element).
+    pub fn forward_sequence(&mut self, sequence: &[Vector]) -> Vec<Vector> {
+        let output = self.forward(&pack_sequences(&[sequence.to_vec()]), false);
+        unpack_sequence(&output[0], self.cell.hidden_size())
+    }
+}
+
+impl<C: Cell> Layer for Recurrent<C> {
+    fn name(&self) -> String {
+        let mode = if self.return_sequences { ", sequences" } else { "" };
+        format!("{}({} -> {}{})", C::NAME, self.cell.input_size(), self.cell.hidden_size(), mode)
+    }
+
+    fn output_size(&self, input_size: usize) -> Result<usize, String> {
+        let features = self.cell.input_size();
+        if input_size == 0 || !input_size.is_multiple_of(features) {
+            return Err(format!(
+                "expects a multiple of {} input features (one block per time step) but receives {}",
+                features, input_size
+            ));
+        }
+        let hidden = self.cell.hidden_size();
+        Ok(if self.return_sequences { input_size / features * hidden } else { hidden })
+    }
+
+    fn forward(&mut self, input: &Matrix, _training: bool) -> Matrix {
+        let features = self.cell.input_size();
+        let hidden = self.cell.hidden_size();
+        let steps = self.steps(input.col_count());
+        assert_eq!(steps * features, input.col_count(), "Input is not a whole number of time steps");
+        let batch = input.row_count();
+
+        let mut state = vec![Matrix::zeros(batch, hidden); self.cell.state_count()];
+        let mut output = Matrix::zeros(batch, if self.return_sequences { steps * hidden } else { hidden });
+        let mut caches = Vec::with_capacity(steps);
+        for s in 0..steps {
+            let t = self.time(s, steps);
+            let (next, cache) = self.cell.step(&input.columns(t * features, (t + 1) * features), &state);
+            if self.return_sequences {
+                output.set_columns(t * hidden, &next[0]);
+            }
+            state = next;
+            caches.push(cache);
+        }
+        if !self.return_sequences {
+            output = state.swap_remove(0);
+        }
+        self.caches = Some(caches);
+        output
+    }
+
+    fn backward(&mut self, grad_output: &Matrix) -> Matrix {
+        let caches = self.caches.take().expect("Recurrent::backward called before forward");
+        let features = self.cell.input_size();
+        let hidden = self.cell.hidden_size();
+        let steps = caches.len();
+        let batch = grad_output.row_count();
+        self.cell.weights_mut().zero_gradients();
+
+        let mut grad_input = Matrix::zeros(batch, steps * features);
+        let mut grad_state = vec![Matrix::zeros(batch, hidden); self.cell.state_count()];
+        for s in (0..steps).rev() {
+            let t = self.time(s, steps);
+            if self.return_sequences {
+                grad_state[0].add_assign(&grad_output.columns(t * hidden, (t + 1) * hidden));
+            } else if s == steps - 1 {
+                grad_state[0].add_assign(grad_output);
+            }
+            let (dx, previous) = self.cell.step_backward(&caches[s], &grad_state);
+            grad_input.set_columns(t * features, &dx);
+            grad_state = match self.truncation {
+                Some(window) if s % window == 0 => vec![Matrix::zeros(batch, hidden); self.cell.state_count()],
+                _ => previous,
+            };
+        }
+        self.caches = Some(caches);
+        grad_input
+    }
+
+    fn parameters(&self) -> Vec<Matrix> {
+        let w = self.cell.weights();
+        vec![w.input.clone(), w.hidden.clone(), w.bias.clone()]
+    }
+
+    fn gradients(&self) -> Vec<Matrix> {
+        let w = self.cell.weights();
+        vec![w.grad_input.clone(), w.grad_hidden.clone(), w.grad_bias.clone()]
+    }
+
+    fn set_parameters(&mut self, parameters: &[Matrix]) {
+        let w = self.cell.weights_mut();
+        w.input = parameters[0].clone();
+        w.hidden = parameters[1].clone();
+        w.bias = parameters[2].clone();
+    }
+}
+
+/// Runs one recurrent layer forward and another backward in time and
+/// concatenates their hidden states, `[forward; backward]`, per time step
+/// (or of their final states).
+pub struct Bidirectional<C: Cell> {
+    pub forward: Recurrent<C>,
+    pub backward: Recurrent<C>,
+}
+
+impl<C: Cell> Bidirectional<C> {
+    /// `backward` is switched to reverse processing. Both layers must agree
+    /// on their input size and output mode.
+    pub fn new(forward: Recurrent<C>, backward: Recurrent<C>) -> Self {
+        assert_eq!(forward.cell.input_size(), backward.cell.input_size(), "Both directions need the same input size");
+        assert_eq!(forward.return_sequences, backward.return_sequences, "Both directions need the same output mode");
+        Self { forward, backward: backward.reversed() }
+    }
+
+    /// Splits an output-sized matrix into the parts of the two directions.
+    fn split(&self, m: &Matrix) -> (Matrix, Matrix) {
+        let (hf, hb) = (self.forward.cell.hidden_size(), self.backward.cell.hidden_size());
+        if !self.forward.return_sequences {
+            return (m.columns(0, hf), m.columns(hf, hf + hb));
+        }
+        let steps = m.col_count() / (hf + hb);
+        let mut f = Matrix::zeros(m.row_count(), steps * hf);
+        let mut b = Matrix::zeros(m.row_count(), steps * hb);
+        for t in 0..steps {
+            let offset = t * (hf + hb);
+            f.set_columns(t * hf, &m.columns(offset, offset + hf));
+            b.set_columns(t * hb, &m.columns(offset + hf, offset + hf + hb));
+        }
+        (f, b)
+    }
+
+    /// Interleaves the outputs of the two directions.
+    fn join(&self, f: &Matrix, b: &Matrix) -> Matrix {
+        if !self.forward.return_sequences {
+            return hstack(&[f, b]);
+        }
+        let (hf, hb) = (self.forward.cell.hidden_size(), self.backward.cell.hidden_size());
+        let steps = f.col_count() / hf;
+        let mut output = Matrix::zeros(f.row_count(), steps * (hf + hb));
+        for t in 0..steps {
+            output.set_columns(t * (hf + hb), &f.columns(t * hf, (t + 1) * hf));
+            output.set_columns(t * (hf + hb) + hf, &b.columns(t * hb, (t + 1) * hb));
+        }
+        output
+    }
+}
+
+impl<C: Cell> Layer for Bidirectional<C> {
+    fn name(&self) -> String {
+        format!("Bidirectional({}, {})", self.forward.name(), self.backward.name())
+    }
+
+    fn output_size(&self, input_size: usize) -> Result<usize, String> {
+        Ok(self.forward.output_size(input_size)? + self.backward.output_size(input_size)?)
+    }
+
+    fn forward(&mut self, input: &Matrix, training: bool) -> Matrix {
+        let f = self.forward.forward(input, training);
+        let b = self.backward.forward(input, training);
+        self.join(&f, &b)
+    }
+
+    fn backward(&mut self, grad_output: &Matrix) -> Matrix {
+        let (f, b) = self.split(grad_output);
+        self.forward.backward(&f).add(&self.backward.backward(&b))
+    }
+
+    fn parameters(&self) -> Vec<Matrix> {
+        let mut parameters = self.forward.parameters();
+        parameters.extend(self.backward.parameters());
+        parameters
+    }
+
+    fn gradients(&self) -> Vec<Matrix> {
+        let mut gradients = self.forward.gradients();
+        gradients.extend(self.backward.gradients());
+        gradients
+    }
+
+    fn set_parameters(&mut self, parameters: &[Matrix]) {
+        self.forward.set_parameters(&parameters[..3]);
+        self.backward.set_parameters(&parameters[3..]);
+    }
+}
@@ -0,0 +1,104 @@
+use crate::math::{Matrix, Vector};
+use rand::Rng;
+
+/// Weight penalties and constraints for a single dense layer.
+///
+/// Column 0 of a layer's weight matrix holds the bias, so every penalty
+/// and constraint here only touches columns `1..`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Regularizer {
+    pub l1: f64,               // L1 (Lasso) penalty strength
+    pub l2: f64,               // L2 (Ridge / weight decay) penalty strength
+    pub max_norm: Option<f64>, // Upper bound on each neuron's incoming weight norm
+}
+
+impl Regularizer {
+    /// A regularizer that leaves the weights untouched.
+    pub fn none() -> Self {
+        Self { l1: 0.0, l2: 0.0, max_norm: None }
+    }
+
+    /// Creates an L1/L2 weight decay regularizer without a max-norm constraint.
+    pub fn new(l1: f64, l2: f64) -> Self {
+        assert!(l1 >= 0.0 && l2 >= 0.0, "Regularization strengths must be non-negative");
+        Self { l1, l2, max_norm: None }
+    }
+
+    /// Computes the penalty `l1 * Σ|w| + l2 * Σw²` over the non-bias weights.
+    pub fn penalty(&self, weights: &Matrix) -> f64 {
+        let mut penalty = 0.0;
+        for row in &weights.rows {
+            for &w in row.data.iter().skip(1) {
+                penalty += self.l1 * w.abs() + self.l2 * w * w;
+            }
+        }
+        penalty
+    }
+
+    /// Derivative of the penalty with respect to a single (non-bias) weight.
+    /// At zero the L1 term uses the subgradient 0, so zero weights stay zero.
+    pub fn gradient(&self, weight: f64) -> f64 {
+        let sign = if weight == 0.0 { 0.0 } else { weight.signum() };
+        self.l1 * sign + 2.0 * self.l2 * weight
+    }
+
+    /// One gradient descent step on a single (non-bias) weight. The L2 decay
+    /// uses the weight before the step, and the L1 term is applied as a
+    /// soft threshold: a weight that would cross zero stops at exactly zero.
+    pub fn update(&self, weight: f64, gradient: f64, learning_rate: f64) -> f64 {
+        let stepped = weight - learning_rate * (gradient + 2.0 * self.l2 * weight);
+        let shrunk = stepped.abs() - learning_rate * self.l1;
+        if shrunk <= 0.0 { 0.0 } else { stepped.signum() * shrunk }
+    }
+
+    /// Rescales each neuron's incoming weights (excluding bias) so their
+    /// Euclidean norm does not exceed `max_norm`.
+    pub fn apply_max_norm(&self, weights: &mut Matrix) {
+        let Some(max_norm) = self.max_norm else {
+            return;
+        };
+        for row in &mut weights.rows {
+            let norm = row.data.iter().skip(1).map(|w| w * w).sum::<f64>().sqrt();
+            if norm > max_norm {
+                let factor = max_norm / norm;
+                for w in row.data.iter_mut().skip(1) {
+                    *w *= factor;
+                }
+            }
+        }
+    }
+}
+
+impl Default for Regularizer {
+    fn default() -> Self {
+        Self::none()
+    }
+}
+
+/// Inverted dropout: during training each unit is zeroed with probability
+/// `rate` and the survivors are scaled by `1 / (1 - rate)`, so no rescaling
+/// is needed at inference time.
+#[derive(Debug, Clone)]
+pub struct Dropout {
+    pub rate: f64, // Probability of dropping a unit
+    pub(crate) cache: Option<Matrix>, // Batch mask from the last training forward pass (Sequential models)
+}
+
+impl Dropout {
+    /// Creates a dropout configuration with the given drop probability.
+    pub fn new(rate: f64) -> Self {
+        assert!((0.0..1.0).contains(&rate), "Dropout rate must be in [0, 1)");
+        Self { rate, cache: None }
+    }
+
+    /// Samples a mask of length `len` whose entries are either 0 or `1 / (1 - rate)`.
+    pub fn mask(&self, len: usize) -> Vector {
+        let mut rng = rand::rng();
+        let keep = 1.0 - self.rate;
+        Vector::new(
+            (0..len)
+                .map(|_| if rng.random::<f64>() < keep { 1.0 / keep } else { 0.0 })
+                .collect(),
+        )
+    }
+}
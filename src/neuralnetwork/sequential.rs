@@ -0,0 +1,158 @@
+use crate::math::{Matrix, Vector};
+use crate::neuralnetwork::layers::Layer;
+use crate::neuralnetwork::loss::Loss;
+use crate::neuralnetwork::optimizer::Optimizer;
+
+/// Collects layers for a `Sequential` model and checks that consecutive
+/// layers agree on their feature counts.
+pub struct SequentialBuilder {
+    input_size: usize,
+    layers: Vec<Box<dyn Layer>>,
+}
+
+impl SequentialBuilder {
+    /// Appends a layer to the model.
+    #[allow(clippy::should_implement_trait)]
+    pub fn add<L: Layer + 'static>(mut self, layer: L) -> Self {
+        self.layers.push(Box::new(layer));
+        self
+    }
+
+    /// Appends an already boxed layer to the model.
+    pub fn add_boxed(mut self, layer: Box<dyn Layer>) -> Self {
+        self.layers.push(layer);
+        self
+    }
+
+    /// Validates the layer shapes and builds the model.
+    /// Returns a readable error naming the first incompatible layer.
+    pub fn build(self) -> Result<Sequential, String> {
+        if self.layers.is_empty() {
+            return Err("Sequential model must contain at least one layer".to_string());
+        }
+        let mut size = self.input_size;
+        let mut sizes = Vec::with_capacity(self.layers.len());
+        for (i, layer) in self.layers.iter().enumerate() {
+            size = layer
+                .output_size(size)
+                .map_err(|e| format!("Layer {} ({}) {}", i, layer.name(), e))?;
+            sizes.push(size);
+        }
+        Ok(Sequential { input_size: self.input_size, layers: self.layers, sizes })
+    }
+}
+
+/// A feed-forward model made of a stack of heterogeneous layers.
+///
+/// ```ignore
+/// let mut model = Sequential::builder(784)
+///     .add(Dense::new(784, 128))
+///     .add(ReLU::new())
+///     .add(Dropout::new(0.2))
+///     .add(Dense::new(128, 10))
+///     .build()?;
+/// ```
+pub struct Sequential {
+    pub input_size: usize,
+    pub layers: Vec<Box<dyn Layer>>,
+    sizes: Vec<usize>, // Output size of each layer
+}
+
+impl Sequential {
+    /// Starts building a model that takes `input_size` features.
+    pub fn builder(input_size: usize) -> SequentialBuilder {
+        SequentialBuilder { input_size, layers: Vec::new() }
+    }
+
+    /// Number of output features of the model.
+    pub fn output_size(&self) -> usize {
+        *self.sizes.last().unwrap()
+    }
+
+    /// One line per layer with its output size and parameter count.
+    pub fn summary(&self) -> String {
+        let mut lines = vec![format!("Input: {}", self.input_size)];
+        for (layer, size) in self.layers.iter().zip(&self.sizes) {
+            let params: usize = layer.parameters().iter().map(|p| p.row_count() * p.col_count()).sum();
+            lines.push(format!("{} -> {} ({} parameters)", layer.name(), size, params));
+        }
+        lines.join("\n")
+    }
+
+    /// Forward pass over a batch (one sample per row).
+    pub fn forward(&mut self, input: &Matrix, training: bool) -> Matrix {
+        assert_eq!(input.col_count(), self.input_size, "Input has the wrong number of features");
+        let mut output = input.clone();
+        for layer in &mut self.layers {
+            output = layer.forward(&output, training);
+        }
+        output
+    }
+
+    /// Backpropagates the gradient of the loss w.r.t. the model output
+    /// through every layer and returns the gradient w.r.t. the input.
+    pub fn backward(&mut self, grad_output: &Matrix) -> Matrix {
+        let mut grad = grad_output.clone();
+        for layer in self.layers.iter_mut().rev() {
+            grad = layer.backward(&grad);
+        }
+        grad
+    }
+
+    /// Applies one optimizer step to every layer using the gradients of the
+    /// last backward pass.
+    pub fn step(&mut self, optimizer: &mut dyn Optimizer) {
+        for (key, layer) in self.layers.iter_mut().enumerate() {
+            let mut parameters = layer.parameters();
+            if parameters.is_empty() {
+                continue;
+            }
+            optimizer.step(key, &mut parameters, &layer.gradients());
+            layer.set_parameters(&parameters);
+        }
+    }
+
+    /// Forward pass, loss, backward pass and optimizer step on one batch.
+    /// Returns the batch loss.
+    pub fn train_step(&mut self, inputs: &Matrix, targets: &Matrix, loss: Loss, optimizer: &mut dyn Optimizer) -> f64 {
+        let output = self.forward(inputs, true);
+        let (value, grad) = loss.compute(&output, targets);
+        self.backward(&grad);
+        self.step(optimizer);
+        value
+    }
+
+    /// Trains the model with mini-batches taken in order from `inputs`/`targets`.
+    /// Returns the mean batch loss of each epoch.
+    pub fn fit(
+        &mut self,
+        inputs: &[Vector],
+        targets: &[Vector],
+        loss: Loss,
+        optimizer: &mut dyn Optimizer,
+        batch_size: usize,
+        epochs: usize,
+    ) -> Vec<f64> {
+        assert_eq!(inputs.len(), targets.len(), "Mismatched input and target sizes!");
+        assert!(batch_size > 0, "Batch size must be positive");
+        let mut history = Vec::with_capacity(epochs);
+        for _ in 0..epochs {
+            let mut total = 0.0;
+            let mut batches = 0;
+            for (x, y) in inputs.chunks(batch_size).zip(targets.chunks(batch_size)) {
+                let x = Matrix::from_vector(x.to_vec());
+                let y = Matrix::from_vector(y.to_vec());
+                total += self.train_step(&x, &y, loss, optimizer);
+                batches += 1;
+            }
+            history.push(total / batches as f64);
+        }
+        history
+    }
+
+    /// Predicts the output for a single input in inference mode.
+    pub fn predict(&mut self, input: &Vector) -> Vector {
+        let output = self.forward(&Matrix::from_vector(vec![input.clone()]), false);
+        output[0].clone()
+    }
+}
@@ -0,0 +1,356 @@
+use crate::math::{LuFactorization, Matrix, Vector};
+
+/// Pivots, reduced costs and ratios smaller than this are treated as zero.
+const TOLERANCE: f64 = 1e-9;
+
+/// Dense linear program
+///
+/// ```text
+/// minimize    cᵀx
+/// subject to  Ax = b,  Gx ≤ h,  lower ≤ x ≤ upper
+/// ```
+///
+/// Variables are non-negative by default; use infinite bounds for free
+/// variables. Solved with the two-phase revised simplex method.
+#[derive(Debug, Clone)]
+pub struct LinearProgram {
+    pub c: Vector,     // Objective coefficients
+    pub a: Matrix,     // Equality constraint matrix, m x n
+    pub b: Vector,     // Equality constraint target
+    pub g: Matrix,     // Inequality constraint matrix, k x n
+    pub h: Vector,     // Inequality constraint bound
+    pub lower: Vector, // Lower bounds for x
+    pub upper: Vector, // Upper bounds for x
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LPStatus {
+    /// An optimal basic solution was found.
+    Optimal,
+    /// The constraints cannot be satisfied simultaneously.
+    Infeasible,
+    /// The objective is unbounded below on the feasible set.
+    Unbounded,
+    /// `max_iter` pivots were performed without reaching an optimum.
+    MaxIterations,
+    /// The basis matrix became numerically singular.
+    Singular,
+}
+
+/// Primal-dual solution of a `LinearProgram`.
+///
+/// The multipliers follow the same convention as `QPSolution`:
+/// `c + Aᵀν + Gᵀλ + μ = 0` at an optimum. They are only meaningful when the
+/// status is `Optimal`; otherwise `x` is the last basic solution visited.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LPSolution {
+    pub x: Vector,
+    pub equality_duals: Vector,   // ν, one per row of A
+    pub inequality_duals: Vector, // λ ≥ 0, one per row of G
+    pub bound_duals: Vector,      // μ, positive at an active upper bound, negative at a lower bound
+    pub objective: f64,
+    pub status: LPStatus,
+    pub iterations: usize, // Pivots over both phases
+}
+
+/// How an original variable is written in terms of non-negative
+/// standard-form variables.
+enum Substitution {
+    Shifted(usize, f64),   // x = lower + x'
+    Reflected(usize, f64), // x = upper - x'
+    Split(usize, usize),   // x = x⁺ - x⁻
+}
+
+/// Outcome of one simplex phase.
+enum Phase {
+    Optimal(Vector), // Simplex multipliers y with Bᵀy = c_B
+    Unbounded,
+    MaxIterations,
+    Singular,
+}
+
+impl LinearProgram {
+    /// Program `min cᵀx` subject to `x ≥ 0`.
+    pub fn new(c: Vector) -> Self {
+        let n = c.len();
+        Self {
+            c,
+            a: Matrix::zeros(0, n),
+            b: Vector::zeros(0),
+            g: Matrix::zeros(0, n),
+            h: Vector::zeros(0),
+            lower: Vector::zeros(n),
+            upper: Vector::new(vec![f64::INFINITY; n]),
+        }
+    }
+
+    /// Adds the equality constraints `Ax = b`.
+    pub fn with_equality(mut self, a: Matrix, b: Vector) -> Self {
+        assert!(a.row_count() == b.len() && (a.row_count() == 0 || a.col_count() == self.size()), "A must be m x n and b of length m");
+        self.a = Matrix { rows: a.rows, cols: self.size() };
+        self.b = b;
+        self
+    }
+
+    /// Adds the inequality constraints `Gx ≤ h`.
+    pub fn with_inequality(mut self, g: Matrix, h: Vector) -> Self {
+        assert!(g.row_count() == h.len() && (g.row_count() == 0 || g.col_count() == self.size()), "G must be k x n and h of length k");
+        self.g = Matrix { rows: g.rows, cols: self.size() };
+        self.h = h;
+        self
+    }
+
+    /// Replaces the default `x ≥ 0` with `lower ≤ x ≤ upper`.
+    pub fn with_bounds(mut self, lower: Vector, upper: Vector) -> Self {
+        assert!(lower.len() == self.size() && upper.len() == self.size(), "Bounds must have length n");
+        assert!(lower.iter().zip(upper.iter()).all(|(l, u)| l <= u), "Lower bounds must not exceed upper bounds");
+        self.lower = lower;
+        self.upper = upper;
+        self
+    }
+
+    pub fn size(&self) -> usize {
+        self.c.len()
+    }
+
+    pub fn objective(&self, x: &Vector) -> f64 {
+        self.c.dot(x)
+    }
+
+    /// Solves the program with the revised simplex method, performing at
+    /// most `max_iter` pivots.
+    ///
+    /// The program is brought to the standard form `min c'ᵀz, A'z = b', z ≥ 0`
+    /// by shifting or reflecting bounded variables, splitting free ones and
+    /// adding slacks for the inequalities and finite upper bounds. Phase one
+    /// minimizes the sum of artificial variables to find a feasible basis,
+    /// phase two minimizes the objective from there. Bland's rule (lowest
+    /// index enters, lowest index leaves on ties) prevents cycling on
+    /// degenerate vertices. The basis is LU factorized once per pivot and the
+    /// factors are reused for the `B` and `Bᵀ` solves.
+    pub fn solve(&self, max_iter: usize) -> LPSolution {
+        let n = self.size();
+        let (m_eq, m_in) = (self.a.row_count(), self.g.row_count());
+
+        // Express x in non-negative variables z
+        let mut substitutions = Vec::with_capacity(n);
+        let mut columns = 0;
+        let mut bounded = Vec::new(); // (z column, upper - lower) rows
+        for j in 0..n {
+            let (lower, upper) = (self.lower[j], self.upper[j]);
+            substitutions.push(if lower.is_finite() {
+                if upper.is_finite() {
+                    bounded.push((columns, upper - lower));
+                }
+                Substitution::Shifted(columns, lower)
+            } else if upper.is_finite() {
+                Substitution::Reflected(columns, upper)
+            } else {
+                columns += 1;
+                Substitution::Split(columns - 1, columns)
+            });
+            columns += 1;
+        }
+        // Writes Σ coefficients[j] x_j in z, returning the constant term
+        let substitute = |coefficients: &Vector, row: &mut [f64]| -> f64 {
+            let mut constant = 0.0;
+            for (j, substitution) in substitutions.iter().enumerate() {
+                let coefficient = coefficients[j];
+                match *substitution {
+                    Substitution::Shifted(k, lower) => {
+                        row[k] += coefficient;
+                        constant += coefficient * lower;
+                    }
+                    Substitution::Reflected(k, upper) => {
+                        row[k] -= coefficient;
+                        constant += coefficient * upper;
+                    }
+                    Substitution::Split(positive, negative) => {
+                        row[positive] += coefficient;
+                        row[negative] -= coefficient;
+                    }
+                }
+            }
+            constant
+        };
+
+        // Standard form rows: equalities, inequalities with slacks, upper bounds with slacks
+        let slacks = m_in + bounded.len();
+        let real = columns + slacks;
+        let m = m_eq + slacks;
+        let mut a_std = Matrix::zeros(m, real + m);
+        let mut b_std = Vector::zeros(m);
+        for (i, (row, target)) in self.a.rows.iter().zip(self.b.iter()).enumerate() {
+            b_std[i] = target - substitute(row, &mut a_std.rows[i].data);
+        }
+        for (i, (row, bound)) in self.g.rows.iter().zip(self.h.iter()).enumerate() {
+            b_std[m_eq + i] = bound - substitute(row, &mut a_std.rows[m_eq + i].data);
+            a_std[(m_eq + i, columns + i)] = 1.0;
+        }
+        for (i, &(k, width)) in bounded.iter().enumerate() {
+            let r = m_eq + m_in + i;
+            a_std[(r, k)] = 1.0;
+            a_std[(r, columns + m_in + i)] = 1.0;
+            b_std[r] = width;
+        }
+        // Make the right-hand side non-negative so the artificial basis is feasible
+        let signs: Vec<f64> = b_std.iter().map(|&v| if v < 0.0 { -1.0 } else { 1.0 }).collect();
+        for i in 0..m {
+            if signs[i] < 0.0 {
+                a_std.scale_row(i, -1.0);
+                b_std[i] = -b_std[i];
+            }
+            a_std[(i, real + i)] = 1.0;
+        }
+        let mut c_std = vec![0.0; real + m];
+        substitute(&self.c, &mut c_std); // The constant term does not affect the minimizer
+
+        // Phase one: minimize the sum of the artificial variables
+        let mut basis: Vec<usize> = (real..real + m).collect();
+        let mut iterations = 0;
+        let phase_one_cost: Vec<f64> = (0..real + m).map(|k| if k < real { 0.0 } else { 1.0 }).collect();
+        let phase = simplex(&a_std, &b_std, &phase_one_cost, &mut basis, real + m, max_iter, &mut iterations);
+        let mut status = match (phase, basis_factorization(&a_std, &basis)) {
+            (Phase::MaxIterations, _) => LPStatus::MaxIterations,
+            (Phase::Singular, _) | (_, None) => LPStatus::Singular,
+            (_, Some(factorization)) => {
+                let values = factorization.solve(&b_std);
+                let infeasibility: f64 = basis.iter().zip(values.iter()).filter(|(k, _)| **k >= real).map(|(_, v)| v).sum();
+                if infeasibility > TOLERANCE * (1.0 + b_std.iter().fold(0.0f64, |m, v| m.max(*v))) {
+                    LPStatus::Infeasible
+                } else {
+                    LPStatus::Optimal
+                }
+            }
+        };
+
+        // Phase two: minimize the objective without letting artificials re-enter
+        let mut multipliers = Vector::zeros(m);
+        if status == LPStatus::Optimal {
+            status = if !drive_out_artificials(&a_std, &mut basis, real) {
+                LPStatus::Singular
+            } else {
+                match simplex(&a_std, &b_std, &c_std, &mut basis, real, max_iter, &mut iterations) {
+                    Phase::Optimal(y) => {
+                        multipliers = y;
+                        LPStatus::Optimal
+                    }
+                    Phase::Unbounded => LPStatus::Unbounded,
+                    Phase::MaxIterations => LPStatus::MaxIterations,
+                    Phase::Singular => LPStatus::Singular,
+                }
+            };
+        }
+
+        // Map the basic solution back to x; a singular basis leaves z at zero
+        let mut z = vec![0.0; real + m];
+        if let Some(factorization) = basis_factorization(&a_std, &basis) {
+            for (&k, v) in basis.iter().zip(factorization.solve(&b_std).iter()) {
+                z[k] = *v;
+            }
+        }
+        let x = Vector::new(
+            substitutions
+                .iter()
+                .map(|substitution| match *substitution {
+                    Substitution::Shifted(k, lower) => lower + z[k],
+                    Substitution::Reflected(k, upper) => upper - z[k],
+                    Substitution::Split(positive, negative) => z[positive] - z[negative],
+                })
+                .collect(),
+        );
+
+        // ν and λ are the negated simplex multipliers of their rows; μ closes stationarity
+        let duals: Vec<f64> = (0..m).map(|i| -signs[i] * multipliers[i]).collect();
+        let equality_duals = Vector::new(duals[..m_eq].to_vec());
+        let inequality_duals = Vector::new(duals[m_eq..m_eq + m_in].to_vec());
+        let mut bound_duals = self.c.scale(-1.0);
+        bound_duals.add_assign(&self.a.transpose().gemv(&equality_duals), -1.0);
+        bound_duals.add_assign(&self.g.transpose().gemv(&inequality_duals), -1.0);
+        if status != LPStatus::Optimal {
+            bound_duals = Vector::zeros(n);
+        }
+
+        LPSolution { objective: self.objective(&x), x, equality_duals, inequality_duals, bound_duals, status, iterations }
+    }
+}
+
+/// LU factors of the basis matrix `B` made of the `basis` columns of `a`,
+/// or `None` if `B` is singular.
+fn basis_factorization(a: &Matrix, basis: &[usize]) -> Option<LuFactorization> {
+    let m = basis.len();
+    let mut b = Matrix::zeros(m, m);
+    for i in 0..m {
+        for (k, &column) in basis.iter().enumerate() {
+            b[(i, k)] = a[(i, column)];
+        }
+    }
+    b.lu_factorization().ok()
+}
+
+/// Revised simplex on `min cᵀz, Az = b, z ≥ 0` from a feasible `basis`,
+/// letting only columns below `allowed` enter. Uses Bland's rule.
+fn simplex(a: &Matrix, b: &Vector, c: &[f64], basis: &mut [usize], allowed: usize, max_iter: usize, iterations: &mut usize) -> Phase {
+    loop {
+        let Some(factorization) = basis_factorization(a, basis) else {
+            return Phase::Singular;
+        };
+        let values = factorization.solve(b);
+        let y = factorization.solve_transpose(&Vector::new(basis.iter().map(|&k| c[k]).collect()));
+
+        // Entering variable: lowest index with a negative reduced cost
+        let entering = (0..allowed).find(|&k| !basis.contains(&k) && c[k] - (0..a.row_count()).map(|i| y[i] * a[(i, k)]).sum::<f64>() < -TOLERANCE);
+        let Some(entering) = entering else {
+            return Phase::Optimal(y);
+        };
+        if *iterations >= max_iter {
+            return Phase::MaxIterations;
+        }
+        *iterations += 1;
+
+        // Leaving variable: minimum ratio, lowest variable index on ties
+        let direction = factorization.solve(&a.get_column(entering));
+        let mut leaving: Option<(usize, f64)> = None;
+        for (i, &d) in direction.iter().enumerate() {
+            if d > TOLERANCE {
+                let ratio = values[i].max(0.0) / d;
+                leaving = match leaving {
+                    Some((r, best)) if ratio > best + TOLERANCE || (ratio > best - TOLERANCE && basis[r] < basis[i]) => Some((r, best)),
+                    _ => Some((i, ratio)),
+                };
+            }
+        }
+        match leaving {
+            Some((row, _)) => basis[row] = entering,
+            None => return Phase::Unbounded,
+        }
+    }
+}
+
+/// After phase one, replaces artificial variables that are still basic (at
+/// zero) by real columns. Artificials whose row of `B⁻¹A` vanishes on every
+/// real column belong to redundant constraints and stay in the basis.
+/// Returns false if the basis is singular.
+fn drive_out_artificials(a: &Matrix, basis: &mut [usize], real: usize) -> bool {
+    let Some(mut factorization) = basis_factorization(a, basis) else {
+        return false;
+    };
+    for position in 0..basis.len() {
+        if basis[position] < real {
+            continue;
+        }
+        let mut unit = Vector::zeros(basis.len());
+        unit[position] = 1.0;
+        let row = factorization.solve_transpose(&unit);
+        let replacement = (0..real)
+            .find(|&k| !basis.contains(&k) && (0..a.row_count()).map(|i| row[i] * a[(i, k)]).sum::<f64>().abs() > TOLERANCE);
+        if let Some(k) = replacement {
+            basis[position] = k;
+            let Some(updated) = basis_factorization(a, basis) else {
+                return false;
+            };
+            factorization = updated;
+        }
+    }
+    true
+}
@@ -0,0 +1,457 @@
+use crate::math::Vector;
+use std::collections::VecDeque;
+
+/// Step length rule used by gradient descent.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LineSearch {
+    /// Backtracking until the sufficient decrease (Armijo) condition holds.
+    Armijo,
+    /// Bracketing and zoom until the strong Wolfe conditions hold.
+    Wolfe,
+}
+
+/// Minimization algorithm.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Method {
+    /// Steepest descent with the given line search.
+    GradientDescent { line_search: LineSearch },
+    /// Nonlinear conjugate gradient (Polak–Ribière+, restarted every n steps).
+    ConjugateGradient,
+    /// Quasi-Newton with a dense inverse Hessian approximation.
+    BFGS,
+    /// Limited-memory BFGS keeping the last `memory` updates.
+    LBFGS { memory: usize },
+    /// Derivative-free simplex search; the gradient is only used for the report.
+    NelderMead,
+    /// Gradient descent projected onto the box `lower <= x <= upper`, with
+    /// Barzilai–Borwein steps and a backtracking line search.
+    ProjectedGradient { lower: Vector, upper: Vector },
+}
+
+/// Stopping criteria shared by every method.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Options {
+    pub max_iter: usize, // Maximum number of iterations
+    pub tol: f64,        // Gradient tolerance (simplex size and spread for Nelder–Mead)
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self { max_iter: 1000, tol: 1e-6 }
+    }
+}
+
+/// Why the optimizer stopped.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Status {
+    /// The tolerance was reached.
+    Converged,
+    /// `max_iter` iterations were performed without reaching the tolerance.
+    MaxIterations,
+    /// No acceptable step was found along the search direction.
+    LineSearchFailed,
+}
+
+/// Outcome of `minimize`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OptimizeResult {
+    pub x: Vector,          // Best point found
+    pub value: f64,         // f(x)
+    pub gradient_norm: f64, // Largest absolute component of the (projected) gradient at x
+    pub iterations: usize,  // Iterations performed
+    pub status: Status,
+}
+
+impl OptimizeResult {
+    pub fn converged(&self) -> bool {
+        self.status == Status::Converged
+    }
+}
+
+/// Minimizes `f` starting from `x0` with default options.
+///
+/// ```ignore
+/// let rosenbrock = |x: &Vector| (1.0 - x[0]).powi(2) + 100.0 * (x[1] - x[0] * x[0]).powi(2);
+/// let gradient = |x: &Vector| Vector::new(vec![
+///     -2.0 * (1.0 - x[0]) - 400.0 * x[0] * (x[1] - x[0] * x[0]),
+///     200.0 * (x[1] - x[0] * x[0]),
+/// ]);
+/// let result = minimize(rosenbrock, gradient, &Vector::new(vec![-1.2, 1.0]), Method::BFGS);
+/// ```
+pub fn minimize<F, G>(f: F, grad: G, x0: &Vector, method: Method) -> OptimizeResult
+where
+    F: Fn(&Vector) -> f64,
+    G: Fn(&Vector) -> Vector,
+{
+    minimize_with_options(f, grad, x0, method, Options::default())
+}
+
+/// Minimizes `f` starting from `x0` with explicit stopping criteria.
+pub fn minimize_with_options<F, G>(f: F, grad: G, x0: &Vector, method: Method, options: Options) -> OptimizeResult
+where
+    F: Fn(&Vector) -> f64,
+    G: Fn(&Vector) -> Vector,
+{
+    assert!(options.tol > 0.0, "Tolerance must be positive");
+    match method {
+        Method::GradientDescent { line_search } => {
+            descent(&f, &grad, x0, options, line_search, 0.9, |_, g, _| g.scale(-1.0))
+        }
+        Method::ConjugateGradient => conjugate_gradient(&f, &grad, x0, options),
+        Method::BFGS => bfgs(&f, &grad, x0, options),
+        Method::LBFGS { memory } => {
+            assert!(memory > 0, "L-BFGS needs a memory of at least one update");
+            lbfgs(&f, &grad, x0, options, memory)
+        }
+        Method::NelderMead => nelder_mead(&f, &grad, x0, options),
+        Method::ProjectedGradient { lower, upper } => projected_gradient(&f, &grad, x0, options, &lower, &upper),
+    }
+}
+
+fn max_norm(v: &Vector) -> f64 {
+    v.iter().fold(0.0, |m, x| m.max(x.abs()))
+}
+
+fn result(x: Vector, value: f64, gradient: &Vector, iterations: usize, status: Status) -> OptimizeResult {
+    OptimizeResult { x, value, gradient_norm: max_norm(gradient), iterations, status }
+}
+
+/// Point, value and gradient accepted by a line search.
+struct Step {
+    x: Vector,
+    value: f64,
+    gradient: Vector,
+}
+
+/// Backtracking from a unit step until `f(x + t d) <= f(x) + c₁ t gᵀd`.
+fn armijo<F, G>(f: &F, grad: &G, x: &Vector, value: f64, gradient: &Vector, direction: &Vector) -> Option<Step>
+where
+    F: Fn(&Vector) -> f64,
+    G: Fn(&Vector) -> Vector,
+{
+    const C1: f64 = 1e-4;
+    let slope = gradient.dot(direction);
+    let mut t = 1.0;
+    for _ in 0..60 {
+        let candidate = x.add(&direction.scale(t));
+        let candidate_value = f(&candidate);
+        if candidate_value <= value + C1 * t * slope {
+            let gradient = grad(&candidate);
+            return Some(Step { x: candidate, value: candidate_value, gradient });
+        }
+        t *= 0.5;
+    }
+    None
+}
+
+/// Strong Wolfe line search (Nocedal & Wright, algorithms 3.5 and 3.6) with
+/// bisection in the zoom phase.
+fn wolfe<F, G>(f: &F, grad: &G, x: &Vector, value: f64, gradient: &Vector, direction: &Vector, c2: f64) -> Option<Step>
+where
+    F: Fn(&Vector) -> f64,
+    G: Fn(&Vector) -> Vector,
+{
+    const C1: f64 = 1e-4;
+    let slope = gradient.dot(direction);
+    let evaluate = |t: f64| {
+        let point = x.add(&direction.scale(t));
+        let value = f(&point);
+        (point, value)
+    };
+    let sufficient = |t: f64, v: f64| v <= value + C1 * t * slope;
+    let curvature = |g: &Vector| g.dot(direction).abs() <= -c2 * slope;
+
+    // Zoom between a low step satisfying sufficient decrease and a high step
+    let zoom = |mut lo: f64, mut lo_value: f64, mut hi: f64| -> Option<Step> {
+        for _ in 0..50 {
+            let t = 0.5 * (lo + hi);
+            let (point, point_value) = evaluate(t);
+            if !sufficient(t, point_value) || point_value >= lo_value {
+                hi = t;
+            } else {
+                let point_gradient = grad(&point);
+                if curvature(&point_gradient) {
+                    return Some(Step { x: point, value: point_value, gradient: point_gradient });
+                }
+                if point_gradient.dot(direction) * (hi - lo) >= 0.0 {
+                    hi = lo;
+                }
+                lo = t;
+                lo_value = point_value;
+            }
+        }
+        None
+    };
+
+    let (mut previous, mut previous_value) = (0.0, value);
+    let mut t = 1.0;
+    for i in 0..50 {
+        let (point, point_value) = evaluate(t);
+        if !sufficient(t, point_value) || (i > 0 && point_value >= previous_value) {
+            return zoom(previous, previous_value, t);
+        }
+        let point_gradient = grad(&point);
+        if curvature(&point_gradient) {
+            return Some(Step { x: point, value: point_value, gradient: point_gradient });
+        }
+        if point_gradient.dot(direction) >= 0.0 {
+            return zoom(t, point_value, previous);
+        }
+        previous = t;
+        previous_value = point_value;
+        t *= 2.0;
+    }
+    None
+}
+
+/// Generic descent loop: `next_direction(x, g, previous_step)` gives the
+/// search direction, which falls back to steepest descent if it is not a
+/// descent direction.
+fn descent<F, G, D>(
+    f: &F,
+    grad: &G,
+    x0: &Vector,
+    options: Options,
+    line_search: LineSearch,
+    c2: f64,
+    mut next_direction: D,
+) -> OptimizeResult
+where
+    F: Fn(&Vector) -> f64,
+    G: Fn(&Vector) -> Vector,
+    D: FnMut(&Vector, &Vector, Option<(&Vector, &Vector)>) -> Vector,
+{
+    let mut x = x0.clone();
+    let mut value = f(&x);
+    let mut gradient = grad(&x);
+    let mut previous: Option<(Vector, Vector)> = None; // (s, y) of the last step
+    for iteration in 0..options.max_iter {
+        if max_norm(&gradient) < options.tol {
+            return result(x, value, &gradient, iteration, Status::Converged);
+        }
+        let mut direction = next_direction(&x, &gradient, previous.as_ref().map(|(s, y)| (s, y)));
+        if direction.dot(&gradient) >= 0.0 {
+            direction = gradient.scale(-1.0);
+        }
+        let step = match line_search {
+            LineSearch::Armijo => armijo(f, grad, &x, value, &gradient, &direction),
+            LineSearch::Wolfe => wolfe(f, grad, &x, value, &gradient, &direction, c2),
+        };
+        let Some(step) = step else {
+            return result(x, value, &gradient, iteration, Status::LineSearchFailed);
+        };
+        let s = step.x.add(&x.scale(-1.0));
+        let y = step.gradient.add(&gradient.scale(-1.0));
+        previous = Some((s, y));
+        (x, value, gradient) = (step.x, step.value, step.gradient);
+    }
+    let status = if max_norm(&gradient) < options.tol { Status::Converged } else { Status::MaxIterations };
+    result(x, value, &gradient, options.max_iter, status)
+}
+
+fn conjugate_gradient<F, G>(f: &F, grad: &G, x0: &Vector, options: Options) -> OptimizeResult
+where
+    F: Fn(&Vector) -> f64,
+    G: Fn(&Vector) -> Vector,
+{
+    let n = x0.len();
+    let mut direction: Option<Vector> = None;
+    let mut steps: usize = 0;
+    descent(f, grad, x0, options, LineSearch::Wolfe, 0.1, |_, g, previous| {
+        steps += 1;
+        let next = match (&direction, previous) {
+            (Some(d), Some((_, y))) if !steps.is_multiple_of(n) => {
+                // Polak–Ribière+: β = max(0, gᵀy / ||g_prev||²), with g_prev = g - y
+                let g_previous = g.add(&y.scale(-1.0));
+                let beta = (g.dot(y) / g_previous.dot(&g_previous)).max(0.0);
+                g.scale(-1.0).add(&d.scale(beta))
+            }
+            _ => g.scale(-1.0),
+        };
+        direction = Some(next.clone());
+        next
+    })
+}
+
+fn bfgs<F, G>(f: &F, grad: &G, x0: &Vector, options: Options) -> OptimizeResult
+where
+    F: Fn(&Vector) -> f64,
+    G: Fn(&Vector) -> Vector,
+{
+    let n = x0.len();
+    let mut inverse_hessian: Option<Vec<Vec<f64>>> = None;
+    descent(f, grad, x0, options, LineSearch::Wolfe, 0.9, |_, g, previous| {
+        if let Some((s, y)) = previous {
+            let sy = s.dot(y);
+            if sy > 1e-12 {
+                // Scale the identity by sᵀy / yᵀy before the first update
+                let h = inverse_hessian.get_or_insert_with(|| {
+                    let gamma = sy / y.dot(y);
+                    (0..n).map(|i| (0..n).map(|j| if i == j { gamma } else { 0.0 }).collect()).collect()
+                });
+                // H ← (I - ρ s yᵀ) H (I - ρ y sᵀ) + ρ s sᵀ
+                let rho = 1.0 / sy;
+                let hy: Vec<f64> = (0..n).map(|i| (0..n).map(|j| h[i][j] * y[j]).sum()).collect();
+                let yhy: f64 = (0..n).map(|i| y[i] * hy[i]).sum();
+                for i in 0..n {
+                    for j in 0..n {
+                        h[i][j] += -rho * (hy[i] * s[j] + s[i] * hy[j]) + (rho * rho * yhy + rho) * s[i] * s[j];
+                    }
+                }
+            }
+        }
+        match &inverse_hessian {
+            Some(h) => Vector::new((0..n).map(|i| -(0..n).map(|j| h[i][j] * g[j]).sum::<f64>()).collect()),
+            None => g.scale(-1.0),
+        }
+    })
+}
+
+fn lbfgs<F, G>(f: &F, grad: &G, x0: &Vector, options: Options, memory: usize) -> OptimizeResult
+where
+    F: Fn(&Vector) -> f64,
+    G: Fn(&Vector) -> Vector,
+{
+    let mut history: VecDeque<(Vector, Vector, f64)> = VecDeque::with_capacity(memory); // (s, y, 1 / sᵀy)
+    descent(f, grad, x0, options, LineSearch::Wolfe, 0.9, |_, g, previous| {
+        if let Some((s, y)) = previous {
+            let sy = s.dot(y);
+            if sy > 1e-12 {
+                if history.len() == memory {
+                    history.pop_front();
+                }
+                history.push_back((s.clone(), y.clone(), 1.0 / sy));
+            }
+        }
+        // Two-loop recursion: direction = -H g
+        let mut q = g.clone();
+        let mut alphas = Vec::with_capacity(history.len());
+        for (s, y, rho) in history.iter().rev() {
+            let alpha = rho * s.dot(&q);
+            q.add_assign(y, -alpha);
+            alphas.push(alpha);
+        }
+        if let Some((s, y, _)) = history.back() {
+            q = q.scale(s.dot(y) / y.dot(y));
+        }
+        for ((s, y, rho), alpha) in history.iter().zip(alphas.iter().rev()) {
+            let beta = rho * y.dot(&q);
+            q.add_assign(s, alpha - beta);
+        }
+        q.scale(-1.0)
+    })
+}
+
+fn nelder_mead<F, G>(f: &F, grad: &G, x0: &Vector, options: Options) -> OptimizeResult
+where
+    F: Fn(&Vector) -> f64,
+    G: Fn(&Vector) -> Vector,
+{
+    let n = x0.len();
+    // Initial simplex: x0 plus a 5% perturbation of each coordinate
+    let mut simplex: Vec<(Vector, f64)> = vec![(x0.clone(), f(x0))];
+    for i in 0..n {
+        let mut vertex = x0.clone();
+        vertex[i] = if vertex[i] != 0.0 { 1.05 * vertex[i] } else { 0.00025 };
+        let value = f(&vertex);
+        simplex.push((vertex, value));
+    }
+
+    let mut iterations = 0;
+    let mut status = Status::MaxIterations;
+    while iterations < options.max_iter {
+        simplex.sort_by(|a, b| a.1.total_cmp(&b.1));
+        let (best, best_value) = &simplex[0];
+        let spread = simplex.iter().map(|(_, v)| (v - best_value).abs()).fold(0.0, f64::max);
+        let size = simplex.iter().map(|(x, _)| max_norm(&x.add(&best.scale(-1.0)))).fold(0.0, f64::max);
+        if spread <= options.tol && size <= options.tol {
+            status = Status::Converged;
+            break;
+        }
+        iterations += 1;
+
+        let mut centroid = Vector::zeros(n);
+        for (x, _) in &simplex[..n] {
+            centroid.add_assign(x, 1.0 / n as f64);
+        }
+        let worst_value = simplex[n].1;
+        let along = |t: f64| centroid.add(&simplex[n].0.add(&centroid.scale(-1.0)).scale(t));
+
+        let reflected = along(-1.0);
+        let reflected_value = f(&reflected);
+        if reflected_value < simplex[0].1 {
+            let expanded = along(-2.0);
+            let expanded_value = f(&expanded);
+            simplex[n] = if expanded_value < reflected_value { (expanded, expanded_value) } else { (reflected, reflected_value) };
+        } else if reflected_value < simplex[n - 1].1 {
+            simplex[n] = (reflected, reflected_value);
+        } else {
+            // Contract outside if the reflection improved on the worst vertex, inside otherwise
+            let outside = reflected_value < worst_value;
+            let contracted = along(if outside { -0.5 } else { 0.5 });
+            let contracted_value = f(&contracted);
+            if contracted_value < reflected_value.min(worst_value) {
+                simplex[n] = (contracted, contracted_value);
+            } else {
+                // Shrink towards the best vertex
+                let best = simplex[0].0.clone();
+                for vertex in simplex.iter_mut().skip(1) {
+                    let x = best.add(&vertex.0.add(&best.scale(-1.0)).scale(0.5));
+                    let value = f(&x);
+                    *vertex = (x, value);
+                }
+            }
+        }
+    }
+    simplex.sort_by(|a, b| a.1.total_cmp(&b.1));
+    let (x, value) = simplex.swap_remove(0);
+    let gradient = grad(&x);
+    result(x, value, &gradient, iterations, status)
+}
+
+fn projected_gradient<F, G>(f: &F, grad: &G, x0: &Vector, options: Options, lower: &Vector, upper: &Vector) -> OptimizeResult
+where
+    F: Fn(&Vector) -> f64,
+    G: Fn(&Vector) -> Vector,
+{
+    assert!(lower.len() == x0.len() && upper.len() == x0.len(), "Bounds must have the same length as x0");
+    assert!(lower.iter().zip(upper.iter()).all(|(l, u)| l <= u), "Lower bounds must not exceed upper bounds");
+    let project = |x: &Vector| Vector::new(x.iter().zip(lower.iter().zip(upper.iter())).map(|(v, (l, u))| v.clamp(*l, *u)).collect());
+    // Projected gradient: P(x - g) - x, zero at a stationary point of the box-constrained problem
+    let projected_gradient = |x: &Vector, g: &Vector| x.add(&project(&x.add(&g.scale(-1.0))).scale(-1.0));
+
+    let mut x = project(x0);
+    let mut value = f(&x);
+    let mut gradient = grad(&x);
+    let mut step = 1.0;
+    for iteration in 0..options.max_iter {
+        let pg = projected_gradient(&x, &gradient);
+        if max_norm(&pg) < options.tol {
+            return result(x, value, &pg, iteration, Status::Converged);
+        }
+        // Backtrack along the projection arc until sufficient decrease
+        let mut accepted = None;
+        for _ in 0..60 {
+            let candidate = project(&x.add(&gradient.scale(-step)));
+            let candidate_value = f(&candidate);
+            let change = candidate.add(&x.scale(-1.0));
+            if candidate_value <= value + 1e-4 * gradient.dot(&change) {
+                accepted = Some((candidate, candidate_value, change));
+                break;
+            }
+            step *= 0.5;
+        }
+        let Some((candidate, candidate_value, s)) = accepted else {
+            return result(x, value, &pg, iteration, Status::LineSearchFailed);
+        };
+        let candidate_gradient = grad(&candidate);
+        // Barzilai–Borwein step for the next iteration
+        let y = candidate_gradient.add(&gradient.scale(-1.0));
+        let sy = s.dot(&y);
+        step = if sy > 1e-12 { s.dot(&s) / sy } else { 1.0 };
+        (x, value, gradient) = (candidate, candidate_value, candidate_gradient);
+    }
+    let pg = projected_gradient(&x, &gradient);
+    let status = if max_norm(&pg) < options.tol { Status::Converged } else { Status::MaxIterations };
+    result(x, value, &pg, options.max_iter, status)
+}
@@ -0,0 +1,7 @@
+pub mod linprog;
+pub mod minimize;
+pub mod qp;
+
+pub use linprog::{LPSolution, LPStatus, LinearProgram};
+pub use minimize::{minimize, minimize_with_options, LineSearch, Method, OptimizeResult, Options, Status};
+pub use qp::{QPReport, QPSettings, QPSolution, QPStatus, QuadraticProgram};
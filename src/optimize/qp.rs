@@ -0,0 +1,430 @@
+use crate::math::{Matrix, Vector};
+
+/// Dense convex quadratic program
+///
+/// ```text
+/// minimize    ½ xᵀQx + pᵀx
+/// subject to  Ax = b,  Gx ≤ h,  lower ≤ x ≤ upper
+/// ```
+///
+/// with `Q` symmetric positive semidefinite. Use infinite bounds for free
+/// variables. Solved with ADMM in the style of OSQP.
+#[derive(Debug, Clone)]
+pub struct QuadraticProgram {
+    pub q: Matrix,     // Quadratic term, n x n
+    pub p: Vector,     // Linear term
+    pub a: Matrix,     // Equality constraint matrix, m x n
+    pub b: Vector,     // Equality constraint target
+    pub g: Matrix,     // Inequality constraint matrix, k x n
+    pub h: Vector,     // Inequality constraint bound
+    pub lower: Vector, // Lower bounds for x
+    pub upper: Vector, // Upper bounds for x
+}
+
+/// ADMM parameters and stopping criteria.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QPSettings {
+    pub max_iter: usize,
+    pub eps_abs: f64,        // Absolute tolerance on the residuals
+    pub eps_rel: f64,        // Relative tolerance on the residuals
+    pub eps_infeasible: f64, // Tolerance of the infeasibility certificates
+    pub rho: f64,            // Initial ADMM step size (adapted during the solve)
+    pub sigma: f64,          // Regularization of the x update
+    pub alpha: f64,          // Over-relaxation, in (0, 2)
+    pub polish: bool,        // Refine the solution by solving the KKT system of the active constraints
+}
+
+impl Default for QPSettings {
+    fn default() -> Self {
+        Self {
+            max_iter: 10_000,
+            eps_abs: 1e-6,
+            eps_rel: 1e-6,
+            eps_infeasible: 1e-6,
+            rho: 0.1,
+            sigma: 1e-6,
+            alpha: 1.6,
+            polish: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum QPStatus {
+    /// Residuals are within the tolerances.
+    Solved,
+    /// `max_iter` iterations were performed without reaching the tolerances.
+    MaxIterations,
+    /// The constraints cannot be satisfied simultaneously.
+    PrimalInfeasible,
+    /// The objective is unbounded below on the feasible set.
+    DualInfeasible,
+    /// `Q` is not positive semidefinite, so the ADMM system could not be factorized.
+    NonConvex,
+}
+
+/// Feasibility and optimality measures of a primal-dual pair, all in max-norm.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QPReport {
+    pub equality_violation: f64,   // ||Ax - b||
+    pub inequality_violation: f64, // max(Gx - h, 0)
+    pub bound_violation: f64,      // Distance of x to the box
+    pub stationarity: f64,         // ||Qx + p + Aᵀν + Gᵀλ + μ||
+    pub dual_violation: f64,       // Negative inequality multipliers, bound multipliers of the wrong sign
+    pub complementarity: f64,      // max |λᵢ (Gx - h)ᵢ| and the same for the bounds
+}
+
+impl QPReport {
+    pub fn primal_residual(&self) -> f64 {
+        self.equality_violation.max(self.inequality_violation).max(self.bound_violation)
+    }
+
+    /// Whether every measure is below `tol`.
+    pub fn is_optimal(&self, tol: f64) -> bool {
+        self.primal_residual() <= tol && self.stationarity <= tol && self.dual_violation <= tol && self.complementarity <= tol
+    }
+}
+
+/// Primal-dual solution of a `QuadraticProgram`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QPSolution {
+    pub x: Vector,
+    pub equality_duals: Vector,   // ν, one per row of A
+    pub inequality_duals: Vector, // λ ≥ 0, one per row of G
+    pub bound_duals: Vector,      // μ, positive at an active upper bound, negative at a lower bound
+    pub objective: f64,
+    pub status: QPStatus,
+    pub iterations: usize,
+    pub polished: bool, // Whether the polished solution was accepted
+    pub report: QPReport,
+}
+
+impl QuadraticProgram {
+    /// Unconstrained program `min ½ xᵀQx + pᵀx`.
+    pub fn new(q: Matrix, p: Vector) -> Self {
+        let n = p.len();
+        assert!(q.row_count() == n && q.col_count() == n, "Q must be n x n");
+        Self {
+            q,
+            p,
+            a: Matrix::zeros(0, n),
+            b: Vector::zeros(0),
+            g: Matrix::zeros(0, n),
+            h: Vector::zeros(0),
+            lower: Vector::new(vec![f64::NEG_INFINITY; n]),
+            upper: Vector::new(vec![f64::INFINITY; n]),
+        }
+    }
+
+    /// Adds the equality constraints `Ax = b`.
+    pub fn with_equality(mut self, a: Matrix, b: Vector) -> Self {
+        assert!(a.row_count() == b.len() && (a.row_count() == 0 || a.col_count() == self.size()), "A must be m x n and b of length m");
+        self.a = Matrix { rows: a.rows, cols: self.size() };
+        self.b = b;
+        self
+    }
+
+    /// Adds the inequality constraints `Gx ≤ h`.
+    pub fn with_inequality(mut self, g: Matrix, h: Vector) -> Self {
+        assert!(g.row_count() == h.len() && (g.row_count() == 0 || g.col_count() == self.size()), "G must be k x n and h of length k");
+        self.g = Matrix { rows: g.rows, cols: self.size() };
+        self.h = h;
+        self
+    }
+
+    /// Adds the box constraints `lower ≤ x ≤ upper`.
+    pub fn with_bounds(mut self, lower: Vector, upper: Vector) -> Self {
+        assert!(lower.len() == self.size() && upper.len() == self.size(), "Bounds must have length n");
+        assert!(lower.iter().zip(upper.iter()).all(|(l, u)| l <= u), "Lower bounds must not exceed upper bounds");
+        self.lower = lower;
+        self.upper = upper;
+        self
+    }
+
+    pub fn size(&self) -> usize {
+        self.p.len()
+    }
+
+    pub fn objective(&self, x: &Vector) -> f64 {
+        0.5 * x.dot(&self.q.gemv(x)) + self.p.dot(x)
+    }
+
+    /// Feasibility and optimality measures of `x` with the given multipliers.
+    pub fn report(&self, x: &Vector, equality_duals: &Vector, inequality_duals: &Vector, bound_duals: &Vector) -> QPReport {
+        let ax = self.a.gemv(x);
+        let gx = self.g.gemv(x);
+        let equality_violation = max_abs(ax.iter().zip(self.b.iter()).map(|(v, b)| v - b));
+        let inequality_violation = gx.iter().zip(self.h.iter()).fold(0.0f64, |m, (v, h)| m.max(v - h));
+        let bound_violation = (0..self.size()).fold(0.0f64, |m, i| m.max(self.lower[i] - x[i]).max(x[i] - self.upper[i]));
+
+        let mut gradient = self.q.gemv(x).add(&self.p).add(bound_duals);
+        gradient.add_assign(&self.a.transpose().gemv(equality_duals), 1.0);
+        gradient.add_assign(&self.g.transpose().gemv(inequality_duals), 1.0);
+
+        let mut dual_violation = inequality_duals.iter().fold(0.0f64, |m, l| m.max(-l));
+        let mut complementarity = max_abs(inequality_duals.iter().zip(gx.iter().zip(self.h.iter())).map(|(l, (v, h))| l * (v - h)));
+        for i in 0..self.size() {
+            let mu = bound_duals[i];
+            if mu > 0.0 {
+                // Pushing against the upper bound
+                dual_violation = dual_violation.max(if self.upper[i].is_finite() { 0.0 } else { mu });
+                complementarity = complementarity.max(if self.upper[i].is_finite() { (mu * (self.upper[i] - x[i])).abs() } else { 0.0 });
+            } else if mu < 0.0 {
+                dual_violation = dual_violation.max(if self.lower[i].is_finite() { 0.0 } else { -mu });
+                complementarity = complementarity.max(if self.lower[i].is_finite() { (mu * (x[i] - self.lower[i])).abs() } else { 0.0 });
+            }
+        }
+        QPReport {
+            equality_violation,
+            inequality_violation,
+            bound_violation,
+            stationarity: max_abs(gradient.iter().copied()),
+            dual_violation,
+            complementarity,
+        }
+    }
+
+    /// Solves the program with OSQP-style ADMM on the stacked constraints
+    /// `l ≤ Cx ≤ u`, `C = [A; G; I]`, followed by an optional polishing step.
+    pub fn solve(&self, settings: &QPSettings) -> QPSolution {
+        let n = self.size();
+        let (m_eq, m_in) = (self.a.row_count(), self.g.row_count());
+        let c = Matrix::from_vector(
+            self.a.rows.iter().chain(&self.g.rows).cloned().chain(Matrix::identity(n).rows).collect(),
+        );
+        let c = Matrix { rows: c.rows, cols: n };
+        let ct = c.transpose();
+        let m = c.row_count();
+        let lower: Vec<f64> = self.b.iter().copied().chain(vec![f64::NEG_INFINITY; m_in]).chain(self.lower.iter().copied()).collect();
+        let upper: Vec<f64> = self.b.iter().copied().chain(self.h.iter().copied()).chain(self.upper.iter().copied()).collect();
+        let project = |z: &Vector| Vector::new((0..m).map(|i| z[i].clamp(lower[i], upper[i])).collect());
+
+        // Step size per constraint: larger for equalities, tiny for free rows
+        let rho_vector = |rho: f64| {
+            Vector::new(
+                (0..m)
+                    .map(|i| match (lower[i].is_finite(), upper[i].is_finite()) {
+                        _ if lower[i] == upper[i] => 1e3 * rho,
+                        (false, false) => 1e-6,
+                        _ => rho,
+                    })
+                    .collect(),
+            )
+        };
+        let factorize = |rho: &Vector| -> Matrix {
+            // Q + σI + Cᵀ diag(ρ) C
+            let mut kkt = self.q.clone();
+            for i in 0..n {
+                kkt[(i, i)] += settings.sigma;
+            }
+            for (k, row) in c.rows.iter().enumerate() {
+                for i in 0..n {
+                    if row[i] == 0.0 {
+                        continue;
+                    }
+                    for j in 0..n {
+                        kkt[(i, j)] += rho[k] * row[i] * row[j];
+                    }
+                }
+            }
+            kkt
+        };
+
+        let mut rho = settings.rho;
+        let mut rho_k = rho_vector(rho);
+        let mut kkt = factorize(&rho_k);
+        let mut x = Vector::zeros(n);
+        let mut z = project(&Vector::zeros(m));
+        let mut y = Vector::zeros(m);
+        let mut status = QPStatus::MaxIterations;
+        let mut iterations = 0;
+
+        while iterations < settings.max_iter {
+            iterations += 1;
+            // x̃ solves (Q + σI + CᵀρC) x̃ = σx - p + Cᵀ(ρz - y)
+            let rhs_dual = Vector::new((0..m).map(|i| rho_k[i] * z[i] - y[i]).collect());
+            let mut rhs = x.scale(settings.sigma).add(&self.p.scale(-1.0));
+            rhs.add_assign(&ct.gemv(&rhs_dual), 1.0);
+            let Ok(x_tilde) = kkt.cholesky_solve(&rhs) else {
+                status = QPStatus::NonConvex;
+                break;
+            };
+            let z_tilde = c.gemv(&x_tilde);
+
+            let x_next = x_tilde.scale(settings.alpha).add(&x.scale(1.0 - settings.alpha));
+            let z_relaxed = z_tilde.scale(settings.alpha).add(&z.scale(1.0 - settings.alpha));
+            let z_next = project(&Vector::new((0..m).map(|i| z_relaxed[i] + y[i] / rho_k[i]).collect()));
+            let y_next = Vector::new((0..m).map(|i| y[i] + rho_k[i] * (z_relaxed[i] - z_next[i])).collect());
+
+            let delta_x = x_next.add(&x.scale(-1.0));
+            let delta_y = y_next.add(&y.scale(-1.0));
+            (x, z, y) = (x_next, z_next, y_next);
+
+            // Convergence
+            let cx = c.gemv(&x);
+            let qx = self.q.gemv(&x);
+            let cty = ct.gemv(&y);
+            let primal = max_abs(cx.iter().zip(z.iter()).map(|(a, b)| a - b));
+            let dual = max_abs(qx.iter().zip(self.p.iter()).zip(cty.iter()).map(|((a, b), c)| a + b + c));
+            let primal_tol = settings.eps_abs + settings.eps_rel * norm(&cx).max(norm(&z));
+            let dual_tol = settings.eps_abs + settings.eps_rel * norm(&qx).max(norm(&cty)).max(norm(&self.p));
+            if primal <= primal_tol && dual <= dual_tol {
+                status = QPStatus::Solved;
+                break;
+            }
+            if self.primal_infeasible(&ct, &delta_y, &lower, &upper, settings.eps_infeasible) {
+                status = QPStatus::PrimalInfeasible;
+                break;
+            }
+            if self.dual_infeasible(&c, &delta_x, &lower, &upper, settings.eps_infeasible) {
+                status = QPStatus::DualInfeasible;
+                break;
+            }
+
+            // Rebalance ρ so the primal and dual residuals shrink at a similar rate
+            if iterations % 25 == 0 {
+                let scaled_primal = primal / norm(&cx).max(norm(&z)).max(1e-10);
+                let scaled_dual = dual / norm(&qx).max(norm(&cty)).max(norm(&self.p)).max(1e-10);
+                let new_rho = (rho * (scaled_primal / scaled_dual.max(1e-10)).sqrt()).clamp(1e-6, 1e6);
+                if new_rho > 5.0 * rho || new_rho < rho / 5.0 {
+                    rho = new_rho;
+                    rho_k = rho_vector(rho);
+                    kkt = factorize(&rho_k);
+                }
+            }
+        }
+
+        let split = |y: &Vector| {
+            (
+                Vector::new(y.data[..m_eq].to_vec()),
+                Vector::new(y.data[m_eq..m_eq + m_in].to_vec()),
+                Vector::new(y.data[m_eq + m_in..].to_vec()),
+            )
+        };
+        let mut polished = false;
+        if status == QPStatus::Solved
+            && settings.polish
+            && let Some((x_polished, y_polished)) = self.polish(&c, &y, &lower, &upper)
+        {
+            let (nu, lambda, mu) = split(&y_polished);
+            let polished_report = self.report(&x_polished, &nu, &lambda, &mu);
+            let (nu, lambda, mu) = split(&y);
+            let admm_report = self.report(&x, &nu, &lambda, &mu);
+            let worst = |r: &QPReport| r.primal_residual().max(r.stationarity).max(r.dual_violation).max(r.complementarity);
+            if worst(&polished_report) <= worst(&admm_report) {
+                (x, y, polished) = (x_polished, y_polished, true);
+            }
+        }
+
+        let (equality_duals, inequality_duals, bound_duals) = split(&y);
+        let report = self.report(&x, &equality_duals, &inequality_duals, &bound_duals);
+        QPSolution { objective: self.objective(&x), x, equality_duals, inequality_duals, bound_duals, status, iterations, polished, report }
+    }
+
+    /// A certificate of primal infeasibility: `Cᵀδy ≈ 0` with
+    /// `uᵀ max(δy, 0) + lᵀ min(δy, 0) < 0`.
+    fn primal_infeasible(&self, ct: &Matrix, delta_y: &Vector, lower: &[f64], upper: &[f64], eps: f64) -> bool {
+        let scale = norm(delta_y);
+        if scale < 1e-12 || norm(&ct.gemv(delta_y)) > eps * scale {
+            return false;
+        }
+        let mut support = 0.0;
+        for (i, &dy) in delta_y.iter().enumerate() {
+            if dy.abs() <= eps * scale {
+                continue;
+            }
+            let bound = if dy > 0.0 { upper[i] } else { lower[i] };
+            if !bound.is_finite() {
+                return false;
+            }
+            support += bound * dy;
+        }
+        support < -eps * scale
+    }
+
+    /// A certificate of dual infeasibility: a direction `δx` with `Qδx ≈ 0`,
+    /// `pᵀδx < 0` along which every constraint stays satisfied.
+    fn dual_infeasible(&self, c: &Matrix, delta_x: &Vector, lower: &[f64], upper: &[f64], eps: f64) -> bool {
+        let scale = norm(delta_x);
+        if scale < 1e-12 || norm(&self.q.gemv(delta_x)) > eps * scale || self.p.dot(delta_x) > -eps * scale {
+            return false;
+        }
+        c.gemv(delta_x).iter().enumerate().all(|(i, &v)| {
+            (!upper[i].is_finite() || v <= eps * scale) && (!lower[i].is_finite() || v >= -eps * scale)
+        })
+    }
+
+    /// Solves the KKT system of the constraints the ADMM solution identifies
+    /// as active, which removes the ADMM tolerance from the solution.
+    fn polish(&self, c: &Matrix, y: &Vector, lower: &[f64], upper: &[f64]) -> Option<(Vector, Vector)> {
+        let n = self.size();
+        // Active rows and the bound they are held at; the ADMM multipliers of
+        // inactive constraints are exactly zero
+        let active: Vec<(usize, f64)> = (0..c.row_count())
+            .filter_map(|i| {
+                if lower[i] == upper[i] || y[i] < -1e-9 {
+                    Some((i, lower[i]))
+                } else if y[i] > 1e-9 {
+                    Some((i, upper[i]))
+                } else {
+                    None
+                }
+            })
+            .filter(|(_, bound)| bound.is_finite())
+            .collect();
+
+        // [Q Cₐᵀ; Cₐ 0] [x; yₐ] = [-p; bounds], solved by Gaussian elimination
+        // with a tiny regularization followed by iterative refinement
+        let size = n + active.len();
+        let kkt = |regularization: f64| {
+            let mut matrix = Matrix::zeros(size, size);
+            for i in 0..n {
+                for j in 0..n {
+                    matrix[(i, j)] = self.q[(i, j)];
+                }
+                matrix[(i, i)] += regularization;
+            }
+            for (k, &(row, _)) in active.iter().enumerate() {
+                for j in 0..n {
+                    matrix[(n + k, j)] = c[(row, j)];
+                    matrix[(j, n + k)] = c[(row, j)];
+                }
+                matrix[(n + k, n + k)] -= regularization;
+            }
+            matrix
+        };
+        let exact = kkt(0.0);
+        let regularized = kkt(1e-9);
+        let target = Vector::new(self.p.iter().map(|v| -v).chain(active.iter().map(|&(_, b)| b)).collect());
+        let solve = |rhs: &Vector| -> Option<Vector> {
+            let mut augmented = Matrix::zeros(size, size + 1);
+            for i in 0..size {
+                for j in 0..size {
+                    augmented[(i, j)] = regularized[(i, j)];
+                }
+                augmented[(i, size)] = rhs[i];
+            }
+            augmented.gaussian_elimination().ok().map(Vector::new)
+        };
+        let mut solution = solve(&target)?;
+        for _ in 0..5 {
+            let residual = target.add(&exact.gemv(&solution).scale(-1.0));
+            solution.add_assign(&solve(&residual)?, 1.0);
+        }
+
+        let x_polished = Vector::new(solution.data[..n].to_vec());
+        let mut y_polished = Vector::zeros(c.row_count());
+        for (k, &(row, _)) in active.iter().enumerate() {
+            y_polished[row] = solution[n + k];
+        }
+        Some((x_polished, y_polished))
+    }
+}
+
+fn norm(v: &Vector) -> f64 {
+    max_abs(v.iter().copied())
+}
+
+fn max_abs<I: Iterator<Item = f64>>(values: I) -> f64 {
+    values.fold(0.0, |m, v| m.max(v.abs()))
+}
@@ -0,0 +1,80 @@
+use crate::math::{Matrix, Vector};
+use crate::pipeline::Transformer;
+use crate::preprocessing::check_size;
+use serde::{Deserialize, Serialize};
+
+/// What happens to the columns no transformer selects.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Remainder {
+    Drop,
+    /// Appended unchanged after the transformed columns.
+    Passthrough,
+}
+
+/// Applies each transformer to its own subset of columns and concatenates
+/// the outputs in order, e.g. scaling the numeric columns while one-hot
+/// encoding the categorical ones. A column may be selected more than once.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ColumnTransformer {
+    pub transformers: Vec<(String, Transformer, Vec<usize>)>, // Name, transformer and the input columns it sees
+    pub remainder: Remainder,
+    input_size: usize,
+}
+
+impl ColumnTransformer {
+    /// Creates a column transformer that drops unselected columns.
+    pub fn new() -> Self {
+        Self { transformers: Vec::new(), remainder: Remainder::Drop, input_size: 0 }
+    }
+
+    pub fn with_transformer(mut self, name: &str, transformer: impl Into<Transformer>, columns: &[usize]) -> Self {
+        assert!(!columns.is_empty(), "A transformer needs at least one column");
+        self.transformers.push((name.to_string(), transformer.into(), columns.to_vec()));
+        self
+    }
+
+    pub fn with_remainder(mut self, remainder: Remainder) -> Self {
+        self.remainder = remainder;
+        self
+    }
+
+    pub fn fit(&mut self, inputs: &Matrix) {
+        self.input_size = inputs.col_count();
+        for (name, transformer, columns) in self.transformers.iter_mut() {
+            assert!(columns.iter().all(|&j| j < inputs.col_count()), "Transformer {:?} selects a column beyond {}", name, inputs.col_count());
+            transformer.fit(&select(inputs, columns));
+        }
+    }
+
+    pub fn transform(&self, inputs: &Matrix) -> Matrix {
+        check_size(inputs, self.input_size);
+        let mut blocks: Vec<Matrix> = self.transformers.iter().map(|(_, transformer, columns)| transformer.transform(&select(inputs, columns))).collect();
+        if self.remainder == Remainder::Passthrough {
+            blocks.push(select(inputs, &self.remainder_columns()));
+        }
+        let rows = (0..inputs.row_count()).map(|i| Vector::new(blocks.iter().flat_map(|block| block.rows[i].data.iter().copied()).collect()));
+        Matrix { rows: rows.collect(), cols: blocks.iter().map(|block| block.cols).sum() }
+    }
+
+    pub fn fit_transform(&mut self, inputs: &Matrix) -> Matrix {
+        self.fit(inputs);
+        self.transform(inputs)
+    }
+
+    /// Input columns no transformer selects, in order.
+    pub fn remainder_columns(&self) -> Vec<usize> {
+        (0..self.input_size).filter(|j| !self.transformers.iter().any(|(_, _, columns)| columns.contains(j))).collect()
+    }
+}
+
+impl Default for ColumnTransformer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The given columns of every row.
+fn select(inputs: &Matrix, columns: &[usize]) -> Matrix {
+    let rows = inputs.rows.iter().map(|row| Vector::new(columns.iter().map(|&j| row[j]).collect()));
+    Matrix { rows: rows.collect(), cols: columns.len() }
+}
@@ -0,0 +1,8 @@
+pub mod column_transformer;
+#[allow(clippy::module_inception)]
+pub mod pipeline;
+pub mod step;
+
+pub use column_transformer::{ColumnTransformer, Remainder};
+pub use pipeline::Pipeline;
+pub use step::{Estimator, Transformer};
@@ -0,0 +1,67 @@
+use crate::math::{Matrix, Vector};
+use crate::pipeline::{Estimator, Transformer};
+use serde::{Deserialize, Serialize};
+
+/// Named transformers applied in order, optionally followed by an estimator.
+///
+/// `fit` fits every step on the output of the previous one and then the
+/// estimator; `predict` replays the fitted steps on new inputs. The whole
+/// pipeline, fitted statistics and model weights included, serializes as one
+/// value, e.g. with `serde_json::to_string`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Pipeline {
+    pub steps: Vec<(String, Transformer)>,
+    pub estimator: Option<Estimator>,
+}
+
+impl Pipeline {
+    pub fn new() -> Self {
+        Self { steps: Vec::new(), estimator: None }
+    }
+
+    /// Appends a transformer step.
+    pub fn with_step(mut self, name: &str, transformer: impl Into<Transformer>) -> Self {
+        assert!(self.estimator.is_none(), "Steps must come before the estimator");
+        assert!(self.step(name).is_none(), "Duplicate step name {:?}", name);
+        self.steps.push((name.to_string(), transformer.into()));
+        self
+    }
+
+    /// Sets the final estimator.
+    pub fn with_estimator(mut self, estimator: impl Into<Estimator>) -> Self {
+        self.estimator = Some(estimator.into());
+        self
+    }
+
+    /// The step called `name`.
+    pub fn step(&self, name: &str) -> Option<&Transformer> {
+        self.steps.iter().find(|(n, _)| n == name).map(|(_, t)| t)
+    }
+
+    /// Fits the steps and then the estimator on `targets`.
+    pub fn fit(&mut self, inputs: &Matrix, targets: &Vector) -> Result<(), String> {
+        assert!(inputs.row_count() == targets.len(), "Mismatched input and target sizes!");
+        let transformed = self.fit_transform(inputs);
+        match self.estimator.as_mut() {
+            Some(estimator) => estimator.fit(&transformed, targets),
+            None => Err("Pipeline has no estimator to fit".to_string()),
+        }
+    }
+
+    /// Fits the steps and returns the transformed inputs; the estimator, if
+    /// any, is left untouched.
+    pub fn fit_transform(&mut self, inputs: &Matrix) -> Matrix {
+        self.steps.iter_mut().fold(inputs.clone(), |data, (_, step)| step.fit_transform(&data))
+    }
+
+    /// Applies the fitted steps.
+    pub fn transform(&self, inputs: &Matrix) -> Matrix {
+        self.steps.iter().fold(inputs.clone(), |data, (_, step)| step.transform(&data))
+    }
+
+    /// Applies the fitted steps and the estimator.
+    pub fn predict(&self, inputs: &Matrix) -> Vector {
+        let estimator = self.estimator.as_ref().expect("Pipeline has no estimator to predict with");
+        estimator.predict(&self.transform(inputs))
+    }
+}
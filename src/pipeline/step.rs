@@ -0,0 +1,138 @@
+use crate::linear_regression::{ElasticNet, Lasso, LinearRegression, Ridge};
+use crate::logistic_regression::LogisticRegression;
+use crate::math::{Matrix, Vector};
+use crate::pipeline::{ColumnTransformer, Pipeline};
+use crate::preprocessing::{
+    IterativeImputer, KNNImputer, MinMaxScaler, Normalizer, OneHotEncoder, PolynomialFeatures, RobustScaler, SimpleImputer, SplineFeatures, StandardScaler,
+};
+use serde::{Deserialize, Serialize};
+
+/// A transformer that can be a pipeline step. Enumerating the variants
+/// (rather than boxing a trait object) keeps whole pipelines serializable.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Transformer {
+    StandardScaler(StandardScaler),
+    MinMaxScaler(MinMaxScaler),
+    RobustScaler(RobustScaler),
+    Normalizer(Normalizer),
+    PolynomialFeatures(PolynomialFeatures),
+    SplineFeatures(SplineFeatures),
+    SimpleImputer(SimpleImputer),
+    KNNImputer(KNNImputer),
+    IterativeImputer(IterativeImputer),
+    /// One-hot encodes numeric category codes, using their decimal text as
+    /// category names. Unknown codes follow the encoder's `handle_unknown`
+    /// and panic in `Error` mode.
+    OneHotEncoder(OneHotEncoder),
+    ColumnTransformer(ColumnTransformer),
+    /// A nested pipeline without a final estimator.
+    Pipeline(Pipeline),
+}
+
+impl Transformer {
+    pub fn fit(&mut self, inputs: &Matrix) {
+        match self {
+            Transformer::StandardScaler(t) => t.fit(inputs),
+            Transformer::MinMaxScaler(t) => t.fit(inputs),
+            Transformer::RobustScaler(t) => t.fit(inputs),
+            Transformer::Normalizer(_) => {}
+            Transformer::PolynomialFeatures(t) => t.fit(inputs),
+            Transformer::SplineFeatures(t) => t.fit(inputs),
+            Transformer::SimpleImputer(t) => t.fit(inputs),
+            Transformer::KNNImputer(t) => t.fit(inputs),
+            Transformer::IterativeImputer(t) => t.fit(inputs),
+            Transformer::OneHotEncoder(t) => t.fit(&as_categories(inputs)),
+            Transformer::ColumnTransformer(t) => t.fit(inputs),
+            Transformer::Pipeline(t) => {
+                t.fit_transform(inputs);
+            }
+        }
+    }
+
+    pub fn transform(&self, inputs: &Matrix) -> Matrix {
+        match self {
+            Transformer::StandardScaler(t) => t.transform(inputs),
+            Transformer::MinMaxScaler(t) => t.transform(inputs),
+            Transformer::RobustScaler(t) => t.transform(inputs),
+            Transformer::Normalizer(t) => t.transform(inputs),
+            Transformer::PolynomialFeatures(t) => t.transform(inputs),
+            Transformer::SplineFeatures(t) => t.transform(inputs),
+            Transformer::SimpleImputer(t) => t.transform(inputs),
+            Transformer::KNNImputer(t) => t.transform(inputs),
+            Transformer::IterativeImputer(t) => t.transform(inputs),
+            Transformer::OneHotEncoder(t) => t.transform(&as_categories(inputs)).unwrap_or_else(|e| panic!("{}", e)),
+            Transformer::ColumnTransformer(t) => t.transform(inputs),
+            Transformer::Pipeline(t) => t.transform(inputs),
+        }
+    }
+
+    pub fn fit_transform(&mut self, inputs: &Matrix) -> Matrix {
+        self.fit(inputs);
+        self.transform(inputs)
+    }
+}
+
+/// The final step of a pipeline: a model fitted on the transformed inputs.
+/// Classifiers take 0/1 targets and predict them as `f64`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Estimator {
+    /// Fitted with the normal equation.
+    LinearRegression(LinearRegression),
+    Ridge(Ridge),
+    Lasso(Lasso),
+    ElasticNet(ElasticNet),
+    /// Fitted with L-BFGS to a gradient tolerance of 1e-6 in at most 1000
+    /// iterations, so only the L2 penalty is supported. `fit` fails on an L1
+    /// penalty or when L-BFGS does not converge.
+    LogisticRegression(LogisticRegression),
+}
+
+impl Estimator {
+    pub fn fit(&mut self, inputs: &Matrix, targets: &Vector) -> Result<(), String> {
+        match self {
+            Estimator::LinearRegression(model) => model.fit(&inputs.rows, targets),
+            Estimator::Ridge(model) => model.fit(&inputs.rows, targets)?,
+            Estimator::Lasso(model) => model.fit(&inputs.rows, targets),
+            Estimator::ElasticNet(model) => model.fit(&inputs.rows, targets),
+            Estimator::LogisticRegression(model) => {
+                if model.l1_lambda != 0.0 {
+                    return Err("LogisticRegression in a pipeline is fitted with L-BFGS; set l1_lambda to 0".to_string());
+                }
+                let convergence = model.fit_lbfgs(&inputs.rows, targets, 1e-6, 1000);
+                if !convergence.converged {
+                    return Err(format!("LogisticRegression did not converge in {} iterations (gradient norm {:e})", convergence.iterations, convergence.gradient_norm));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn predict(&self, inputs: &Matrix) -> Vector {
+        let predict_one = |x: &Vector| match self {
+            Estimator::LinearRegression(model) => model.predict(x),
+            Estimator::Ridge(model) => model.predict(x),
+            Estimator::Lasso(model) => model.predict(x),
+            Estimator::ElasticNet(model) => model.predict(x),
+            Estimator::LogisticRegression(model) => model.predict(x) as f64,
+        };
+        Vector::new(inputs.rows.iter().map(predict_one).collect())
+    }
+}
+
+macro_rules! impl_from {
+    ($target:ident: $($variant:ident),*) => {
+        $(impl From<$variant> for $target {
+            fn from(value: $variant) -> Self {
+                $target::$variant(value)
+            }
+        })*
+    };
+}
+
+impl_from!(Transformer: StandardScaler, MinMaxScaler, RobustScaler, Normalizer, PolynomialFeatures, SplineFeatures, SimpleImputer, KNNImputer, IterativeImputer, OneHotEncoder, ColumnTransformer, Pipeline);
+impl_from!(Estimator: LinearRegression, Ridge, Lasso, ElasticNet, LogisticRegression);
+
+/// Numeric codes as category strings, e.g. `2.0` as `"2"`.
+fn as_categories(inputs: &Matrix) -> Vec<Vec<String>> {
+    inputs.rows.iter().map(|row| row.iter().map(|v| v.to_string()).collect()).collect()
+}
@@ -0,0 +1,368 @@
+use crate::math::{Matrix, SparseMatrix, Vector};
+use crate::preprocessing::input_names_or_default;
+use serde::{Deserialize, Serialize};
+
+/// What `OneHotEncoder::transform` does with a category not seen by `fit`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum HandleUnknown {
+    /// Fail with an error naming the category.
+    Error,
+    /// Encode the column as all zeros.
+    Ignore,
+}
+
+/// Encodes each string column as one indicator column per category, with
+/// categories in sorted order.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OneHotEncoder {
+    pub handle_unknown: HandleUnknown,
+    pub categories: Vec<Vec<String>>, // Sorted categories per column, set by fit
+}
+
+impl OneHotEncoder {
+    pub fn new() -> Self {
+        Self { handle_unknown: HandleUnknown::Error, categories: Vec::new() }
+    }
+
+    pub fn with_handle_unknown(mut self, handle_unknown: HandleUnknown) -> Self {
+        self.handle_unknown = handle_unknown;
+        self
+    }
+
+    pub fn fit<S: AsRef<str>>(&mut self, rows: &[Vec<S>]) {
+        self.categories = fit_categories(rows);
+    }
+
+    /// Number of output columns.
+    pub fn output_size(&self) -> usize {
+        self.categories.iter().map(Vec::len).sum()
+    }
+
+    /// Output columns set to one for each row.
+    fn active_columns<S: AsRef<str>>(&self, rows: &[Vec<S>]) -> Result<Vec<Vec<usize>>, String> {
+        rows.iter()
+            .map(|row| {
+                check_row(row.len(), self.categories.len());
+                let mut offset = 0;
+                let mut active = Vec::with_capacity(row.len());
+                for (j, (value, categories)) in row.iter().zip(&self.categories).enumerate() {
+                    match categories.binary_search_by(|c| c.as_str().cmp(value.as_ref())) {
+                        Ok(k) => active.push(offset + k),
+                        Err(_) if self.handle_unknown == HandleUnknown::Ignore => {}
+                        Err(_) => return Err(format!("Unknown category {:?} in column {}", value.as_ref(), j)),
+                    }
+                    offset += categories.len();
+                }
+                Ok(active)
+            })
+            .collect()
+    }
+
+    pub fn transform<S: AsRef<str>>(&self, rows: &[Vec<S>]) -> Result<Matrix, String> {
+        let mut output = Matrix::zeros(rows.len(), self.output_size());
+        for (i, active) in self.active_columns(rows)?.into_iter().enumerate() {
+            for k in active {
+                output[(i, k)] = 1.0;
+            }
+        }
+        Ok(output)
+    }
+
+    /// Same as `transform` but stores only the ones, which pays off for
+    /// columns with many categories.
+    pub fn transform_sparse<S: AsRef<str>>(&self, rows: &[Vec<S>]) -> Result<SparseMatrix, String> {
+        let mut output = SparseMatrix::new(self.output_size());
+        for active in self.active_columns(rows)? {
+            output.push_row(active.into_iter().map(|k| (k, 1.0)));
+        }
+        Ok(output)
+    }
+
+    pub fn fit_transform<S: AsRef<str>>(&mut self, rows: &[Vec<S>]) -> Matrix {
+        self.fit(rows);
+        self.transform(rows).expect("Every category is known after fit")
+    }
+
+    /// Recovers the categories from indicator rows; a column without a one
+    /// (an ignored unknown category) gives `None`.
+    pub fn inverse_transform(&self, encoded: &Matrix) -> Vec<Vec<Option<String>>> {
+        assert!(encoded.col_count() == self.output_size(), "Expected {} encoded columns, got {}", self.output_size(), encoded.col_count());
+        encoded
+            .rows
+            .iter()
+            .map(|row| {
+                let mut offset = 0;
+                self.categories
+                    .iter()
+                    .map(|categories| {
+                        let block = &row.data[offset..offset + categories.len()];
+                        offset += categories.len();
+                        block.iter().position(|&v| v != 0.0).map(|k| categories[k].clone())
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Names of the output columns such as `"x0_red"`, built from
+    /// `input_names` (default `x0`, `x1`, ...).
+    pub fn feature_names(&self, input_names: Option<&[String]>) -> Vec<String> {
+        input_names_or_default(input_names, self.categories.len())
+            .iter()
+            .zip(&self.categories)
+            .flat_map(|(name, categories)| categories.iter().map(move |c| format!("{}_{}", name, c)))
+            .collect()
+    }
+}
+
+impl Default for OneHotEncoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Encodes each string column as the index of its category in sorted order.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OrdinalEncoder {
+    pub unknown_value: Option<f64>,   // Code for unseen categories; None makes them an error
+    pub categories: Vec<Vec<String>>, // Sorted categories per column, set by fit
+}
+
+impl OrdinalEncoder {
+    pub fn new() -> Self {
+        Self { unknown_value: None, categories: Vec::new() }
+    }
+
+    /// Encodes unseen categories as `value` (e.g. -1 or NaN) instead of failing.
+    pub fn with_unknown_value(mut self, value: f64) -> Self {
+        self.unknown_value = Some(value);
+        self
+    }
+
+    pub fn fit<S: AsRef<str>>(&mut self, rows: &[Vec<S>]) {
+        self.categories = fit_categories(rows);
+    }
+
+    pub fn transform<S: AsRef<str>>(&self, rows: &[Vec<S>]) -> Result<Matrix, String> {
+        let encoded: Result<Vec<Vector>, String> = rows
+            .iter()
+            .map(|row| {
+                check_row(row.len(), self.categories.len());
+                let codes = row.iter().zip(&self.categories).enumerate().map(|(j, (value, categories))| {
+                    match (categories.binary_search_by(|c| c.as_str().cmp(value.as_ref())), self.unknown_value) {
+                        (Ok(k), _) => Ok(k as f64),
+                        (Err(_), Some(unknown)) => Ok(unknown),
+                        (Err(_), None) => Err(format!("Unknown category {:?} in column {}", value.as_ref(), j)),
+                    }
+                });
+                Ok(Vector::new(codes.collect::<Result<_, String>>()?))
+            })
+            .collect();
+        Ok(Matrix { rows: encoded?, cols: self.categories.len() })
+    }
+
+    pub fn fit_transform<S: AsRef<str>>(&mut self, rows: &[Vec<S>]) -> Matrix {
+        self.fit(rows);
+        self.transform(rows).expect("Every category is known after fit")
+    }
+
+    /// Maps codes back to categories; codes that are not a valid index
+    /// (such as the unknown value) give `None`.
+    pub fn inverse_transform(&self, encoded: &Matrix) -> Vec<Vec<Option<String>>> {
+        check_row(encoded.col_count(), self.categories.len());
+        encoded
+            .rows
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .zip(&self.categories)
+                    .map(|(&code, categories)| {
+                        let valid = code >= 0.0 && code.fract() == 0.0 && (code as usize) < categories.len();
+                        valid.then(|| categories[code as usize].clone())
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+impl Default for OrdinalEncoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Maps class labels to the indices `0..n_classes` expected by the
+/// classifiers, with classes in sorted order.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct LabelEncoder {
+    pub classes: Vec<String>, // Sorted distinct labels, set by fit
+}
+
+impl LabelEncoder {
+    pub fn new() -> Self {
+        Self { classes: Vec::new() }
+    }
+
+    pub fn fit<S: AsRef<str>>(&mut self, labels: &[S]) {
+        let mut classes: Vec<String> = labels.iter().map(|l| l.as_ref().to_string()).collect();
+        classes.sort();
+        classes.dedup();
+        self.classes = classes;
+    }
+
+    /// Class indices for `SoftmaxClassifier` and `SoftmaxRegression`.
+    pub fn transform<S: AsRef<str>>(&self, labels: &[S]) -> Result<Vec<usize>, String> {
+        labels
+            .iter()
+            .map(|l| self.classes.binary_search_by(|c| c.as_str().cmp(l.as_ref())).map_err(|_| format!("Unknown label {:?}", l.as_ref())))
+            .collect()
+    }
+
+    /// Class indices as `i32`, for `MultiClassPerceptron`.
+    pub fn transform_i32<S: AsRef<str>>(&self, labels: &[S]) -> Result<Vec<i32>, String> {
+        Ok(self.transform(labels)?.into_iter().map(|k| k as i32).collect())
+    }
+
+    pub fn fit_transform<S: AsRef<str>>(&mut self, labels: &[S]) -> Vec<usize> {
+        self.fit(labels);
+        self.transform(labels).expect("Every label is known after fit")
+    }
+
+    /// Labels of predicted class indices.
+    pub fn inverse_transform(&self, indices: &[usize]) -> Vec<String> {
+        indices
+            .iter()
+            .map(|&k| {
+                assert!(k < self.classes.len(), "Class index {} out of range for {} classes", k, self.classes.len());
+                self.classes[k].clone()
+            })
+            .collect()
+    }
+}
+
+/// Replaces each category by a smoothed mean of the target over the rows
+/// with that category:
+///
+/// `(count * category_mean + smoothing * global_mean) / (count + smoothing)`
+///
+/// which shrinks rare categories towards the global mean. `fit_transform`
+/// cross-fits: the rows are split into `n_folds` interleaved folds and each
+/// fold is encoded with statistics from the other folds only, so a row's own
+/// target never leaks into its encoding. `transform` uses the statistics of
+/// all training rows, and unseen categories get the global mean.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TargetEncoder {
+    pub smoothing: f64,
+    pub n_folds: usize,
+    pub categories: Vec<Vec<String>>, // Sorted categories per column, set by fit
+    pub encodings: Vec<Vec<f64>>,     // Encoded value per category, set by fit
+    pub global_mean: f64,
+}
+
+impl TargetEncoder {
+    /// Creates an unfitted encoder with smoothing 1 and 5 folds.
+    pub fn new() -> Self {
+        Self { smoothing: 1.0, n_folds: 5, categories: Vec::new(), encodings: Vec::new(), global_mean: 0.0 }
+    }
+
+    pub fn with_smoothing(mut self, smoothing: f64) -> Self {
+        assert!(smoothing >= 0.0, "Smoothing must be non-negative");
+        self.smoothing = smoothing;
+        self
+    }
+
+    pub fn with_folds(mut self, n_folds: usize) -> Self {
+        assert!(n_folds >= 2, "Cross-fitting needs at least two folds");
+        self.n_folds = n_folds;
+        self
+    }
+
+    /// Learns the encodings from all rows.
+    pub fn fit<S: AsRef<str>>(&mut self, rows: &[Vec<S>], targets: &Vector) {
+        assert!(rows.len() == targets.len(), "Mismatched input and target sizes!");
+        self.categories = fit_categories(rows);
+        let all: Vec<usize> = (0..rows.len()).collect();
+        (self.encodings, self.global_mean) = self.statistics(rows, targets, &all);
+    }
+
+    pub fn transform<S: AsRef<str>>(&self, rows: &[Vec<S>]) -> Matrix {
+        let encoded = rows.iter().map(|row| self.encode_row(row, &self.encodings, self.global_mean)).collect();
+        Matrix { rows: encoded, cols: self.categories.len() }
+    }
+
+    /// Fits on all rows and returns the cross-fitted encoding of the
+    /// training rows, which is what a downstream model should be trained on.
+    pub fn fit_transform<S: AsRef<str>>(&mut self, rows: &[Vec<S>], targets: &Vector) -> Matrix {
+        self.fit(rows, targets);
+        let mut output = Matrix::zeros(rows.len(), self.categories.len());
+        for fold in 0..self.n_folds.min(rows.len()) {
+            let training: Vec<usize> = (0..rows.len()).filter(|i| i % self.n_folds != fold).collect();
+            let (encodings, mean) = self.statistics(rows, targets, &training);
+            for i in (fold..rows.len()).step_by(self.n_folds) {
+                output.rows[i] = self.encode_row(&rows[i], &encodings, mean);
+            }
+        }
+        output
+    }
+
+    /// Smoothed category means and the global mean over the rows in `subset`.
+    fn statistics<S: AsRef<str>>(&self, rows: &[Vec<S>], targets: &Vector, subset: &[usize]) -> (Vec<Vec<f64>>, f64) {
+        let mean = subset.iter().map(|&i| targets[i]).sum::<f64>() / subset.len().max(1) as f64;
+        let encodings = self
+            .categories
+            .iter()
+            .enumerate()
+            .map(|(j, categories)| {
+                let mut sums = vec![0.0; categories.len()];
+                let mut counts = vec![0.0; categories.len()];
+                for &i in subset {
+                    let k = categories.binary_search_by(|c| c.as_str().cmp(rows[i][j].as_ref())).expect("Category seen by fit");
+                    sums[k] += targets[i];
+                    counts[k] += 1.0;
+                }
+                sums.iter().zip(&counts).map(|(s, c)| if c + self.smoothing > 0.0 { (s + self.smoothing * mean) / (c + self.smoothing) } else { mean }).collect()
+            })
+            .collect();
+        (encodings, mean)
+    }
+
+    fn encode_row<S: AsRef<str>>(&self, row: &[S], encodings: &[Vec<f64>], mean: f64) -> Vector {
+        check_row(row.len(), self.categories.len());
+        Vector::new(
+            row.iter()
+                .zip(&self.categories)
+                .zip(encodings)
+                .map(|((value, categories), encoding)| categories.binary_search_by(|c| c.as_str().cmp(value.as_ref())).map_or(mean, |k| encoding[k]))
+                .collect(),
+        )
+    }
+}
+
+impl Default for TargetEncoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Sorted distinct values of each column.
+fn fit_categories<S: AsRef<str>>(rows: &[Vec<S>]) -> Vec<Vec<String>> {
+    assert!(!rows.is_empty(), "Cannot fit on an empty dataset");
+    let width = rows[0].len();
+    let mut categories = vec![Vec::new(); width];
+    for row in rows {
+        check_row(row.len(), width);
+        for (values, value) in categories.iter_mut().zip(row) {
+            values.push(value.as_ref().to_string());
+        }
+    }
+    for values in categories.iter_mut() {
+        values.sort();
+        values.dedup();
+    }
+    categories
+}
+
+fn check_row(len: usize, fitted: usize) {
+    assert!(len == fitted, "Encoder was fitted on {} columns, got {}", fitted, len);
+}
@@ -0,0 +1,340 @@
+use crate::linear_regression::Ridge;
+use crate::math::{Matrix, Vector};
+use crate::preprocessing::check_size;
+use crate::preprocessing::scaler::quantile;
+use serde::{Deserialize, Serialize};
+
+/// Marks missing values (NaN) with indicator columns, one for each feature
+/// that had missing values during `fit`.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct MissingIndicator {
+    pub features: Vec<usize>, // Columns with missing values at fit time
+    input_size: usize,
+}
+
+impl MissingIndicator {
+    pub fn new() -> Self {
+        Self { features: Vec::new(), input_size: 0 }
+    }
+
+    pub fn fit(&mut self, inputs: &Matrix) {
+        self.input_size = inputs.col_count();
+        self.features = (0..inputs.col_count()).filter(|&j| inputs.rows.iter().any(|row| row[j].is_nan())).collect();
+    }
+
+    /// 1 where the value is missing, 0 otherwise, for the fitted features.
+    pub fn transform(&self, inputs: &Matrix) -> Matrix {
+        check_size(inputs, self.input_size);
+        let rows = inputs.rows.iter().map(|row| Vector::new(self.features.iter().map(|&j| if row[j].is_nan() { 1.0 } else { 0.0 }).collect()));
+        Matrix { rows: rows.collect(), cols: self.features.len() }
+    }
+
+    pub fn fit_transform(&mut self, inputs: &Matrix) -> Matrix {
+        self.fit(inputs);
+        self.transform(inputs)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ImputeStrategy {
+    Mean,
+    Median,
+    /// The most common value, the smallest one on ties.
+    MostFrequent,
+    Constant(f64),
+}
+
+/// Replaces missing values (NaN) with a per-column statistic of the
+/// observed values. Columns without any observed value are filled with 0.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SimpleImputer {
+    pub strategy: ImputeStrategy,
+    pub statistics: Vector,                  // Fill value per column, set by fit
+    pub indicator: Option<MissingIndicator>, // Appends missing indicator columns when set
+}
+
+impl SimpleImputer {
+    pub fn new(strategy: ImputeStrategy) -> Self {
+        Self { strategy, statistics: Vector::zeros(0), indicator: None }
+    }
+
+    /// Appends a `MissingIndicator` for the columns with gaps to the output.
+    pub fn with_indicator(mut self) -> Self {
+        self.indicator = Some(MissingIndicator::new());
+        self
+    }
+
+    pub fn fit(&mut self, inputs: &Matrix) {
+        self.statistics = Vector::new(
+            (0..inputs.col_count())
+                .map(|j| {
+                    let mut observed: Vec<f64> = inputs.rows.iter().map(|row| row[j]).filter(|v| !v.is_nan()).collect();
+                    if let ImputeStrategy::Constant(value) = self.strategy {
+                        return value;
+                    }
+                    if observed.is_empty() {
+                        return 0.0;
+                    }
+                    observed.sort_by(f64::total_cmp);
+                    match self.strategy {
+                        ImputeStrategy::Mean => observed.iter().sum::<f64>() / observed.len() as f64,
+                        ImputeStrategy::Median => quantile(&observed, 0.5),
+                        _ => most_frequent(&observed),
+                    }
+                })
+                .collect(),
+        );
+        if let Some(indicator) = self.indicator.as_mut() {
+            indicator.fit(inputs);
+        }
+    }
+
+    pub fn transform(&self, inputs: &Matrix) -> Matrix {
+        check_size(inputs, self.statistics.len());
+        let rows = inputs.rows.iter().map(|row| Vector::new(row.iter().zip(self.statistics.iter()).map(|(&v, &fill)| if v.is_nan() { fill } else { v }).collect()));
+        append_indicator(Matrix { rows: rows.collect(), cols: inputs.col_count() }, inputs, &self.indicator)
+    }
+
+    pub fn fit_transform(&mut self, inputs: &Matrix) -> Matrix {
+        self.fit(inputs);
+        self.transform(inputs)
+    }
+}
+
+/// Fills each missing value with the mean of that feature over the
+/// `n_neighbors` nearest training rows which observe it.
+///
+/// Distances ignore coordinates missing in either row and are scaled up by
+/// the share of coordinates used:
+/// `d(a, b) = sqrt(n / |common| * Σ_common (a_j - b_j)²)`. Rows sharing no
+/// observed coordinate are never neighbors; when no neighbor observes the
+/// feature the training mean is used. The training rows are kept by `fit`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KNNImputer {
+    pub n_neighbors: usize,
+    #[serde(with = "missing_as_null")]
+    pub training: Matrix, // Rows searched for neighbors, set by fit
+    pub means: Vector,    // Fallback per column, set by fit
+    pub indicator: Option<MissingIndicator>,
+}
+
+impl KNNImputer {
+    pub fn new(n_neighbors: usize) -> Self {
+        assert!(n_neighbors > 0, "Need at least one neighbor");
+        Self { n_neighbors, training: Matrix::zeros(0, 0), means: Vector::zeros(0), indicator: None }
+    }
+
+    pub fn with_indicator(mut self) -> Self {
+        self.indicator = Some(MissingIndicator::new());
+        self
+    }
+
+    pub fn fit(&mut self, inputs: &Matrix) {
+        let mut mean_imputer = SimpleImputer::new(ImputeStrategy::Mean);
+        mean_imputer.fit(inputs);
+        self.means = mean_imputer.statistics;
+        self.training = inputs.clone();
+        if let Some(indicator) = self.indicator.as_mut() {
+            indicator.fit(inputs);
+        }
+    }
+
+    pub fn transform(&self, inputs: &Matrix) -> Matrix {
+        check_size(inputs, self.means.len());
+        let rows = inputs.rows.iter().map(|row| {
+            if !row.iter().any(|v| v.is_nan()) {
+                return row.clone();
+            }
+            let mut distances: Vec<(f64, &Vector)> = self.training.rows.iter().filter_map(|other| nan_euclidean(row, other).map(|d| (d, other))).collect();
+            distances.sort_by(|a, b| a.0.total_cmp(&b.0));
+            Vector::new(
+                row.iter()
+                    .enumerate()
+                    .map(|(j, &v)| {
+                        if !v.is_nan() {
+                            return v;
+                        }
+                        let donors: Vec<f64> = distances.iter().map(|(_, other)| other[j]).filter(|x| !x.is_nan()).take(self.n_neighbors).collect();
+                        if donors.is_empty() { self.means[j] } else { donors.iter().sum::<f64>() / donors.len() as f64 }
+                    })
+                    .collect(),
+            )
+        });
+        append_indicator(Matrix { rows: rows.collect(), cols: inputs.col_count() }, inputs, &self.indicator)
+    }
+
+    pub fn fit_transform(&mut self, inputs: &Matrix) -> Matrix {
+        self.fit(inputs);
+        self.transform(inputs)
+    }
+}
+
+/// Models each feature with gaps as a `Ridge` regression on all other
+/// features and refines the imputed values round by round.
+///
+/// Missing values start at the column means. Each round regresses every
+/// column that had gaps during `fit` on the current values of the others,
+/// using the rows where it is observed, and replaces its missing entries by
+/// the predictions. Rounds stop after `max_iter` or once the largest change
+/// of an imputed value is below `tol` times the largest absolute value.
+/// `transform` replays the same number of rounds with the final models;
+/// gaps in columns that were complete during `fit` keep the mean.
+///
+/// The small ridge penalty `lambda` keeps the regressions solvable when
+/// features are collinear or a column has fewer observed rows than
+/// features. Columns without any observed value keep the zero fill of
+/// `SimpleImputer`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IterativeImputer {
+    pub max_iter: usize,
+    pub tol: f64,
+    pub lambda: f64,                 // Ridge penalty of the regressions
+    pub means: Vector,               // Initial fill per column, set by fit
+    pub models: Vec<Option<Vector>>, // Regression weights (bias first) per column with gaps, set by fit
+    pub n_iter: usize,               // Rounds performed by fit
+    pub indicator: Option<MissingIndicator>,
+}
+
+impl IterativeImputer {
+    /// Creates an unfitted imputer with up to 10 rounds, a tolerance of 1e-3
+    /// and a ridge penalty of 1e-8.
+    pub fn new() -> Self {
+        Self { max_iter: 10, tol: 1e-3, lambda: 1e-8, means: Vector::zeros(0), models: Vec::new(), n_iter: 0, indicator: None }
+    }
+
+    pub fn with_max_iter(mut self, max_iter: usize) -> Self {
+        self.max_iter = max_iter;
+        self
+    }
+
+    pub fn with_tolerance(mut self, tol: f64) -> Self {
+        self.tol = tol;
+        self
+    }
+
+    pub fn with_lambda(mut self, lambda: f64) -> Self {
+        assert!(lambda > 0.0, "Ridge penalty must be positive");
+        self.lambda = lambda;
+        self
+    }
+
+    pub fn with_indicator(mut self) -> Self {
+        self.indicator = Some(MissingIndicator::new());
+        self
+    }
+
+    pub fn fit(&mut self, inputs: &Matrix) {
+        let mut mean_imputer = SimpleImputer::new(ImputeStrategy::Mean);
+        let mut filled = mean_imputer.fit_transform(inputs);
+        self.means = mean_imputer.statistics;
+        self.models = vec![None; inputs.col_count()];
+        self.n_iter = 0;
+        let scale = filled.rows.iter().flat_map(|row| row.iter()).fold(0.0f64, |m, v| m.max(v.abs()));
+
+        while self.n_iter < self.max_iter {
+            self.n_iter += 1;
+            let mut max_change: f64 = 0.0;
+            for j in 0..inputs.col_count() {
+                let observed: Vec<usize> = (0..inputs.row_count()).filter(|&i| !inputs[(i, j)].is_nan()).collect();
+                if observed.is_empty() || observed.len() == inputs.row_count() {
+                    continue;
+                }
+                let mut model = Ridge::new(self.lambda);
+                let features: Vec<Vector> = observed.iter().map(|&i| without(&filled.rows[i], j)).collect();
+                model.fit(&features, &Vector::new(observed.iter().map(|&i| filled[(i, j)]).collect())).expect("A positive ridge penalty keeps the system solvable");
+                for i in (0..inputs.row_count()).filter(|&i| inputs[(i, j)].is_nan()) {
+                    let prediction = model.predict(&without(&filled.rows[i], j));
+                    max_change = max_change.max((prediction - filled[(i, j)]).abs());
+                    filled[(i, j)] = prediction;
+                }
+                self.models[j] = Some(model.weights);
+            }
+            if max_change <= self.tol * scale {
+                break;
+            }
+        }
+        if let Some(indicator) = self.indicator.as_mut() {
+            indicator.fit(inputs);
+        }
+    }
+
+    pub fn transform(&self, inputs: &Matrix) -> Matrix {
+        check_size(inputs, self.means.len());
+        let fill = SimpleImputer { strategy: ImputeStrategy::Mean, statistics: self.means.clone(), indicator: None };
+        let mut filled = fill.transform(inputs);
+        for _ in 0..self.n_iter {
+            for (j, model) in self.models.iter().enumerate() {
+                let Some(weights) = model else { continue };
+                for i in (0..inputs.row_count()).filter(|&i| inputs[(i, j)].is_nan()) {
+                    let features = without(&filled.rows[i], j);
+                    filled[(i, j)] = weights[0] + weights.data[1..].iter().zip(features.iter()).map(|(w, x)| w * x).sum::<f64>();
+                }
+            }
+        }
+        append_indicator(filled, inputs, &self.indicator)
+    }
+
+    pub fn fit_transform(&mut self, inputs: &Matrix) -> Matrix {
+        self.fit(inputs);
+        self.transform(inputs)
+    }
+}
+
+impl Default for IterativeImputer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Serializes a matrix with missing values, writing NaN as `null` since
+/// JSON has no NaN.
+mod missing_as_null {
+    use crate::math::{Matrix, Vector};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(matrix: &Matrix, serializer: S) -> Result<S::Ok, S::Error> {
+        let rows: Vec<Vec<Option<f64>>> = matrix.rows.iter().map(|row| row.iter().map(|v| (!v.is_nan()).then_some(*v)).collect()).collect();
+        (rows, matrix.cols).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Matrix, D::Error> {
+        let (rows, cols): (Vec<Vec<Option<f64>>>, usize) = Deserialize::deserialize(deserializer)?;
+        let rows = rows.into_iter().map(|row| Vector::new(row.into_iter().map(|v| v.unwrap_or(f64::NAN)).collect()));
+        Ok(Matrix { rows: rows.collect(), cols })
+    }
+}
+
+/// Most common value of sorted values, the smallest one on ties.
+fn most_frequent(sorted: &[f64]) -> f64 {
+    let (mut best, mut best_count) = (sorted[0], 0);
+    let mut k = 0;
+    while k < sorted.len() {
+        let count = sorted[k..].iter().take_while(|&&v| v == sorted[k]).count();
+        if count > best_count {
+            (best, best_count) = (sorted[k], count);
+        }
+        k += count;
+    }
+    best
+}
+
+/// Euclidean distance over the coordinates observed in both rows, scaled to
+/// the full dimension; `None` when no coordinate is shared.
+fn nan_euclidean(a: &Vector, b: &Vector) -> Option<f64> {
+    let (sum, common) = a.iter().zip(b.iter()).filter(|(x, y)| !x.is_nan() && !y.is_nan()).fold((0.0, 0), |(s, c), (x, y)| (s + (x - y).powi(2), c + 1));
+    (common > 0).then(|| (sum * a.len() as f64 / common as f64).sqrt())
+}
+
+/// The row without column `j`.
+fn without(row: &Vector, j: usize) -> Vector {
+    Vector::new(row.iter().enumerate().filter(|&(k, _)| k != j).map(|(_, &v)| v).collect())
+}
+
+fn append_indicator(imputed: Matrix, inputs: &Matrix, indicator: &Option<MissingIndicator>) -> Matrix {
+    let Some(indicator) = indicator else {
+        return imputed;
+    };
+    let flags = indicator.transform(inputs);
+    let rows = imputed.rows.into_iter().zip(flags.rows).map(|(row, flag)| Vector::new(row.data.into_iter().chain(flag.data).collect()));
+    Matrix { rows: rows.collect(), cols: imputed.cols + flags.cols }
+}
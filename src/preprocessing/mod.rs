@@ -0,0 +1,29 @@
+pub mod encoder;
+pub mod imputer;
+pub mod polynomial;
+pub mod scaler;
+pub mod spline;
+
+pub use encoder::{HandleUnknown, LabelEncoder, OneHotEncoder, OrdinalEncoder, TargetEncoder};
+pub use imputer::{ImputeStrategy, IterativeImputer, KNNImputer, MissingIndicator, SimpleImputer};
+pub use polynomial::PolynomialFeatures;
+pub use scaler::{MinMaxScaler, Norm, Normalizer, RobustScaler, StandardScaler};
+pub use spline::SplineFeatures;
+
+use crate::math::Matrix;
+
+/// Panics unless `inputs` has as many columns as the transformer was fitted on.
+pub(crate) fn check_size(inputs: &Matrix, fitted: usize) {
+    assert!(inputs.col_count() == fitted, "Transformer was fitted on {} features, got {}", fitted, inputs.col_count());
+}
+
+/// `input_names` checked against `size`, or `x0`, `x1`, ... when absent.
+pub(crate) fn input_names_or_default(input_names: Option<&[String]>, size: usize) -> Vec<String> {
+    match input_names {
+        Some(names) => {
+            assert!(names.len() == size, "Expected {} feature names, got {}", size, names.len());
+            names.to_vec()
+        }
+        None => (0..size).map(|j| format!("x{}", j)).collect(),
+    }
+}
@@ -0,0 +1,101 @@
+use crate::math::{Matrix, Vector};
+use crate::preprocessing::{check_size, input_names_or_default};
+use serde::{Deserialize, Serialize};
+
+/// Expands each row into all monomials of its features up to `degree`.
+///
+/// For `[a, b]` and degree 2 the output is `[1, a, b, a², ab, b²]`: the bias,
+/// then the terms of each degree in lexicographic order of the feature
+/// indices. With `interaction_only` no feature appears twice in a term
+/// (`[1, a, b, ab]`). Fitting on the expanded features lets the linear
+/// models capture polynomial trends.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PolynomialFeatures {
+    pub degree: usize,
+    pub interaction_only: bool, // Only products of distinct features
+    pub include_bias: bool,     // Start with a constant column of ones
+    terms: Vec<Vec<usize>>,     // Feature indices multiplied in each output column, set by fit
+    input_size: usize,
+}
+
+impl PolynomialFeatures {
+    /// Creates an unfitted transformer including the bias column.
+    pub fn new(degree: usize) -> Self {
+        Self { degree, interaction_only: false, include_bias: true, terms: Vec::new(), input_size: 0 }
+    }
+
+    pub fn with_interaction_only(mut self, interaction_only: bool) -> Self {
+        self.interaction_only = interaction_only;
+        self
+    }
+
+    pub fn with_include_bias(mut self, include_bias: bool) -> Self {
+        self.include_bias = include_bias;
+        self
+    }
+
+    /// Enumerates the output terms for the number of columns of `inputs`.
+    pub fn fit(&mut self, inputs: &Matrix) {
+        self.input_size = inputs.col_count();
+        self.terms = if self.include_bias { vec![Vec::new()] } else { Vec::new() };
+        let mut previous: Vec<Vec<usize>> = vec![Vec::new()];
+        for _ in 0..self.degree {
+            // Extend each term of the previous degree with an index not below its last one
+            let next: Vec<Vec<usize>> = previous
+                .iter()
+                .flat_map(|term| {
+                    let start = match term.last() {
+                        Some(&last) if self.interaction_only => last + 1,
+                        Some(&last) => last,
+                        None => 0,
+                    };
+                    (start..self.input_size).map(move |j| {
+                        let mut extended = term.clone();
+                        extended.push(j);
+                        extended
+                    })
+                })
+                .collect();
+            self.terms.extend(next.iter().cloned());
+            previous = next;
+        }
+    }
+
+    /// Number of output columns, available after `fit`.
+    pub fn output_size(&self) -> usize {
+        self.terms.len()
+    }
+
+    pub fn transform(&self, inputs: &Matrix) -> Matrix {
+        check_size(inputs, self.input_size);
+        let rows = inputs.rows.iter().map(|row| Vector::new(self.terms.iter().map(|term| term.iter().map(|&j| row[j]).product()).collect()));
+        Matrix { rows: rows.collect(), cols: self.output_size() }
+    }
+
+    pub fn fit_transform(&mut self, inputs: &Matrix) -> Matrix {
+        self.fit(inputs);
+        self.transform(inputs)
+    }
+
+    /// Names of the output columns such as `"1"`, `"x0"` or `"x0^2 x1"`,
+    /// built from `input_names` (default `x0`, `x1`, ...).
+    pub fn feature_names(&self, input_names: Option<&[String]>) -> Vec<String> {
+        let names = input_names_or_default(input_names, self.input_size);
+        self.terms
+            .iter()
+            .map(|term| {
+                if term.is_empty() {
+                    return "1".to_string();
+                }
+                let mut factors: Vec<String> = Vec::new();
+                let mut k = 0;
+                while k < term.len() {
+                    let power = term[k..].iter().take_while(|&&j| j == term[k]).count();
+                    factors.push(if power == 1 { names[term[k]].clone() } else { format!("{}^{}", names[term[k]], power) });
+                    k += power;
+                }
+                factors.join(" ")
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,254 @@
+use crate::math::{Matrix, Vector};
+use crate::preprocessing::check_size;
+use serde::{Deserialize, Serialize};
+
+/// Standardizes each column to zero mean and unit variance:
+/// `z = (x - mean) / std`, with the population standard deviation.
+/// Columns with zero variance are only centered.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StandardScaler {
+    pub with_mean: bool, // Subtract the column means
+    pub with_std: bool,  // Divide by the column standard deviations
+    pub mean: Vector,    // Column means, set by fit
+    pub scale: Vector,   // Column standard deviations (1 for constant columns), set by fit
+}
+
+impl StandardScaler {
+    pub fn new() -> Self {
+        Self { with_mean: true, with_std: true, mean: Vector::zeros(0), scale: Vector::zeros(0) }
+    }
+
+    pub fn with_mean(mut self, with_mean: bool) -> Self {
+        self.with_mean = with_mean;
+        self
+    }
+
+    pub fn with_std(mut self, with_std: bool) -> Self {
+        self.with_std = with_std;
+        self
+    }
+
+    pub fn fit(&mut self, inputs: &Matrix) {
+        assert!(inputs.row_count() > 0, "Cannot fit on an empty dataset");
+        let n = inputs.row_count() as f64;
+        self.mean = inputs.column_sums().scale(1.0 / n);
+        self.scale = Vector::new(
+            (0..inputs.col_count())
+                .map(|j| {
+                    let variance = inputs.rows.iter().map(|row| (row[j] - self.mean[j]).powi(2)).sum::<f64>() / n;
+                    non_zero(variance.sqrt())
+                })
+                .collect(),
+        );
+    }
+
+    pub fn transform(&self, inputs: &Matrix) -> Matrix {
+        check_size(inputs, self.mean.len());
+        let center = if self.with_mean { self.mean.clone() } else { Vector::zeros(self.mean.len()) };
+        let scale = if self.with_std { self.scale.clone() } else { Vector::new(vec![1.0; self.scale.len()]) };
+        map_columns(inputs, |j, x| (x - center[j]) / scale[j])
+    }
+
+    pub fn inverse_transform(&self, inputs: &Matrix) -> Matrix {
+        check_size(inputs, self.mean.len());
+        map_columns(inputs, |j, z| {
+            let x = if self.with_std { z * self.scale[j] } else { z };
+            if self.with_mean { x + self.mean[j] } else { x }
+        })
+    }
+
+    pub fn fit_transform(&mut self, inputs: &Matrix) -> Matrix {
+        self.fit(inputs);
+        self.transform(inputs)
+    }
+}
+
+impl Default for StandardScaler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Maps each column linearly onto `feature_range` (default `[0, 1]`) using
+/// the minimum and maximum seen by `fit`. Constant columns map to the lower end.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MinMaxScaler {
+    pub feature_range: (f64, f64),
+    pub data_min: Vector, // Column minima, set by fit
+    pub data_max: Vector, // Column maxima, set by fit
+}
+
+impl MinMaxScaler {
+    pub fn new() -> Self {
+        Self { feature_range: (0.0, 1.0), data_min: Vector::zeros(0), data_max: Vector::zeros(0) }
+    }
+
+    pub fn with_feature_range(mut self, low: f64, high: f64) -> Self {
+        assert!(low < high, "The feature range must be increasing");
+        self.feature_range = (low, high);
+        self
+    }
+
+    pub fn fit(&mut self, inputs: &Matrix) {
+        assert!(inputs.row_count() > 0, "Cannot fit on an empty dataset");
+        let columns = inputs.transpose();
+        self.data_min = Vector::new(columns.rows.iter().map(|c| c.iter().copied().fold(f64::INFINITY, f64::min)).collect());
+        self.data_max = Vector::new(columns.rows.iter().map(|c| c.iter().copied().fold(f64::NEG_INFINITY, f64::max)).collect());
+    }
+
+    /// Per column factor from the data range to the feature range.
+    fn scale(&self, j: usize) -> f64 {
+        (self.feature_range.1 - self.feature_range.0) / non_zero(self.data_max[j] - self.data_min[j])
+    }
+
+    pub fn transform(&self, inputs: &Matrix) -> Matrix {
+        check_size(inputs, self.data_min.len());
+        map_columns(inputs, |j, x| self.feature_range.0 + (x - self.data_min[j]) * self.scale(j))
+    }
+
+    pub fn inverse_transform(&self, inputs: &Matrix) -> Matrix {
+        check_size(inputs, self.data_min.len());
+        map_columns(inputs, |j, z| self.data_min[j] + (z - self.feature_range.0) / self.scale(j))
+    }
+
+    pub fn fit_transform(&mut self, inputs: &Matrix) -> Matrix {
+        self.fit(inputs);
+        self.transform(inputs)
+    }
+}
+
+impl Default for MinMaxScaler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Centers each column on its median and divides by an interquantile range
+/// (by default the 25th to 75th percentile), which keeps outliers from
+/// dominating the statistics. Quantiles interpolate linearly between order
+/// statistics. Columns with a zero range are only centered.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RobustScaler {
+    pub with_centering: bool,
+    pub with_scaling: bool,
+    pub quantile_range: (f64, f64), // Percentiles in [0, 100]
+    pub center: Vector,             // Column medians, set by fit
+    pub scale: Vector,              // Column interquantile ranges, set by fit
+}
+
+impl RobustScaler {
+    pub fn new() -> Self {
+        Self { with_centering: true, with_scaling: true, quantile_range: (25.0, 75.0), center: Vector::zeros(0), scale: Vector::zeros(0) }
+    }
+
+    pub fn with_centering(mut self, with_centering: bool) -> Self {
+        self.with_centering = with_centering;
+        self
+    }
+
+    pub fn with_scaling(mut self, with_scaling: bool) -> Self {
+        self.with_scaling = with_scaling;
+        self
+    }
+
+    pub fn with_quantile_range(mut self, low: f64, high: f64) -> Self {
+        assert!(0.0 <= low && low < high && high <= 100.0, "The quantile range must be increasing within [0, 100]");
+        self.quantile_range = (low, high);
+        self
+    }
+
+    pub fn fit(&mut self, inputs: &Matrix) {
+        assert!(inputs.row_count() > 0, "Cannot fit on an empty dataset");
+        let (low, high) = self.quantile_range;
+        let mut centers = Vec::with_capacity(inputs.col_count());
+        let mut scales = Vec::with_capacity(inputs.col_count());
+        for column in inputs.transpose().rows {
+            let mut sorted = column.data;
+            sorted.sort_by(f64::total_cmp);
+            centers.push(quantile(&sorted, 0.5));
+            scales.push(non_zero(quantile(&sorted, high / 100.0) - quantile(&sorted, low / 100.0)));
+        }
+        self.center = Vector::new(centers);
+        self.scale = Vector::new(scales);
+    }
+
+    pub fn transform(&self, inputs: &Matrix) -> Matrix {
+        check_size(inputs, self.center.len());
+        map_columns(inputs, |j, x| {
+            let centered = if self.with_centering { x - self.center[j] } else { x };
+            if self.with_scaling { centered / self.scale[j] } else { centered }
+        })
+    }
+
+    pub fn inverse_transform(&self, inputs: &Matrix) -> Matrix {
+        check_size(inputs, self.center.len());
+        map_columns(inputs, |j, z| {
+            let x = if self.with_scaling { z * self.scale[j] } else { z };
+            if self.with_centering { x + self.center[j] } else { x }
+        })
+    }
+
+    pub fn fit_transform(&mut self, inputs: &Matrix) -> Matrix {
+        self.fit(inputs);
+        self.transform(inputs)
+    }
+}
+
+impl Default for RobustScaler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Norm {
+    /// Sum of absolute values
+    L1,
+    /// Euclidean length
+    L2,
+    /// Largest absolute value
+    Max,
+}
+
+/// Scales each row (not column) to unit norm; rows of zeros are left
+/// unchanged. Stateless, so there is nothing to fit and no inverse.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Normalizer {
+    pub norm: Norm,
+}
+
+impl Normalizer {
+    pub fn new(norm: Norm) -> Self {
+        Self { norm }
+    }
+
+    pub fn transform(&self, inputs: &Matrix) -> Matrix {
+        let rows = inputs.rows.iter().map(|row| {
+            let norm = match self.norm {
+                Norm::L1 => row.iter().map(|x| x.abs()).sum(),
+                Norm::L2 => row.norm(),
+                Norm::Max => row.iter().fold(0.0f64, |m, x| m.max(x.abs())),
+            };
+            row.scale(1.0 / non_zero(norm))
+        });
+        Matrix { rows: rows.collect(), cols: inputs.col_count() }
+    }
+}
+
+/// Linearly interpolated quantile `q ∈ [0, 1]` of sorted values.
+pub(crate) fn quantile(sorted: &[f64], q: f64) -> f64 {
+    let position = q * (sorted.len() - 1) as f64;
+    let (below, above) = (position.floor() as usize, position.ceil() as usize);
+    sorted[below] + (position - below as f64) * (sorted[above] - sorted[below])
+}
+
+/// Replaces a zero scale by 1 so that constant columns pass through.
+fn non_zero(scale: f64) -> f64 {
+    if scale == 0.0 { 1.0 } else { scale }
+}
+
+/// Applies `f(column, value)` to every entry.
+fn map_columns(inputs: &Matrix, f: impl Fn(usize, f64) -> f64) -> Matrix {
+    let rows = inputs.rows.iter().map(|row| Vector::new(row.iter().enumerate().map(|(j, &x)| f(j, x)).collect()));
+    Matrix { rows: rows.collect(), cols: inputs.col_count() }
+}
@@ -0,0 +1,105 @@
+use crate::math::{Matrix, Vector};
+use crate::preprocessing::{check_size, input_names_or_default};
+use serde::{Deserialize, Serialize};
+
+/// Replaces each column with a B-spline basis on uniformly spaced knots.
+///
+/// `n_knots` knots span the range of the column seen by `fit`; the knot
+/// vector is extended by `degree` equally spaced knots on both sides so that
+/// every column yields `n_knots + degree - 1` basis functions which sum to one
+/// inside the range. Values outside the fitted range are clamped to it.
+/// Without `include_bias` the last basis function of each column is dropped,
+/// which removes the collinearity with a model's own intercept.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SplineFeatures {
+    pub n_knots: usize,
+    pub degree: usize,
+    pub include_bias: bool,
+    knots: Vec<Vec<f64>>, // Extended knot vector per column, set by fit
+}
+
+impl SplineFeatures {
+    /// Creates an unfitted transformer; cubic splines use `degree = 3`.
+    pub fn new(n_knots: usize, degree: usize) -> Self {
+        assert!(n_knots >= 2, "Splines need at least two knots");
+        Self { n_knots, degree, include_bias: true, knots: Vec::new() }
+    }
+
+    pub fn with_include_bias(mut self, include_bias: bool) -> Self {
+        self.include_bias = include_bias;
+        self
+    }
+
+    /// Places the knots on the range of each column. A constant column gets
+    /// knots on a unit interval around its value.
+    pub fn fit(&mut self, inputs: &Matrix) {
+        assert!(inputs.row_count() > 0, "Cannot fit on an empty dataset");
+        self.knots = (0..inputs.col_count())
+            .map(|j| {
+                let (mut min, mut max) = inputs.rows.iter().fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), row| (lo.min(row[j]), hi.max(row[j])));
+                if max == min {
+                    (min, max) = (min - 0.5, max + 0.5);
+                }
+                let step = (max - min) / (self.n_knots - 1) as f64;
+                let degree = self.degree as i64;
+                (-degree..self.n_knots as i64 + degree).map(|k| min + k as f64 * step).collect()
+            })
+            .collect();
+    }
+
+    /// Basis functions per column.
+    pub fn basis_size(&self) -> usize {
+        self.n_knots + self.degree - if self.include_bias { 1 } else { 2 }
+    }
+
+    pub fn output_size(&self) -> usize {
+        self.knots.len() * self.basis_size()
+    }
+
+    pub fn transform(&self, inputs: &Matrix) -> Matrix {
+        check_size(inputs, self.knots.len());
+        let rows = inputs.rows.iter().map(|row| {
+            let mut features = Vec::with_capacity(self.output_size());
+            for (j, knots) in self.knots.iter().enumerate() {
+                features.extend(self.basis(knots, row[j]).into_iter().take(self.basis_size()));
+            }
+            Vector::new(features)
+        });
+        Matrix { rows: rows.collect(), cols: self.output_size() }
+    }
+
+    pub fn fit_transform(&mut self, inputs: &Matrix) -> Matrix {
+        self.fit(inputs);
+        self.transform(inputs)
+    }
+
+    /// Names of the output columns such as `"x0_sp2"`, built from
+    /// `input_names` (default `x0`, `x1`, ...).
+    pub fn feature_names(&self, input_names: Option<&[String]>) -> Vec<String> {
+        input_names_or_default(input_names, self.knots.len())
+            .iter()
+            .flat_map(|name| (0..self.basis_size()).map(move |k| format!("{}_sp{}", name, k)))
+            .collect()
+    }
+
+    /// All `n_knots + degree - 1` B-splines of the column at `x`, by the
+    /// Cox-de Boor recursion.
+    fn basis(&self, knots: &[f64], x: f64) -> Vec<f64> {
+        let (min, max) = (knots[self.degree], knots[self.degree + self.n_knots - 1]);
+        let x = x.clamp(min, max);
+        // Degree 0: the indicator of the knot span holding x, with the last span closed
+        let step = knots[1] - knots[0];
+        let span = (((x - min) / step) as usize).min(self.n_knots - 2) + self.degree;
+        let mut values = vec![0.0; knots.len() - 1];
+        values[span] = 1.0;
+        for k in 1..=self.degree {
+            for i in 0..knots.len() - 1 - k {
+                let left = (x - knots[i]) / (knots[i + k] - knots[i]) * values[i];
+                let right = (knots[i + k + 1] - x) / (knots[i + k + 1] - knots[i + 1]) * values[i + 1];
+                values[i] = left + right;
+            }
+            values.pop();
+        }
+        values
+    }
+}
@@ -0,0 +1,3 @@
+#[allow(clippy::module_inception)]
+pub mod rbm;
+pub use rbm::{Training, VisibleUnits, RBM};
@@ -0,0 +1,261 @@
+use crate::math::{Matrix, Vector};
+use crate::neuralnetwork::{Dense, Layer, NeuralNetwork};
+use crate::utils::activation::sigmoid;
+use crate::utils::random::{normal, standard_normal};
+use rand::Rng;
+use rand::seq::SliceRandom;
+
+/// Distribution of the visible units. Hidden units are always Bernoulli.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VisibleUnits {
+    /// Binary units in {0, 1} (or probabilities in [0, 1] as data).
+    Bernoulli,
+    /// Real-valued units with unit variance; standardize the data first.
+    Gaussian,
+}
+
+/// How the negative phase of the gradient is sampled.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Training {
+    /// CD-k: Gibbs chains start at the data and run `k` steps.
+    ContrastiveDivergence { k: usize },
+    /// Persistent CD: a fixed set of chains, one per sample of the first
+    /// batch, continues from where the previous batch left it.
+    PersistentContrastiveDivergence { k: usize },
+}
+
+/// Restricted Boltzmann Machine with Bernoulli hidden units.
+///
+/// Energy (Bernoulli visible): `E(v, h) = -bᵀv - cᵀh - hᵀWv`;
+/// (Gaussian visible): `E(v, h) = ½‖v - b‖² - cᵀh - hᵀWv`.
+pub struct RBM {
+    pub weights: Matrix,         // Dimensions: n_hidden x n_visible
+    pub visible_bias: Vector,    // b
+    pub hidden_bias: Vector,     // c
+    pub visible: VisibleUnits,   // Distribution of the visible units
+    chains: Option<Vec<Vector>>, // Persistent chains for PCD
+}
+
+impl RBM {
+    /// Creates an RBM with weights drawn from N(0, 0.01²) and zero biases.
+    pub fn new(n_visible: usize, n_hidden: usize, visible: VisibleUnits) -> Self {
+        let mut rng = rand::rng();
+        Self {
+            weights: Matrix::new(
+                (0..n_hidden).map(|_| (0..n_visible).map(|_| normal(&mut rng, 0.0, 0.01)).collect()).collect(),
+            ),
+            visible_bias: Vector::zeros(n_visible),
+            hidden_bias: Vector::zeros(n_hidden),
+            visible,
+            chains: None,
+        }
+    }
+
+    pub fn n_visible(&self) -> usize {
+        self.visible_bias.len()
+    }
+
+    pub fn n_hidden(&self) -> usize {
+        self.hidden_bias.len()
+    }
+
+    /// `p(h_j = 1 | v) = σ(c_j + W_j v)`
+    pub fn hidden_probabilities(&self, visible: &Vector) -> Vector {
+        let mut activation = self.weights.gemv(visible);
+        activation.add_assign(&self.hidden_bias, 1.0);
+        Vector::new(activation.iter().map(|&a| sigmoid(a)).collect())
+    }
+
+    /// Mean of the visible units given the hidden state: `σ(b + Wᵀh)` for
+    /// Bernoulli units, `b + Wᵀh` for Gaussian units.
+    pub fn visible_mean(&self, hidden: &Vector) -> Vector {
+        let mut activation = self.weights.transpose().gemv(hidden);
+        activation.add_assign(&self.visible_bias, 1.0);
+        match self.visible {
+            VisibleUnits::Bernoulli => Vector::new(activation.iter().map(|&a| sigmoid(a)).collect()),
+            VisibleUnits::Gaussian => activation,
+        }
+    }
+
+    pub fn sample_hidden<R: Rng + ?Sized>(&self, visible: &Vector, rng: &mut R) -> Vector {
+        let p = self.hidden_probabilities(visible);
+        Vector::new(p.iter().map(|&p| if rng.random::<f64>() < p { 1.0 } else { 0.0 }).collect())
+    }
+
+    pub fn sample_visible<R: Rng + ?Sized>(&self, hidden: &Vector, rng: &mut R) -> Vector {
+        let mean = self.visible_mean(hidden);
+        match self.visible {
+            VisibleUnits::Bernoulli => {
+                Vector::new(mean.iter().map(|&p| if rng.random::<f64>() < p { 1.0 } else { 0.0 }).collect())
+            }
+            VisibleUnits::Gaussian => Vector::new(mean.iter().map(|&m| m + standard_normal(rng)).collect()),
+        }
+    }
+
+    /// Hidden activations `p(h | v)` for each input, e.g. as features for
+    /// a downstream model.
+    pub fn transform(&self, inputs: &[Vector]) -> Vec<Vector> {
+        inputs.iter().map(|v| self.hidden_probabilities(v)).collect()
+    }
+
+    /// Runs `steps` rounds of block Gibbs sampling v -> h -> v starting from
+    /// `visible` and returns the final visible sample.
+    pub fn gibbs_sample(&self, visible: &Vector, steps: usize) -> Vector {
+        self.gibbs_sample_with_rng(visible, steps, &mut rand::rng())
+    }
+
+    pub fn gibbs_sample_with_rng<R: Rng + ?Sized>(&self, visible: &Vector, steps: usize, rng: &mut R) -> Vector {
+        let mut v = visible.clone();
+        for _ in 0..steps {
+            let h = self.sample_hidden(&v, rng);
+            v = self.sample_visible(&h, rng);
+        }
+        v
+    }
+
+    /// Mean squared difference between each input and its mean-field
+    /// reconstruction `E[v | p(h | v)]`.
+    pub fn reconstruction_error(&self, inputs: &[Vector]) -> f64 {
+        let total: f64 = inputs
+            .iter()
+            .map(|v| {
+                let reconstruction = self.visible_mean(&self.hidden_probabilities(v));
+                v.iter().zip(reconstruction.iter()).map(|(a, b)| (a - b).powi(2)).sum::<f64>()
+            })
+            .sum();
+        total / (inputs.len() * self.n_visible()) as f64
+    }
+
+    /// `F(v) = -log Σ_h exp(-E(v, h))`; lower for more probable inputs.
+    pub fn free_energy(&self, visible: &Vector) -> f64 {
+        let mut activation = self.weights.gemv(visible);
+        activation.add_assign(&self.hidden_bias, 1.0);
+        // softplus(x) = log(1 + e^x), computed stably
+        let softplus: f64 = activation.iter().map(|&x| x.max(0.0) + (-x.abs()).exp().ln_1p()).sum();
+        let visible_term = match self.visible {
+            VisibleUnits::Bernoulli => -self.visible_bias.dot(visible),
+            VisibleUnits::Gaussian => {
+                visible.iter().zip(self.visible_bias.iter()).map(|(v, b)| 0.5 * (v - b).powi(2)).sum()
+            }
+        };
+        visible_term - softplus
+    }
+
+    /// Trains the RBM with mini-batch CD-k or PCD-k.
+    /// Returns the reconstruction error after each epoch.
+    pub fn fit(&mut self, inputs: &[Vector], training: Training, learning_rate: f64, epochs: usize, batch_size: usize) -> Vec<f64> {
+        self.fit_with_rng(inputs, training, learning_rate, epochs, batch_size, &mut rand::rng())
+    }
+
+    pub fn fit_with_rng<R: Rng + ?Sized>(
+        &mut self,
+        inputs: &[Vector],
+        training: Training,
+        learning_rate: f64,
+        epochs: usize,
+        batch_size: usize,
+        rng: &mut R,
+    ) -> Vec<f64> {
+        assert!(!inputs.is_empty(), "Training data must not be empty");
+        assert!(batch_size > 0, "Batch size must be positive");
+        assert!(inputs.iter().all(|v| v.len() == self.n_visible()), "Inputs must have n_visible features");
+
+        let mut history = Vec::with_capacity(epochs);
+        let mut indices: Vec<usize> = (0..inputs.len()).collect();
+        for _ in 0..epochs {
+            indices.shuffle(rng);
+            for batch in indices.chunks(batch_size) {
+                let batch: Vec<&Vector> = batch.iter().map(|&i| &inputs[i]).collect();
+                self.train_batch(&batch, training, learning_rate, rng);
+            }
+            history.push(self.reconstruction_error(inputs));
+        }
+        history
+    }
+
+    /// Persistent chains of PCD training, one per sample of the first batch;
+    /// `None` before PCD training starts.
+    pub fn chains(&self) -> Option<&[Vector]> {
+        self.chains.as_deref()
+    }
+
+    fn train_batch<R: Rng + ?Sized>(&mut self, batch: &[&Vector], training: Training, learning_rate: f64, rng: &mut R) {
+        // CD starts one chain per sample at the data; PCD keeps a fixed set of
+        // chains, created from the first batch, and advances each in place.
+        let (k, mut chains, persistent) = match training {
+            Training::ContrastiveDivergence { k } => (k, batch.iter().map(|&v| v.clone()).collect(), false),
+            Training::PersistentContrastiveDivergence { k } => {
+                let chains = self.chains.take().unwrap_or_else(|| batch.iter().map(|&v| v.clone()).collect());
+                (k, chains, true)
+            }
+        };
+        assert!(k > 0, "Gibbs chains need at least one step");
+
+        // Positive phase
+        let mut positive_w = Matrix::zeros(self.n_hidden(), self.n_visible());
+        let mut positive_b = Vector::zeros(self.n_visible());
+        let mut positive_c = Vector::zeros(self.n_hidden());
+        for &v0 in batch {
+            let h0 = self.hidden_probabilities(v0);
+            positive_w.add_assign(&h0.outer_product(v0));
+            positive_b.add_assign(v0, 1.0);
+            positive_c.add_assign(&h0, 1.0);
+        }
+
+        // Negative phase
+        let mut negative_w = Matrix::zeros(self.n_hidden(), self.n_visible());
+        let mut negative_b = Vector::zeros(self.n_visible());
+        let mut negative_c = Vector::zeros(self.n_hidden());
+        for v in chains.iter_mut() {
+            for step in 0..k {
+                let h = self.sample_hidden(v, rng);
+                *v = if step + 1 == k && !persistent {
+                    self.visible_mean(&h) // Mean-field reconstruction reduces sampling noise
+                } else {
+                    self.sample_visible(&h, rng)
+                };
+            }
+            let hk = self.hidden_probabilities(v);
+            negative_w.add_assign(&hk.outer_product(v));
+            negative_b.add_assign(v, 1.0);
+            negative_c.add_assign(&hk, 1.0);
+        }
+
+        let (n, m) = (batch.len() as f64, chains.len() as f64);
+        positive_w.scale(learning_rate / n);
+        negative_w.scale(-learning_rate / m);
+        self.weights.add_assign(&positive_w);
+        self.weights.add_assign(&negative_w);
+        self.visible_bias.add_assign(&positive_b, learning_rate / n);
+        self.visible_bias.add_assign(&negative_b, -learning_rate / m);
+        self.hidden_bias.add_assign(&positive_c, learning_rate / n);
+        self.hidden_bias.add_assign(&negative_c, -learning_rate / m);
+        if persistent {
+            self.chains = Some(chains);
+        }
+    }
+
+    /// A `Dense` layer computing the hidden pre-activations `c + Wv`; follow it
+    /// with a `Sigmoid` layer to reproduce `transform` in a `Sequential` model.
+    pub fn to_dense(&self) -> Dense {
+        let mut dense = Dense::new(self.n_visible(), self.n_hidden());
+        dense.set_parameters(&[self.weights.transpose(), Matrix::from_vector(vec![self.hidden_bias.clone()])]);
+        dense
+    }
+
+    /// Copies the weights and hidden biases into layer `layer` of a sigmoid
+    /// `NeuralNetwork` (greedy layer-wise pretraining).
+    pub fn initialize_layer(&self, network: &mut NeuralNetwork, layer: usize) {
+        let target = &mut network.layers[layer].weights;
+        assert!(
+            target.row_count() == self.n_hidden() && target.cols == self.n_visible() + 1,
+            "Layer shape does not match the RBM"
+        );
+        for j in 0..self.n_hidden() {
+            target[(j, 0)] = self.hidden_bias[j];
+            for i in 0..self.n_visible() {
+                target[(j, i + 1)] = self.weights[(j, i)];
+            }
+        }
+    }
+}
@@ -43,11 +43,6 @@ impl KernelSVM {
             // println!("Transformed Inputs: \n {:?}", transformed_inputs);
             let n = transformed_inputs.len();
             let mut q = Matrix::zeros(n, n);
-            let p = Vector::new(vec![-1.0; n]);
-            let a = Matrix::from_vector(vec![targets.clone()]);
-            let b = Vector::new(vec![0.0]);
-            let l = Vector::new(vec![0.0; n]);
-            let u = Vector::new(vec![self.c; n]);
             
             // Construct Q matrix: Q[i,j] = y_i * y_j * K(x_i, x_j), with K approximated by dot product
             for i in 0..n {
@@ -57,7 +52,7 @@ impl KernelSVM {
             }
             println!("Q[0,0]: {}", q[(0,0)]);
             println!("Q[0,1]: {}", q[(0,1)]);
-            let mut qp_solver = QPSolver::new(q, p, a, b, l, u, targets.clone());
+            let mut qp_solver = QPSolver::svm_dual(q, targets.clone(), self.c);
             self.alpha = qp_solver.solve(1000, 1e-5);
             
             // Identify support vectors (where α > threshold) and store them along with corresponding targets and alphas
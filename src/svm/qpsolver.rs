@@ -1,4 +1,5 @@
 use crate::math::{Matrix, Vector};
+use crate::optimize::{QPSettings, QPSolution, QuadraticProgram};
 
 pub struct QPSolver {
     pub q: Matrix,       // Q matrix: y_i * y_j * K(x_i, x_j)
@@ -8,14 +9,54 @@ pub struct QPSolver {
     pub l: Vector,       // Lower bounds for alpha
     pub u: Vector,       // Upper bounds for alpha
     pub y: Vector,       // Actual target labels (+1/-1)
+    smo: bool,           // Whether the problem is the SVM dual, solved with SMO
 }
 
 impl QPSolver {
+    /// A general problem with constraints `Aα = b`, solved with ADMM.
     pub fn new(q: Matrix, p: Vector, a: Matrix, b: Vector, l: Vector, u: Vector, y: Vector) -> Self {
-        Self { q, p, a, b, l, u, y }
+        Self { q, p, a, b, l, u, y, smo: false }
     }
 
+    /// The SVM dual `min ½ αᵀQα - Σα` subject to `yᵀα = 0` and `0 ≤ α ≤ c`,
+    /// solved with SMO.
+    pub fn svm_dual(q: Matrix, y: Vector, c: f64) -> Self {
+        assert!(y.iter().all(|&y| y == 1.0 || y == -1.0), "SVM labels must be +1 or -1");
+        let n = y.len();
+        Self {
+            q,
+            p: Vector::new(vec![-1.0; n]),
+            a: Matrix::from_vector(vec![y.clone()]),
+            b: Vector::new(vec![0.0]),
+            l: Vector::zeros(n),
+            u: Vector::new(vec![c; n]),
+            y,
+            smo: true,
+        }
+    }
+
+    /// The problem as a general `QuadraticProgram`:
+    /// `min ½ αᵀQα + pᵀα` subject to `Aα = b` and `l ≤ α ≤ u`.
+    pub fn program(&self) -> QuadraticProgram {
+        QuadraticProgram::new(self.q.clone(), self.p.clone())
+            .with_equality(self.a.clone(), self.b.clone())
+            .with_bounds(self.l.clone(), self.u.clone())
+    }
+
+    /// Solves the general problem, honoring `a` and `b`, and returns the
+    /// solution with its feasibility/optimality report.
+    pub fn solve_with_report(&self, settings: &QPSettings) -> QPSolution {
+        self.program().solve(settings)
+    }
+
+    /// Solves the problem. A solver built with `svm_dual` uses SMO; one built
+    /// with `new` is handed to the general ADMM solver with `tolerance` as its
+    /// absolute and relative tolerance.
     pub fn solve(&mut self, max_iters: usize, tolerance: f64) -> Vector {
+        if !self.smo {
+            let settings = QPSettings { max_iter: max_iters, eps_abs: tolerance, eps_rel: tolerance, ..QPSettings::default() };
+            return self.solve_with_report(&settings).x;
+        }
         let l = self.l.len();
         let mut alpha = Vector::zeros(l);
         let mut grad = self.p.clone(); // Gradient of the objective function
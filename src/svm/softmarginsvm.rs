@@ -48,11 +48,6 @@ impl SoftMarginSVM {
         assert!(inputs.len() == targets.len(), "Mismatched input and target sizes!");
         let n = inputs.len();
         let mut q = Matrix::zeros(n, n);
-        let p = Vector::new(vec![-1.0; n]);
-        let a = Matrix::from_vector(vec![targets.clone()]);
-        let b = Vector::new(vec![0.0]);
-        let l = Vector::new(vec![0.0; n]);
-        let u = Vector::new(vec![self.c; n]);
         
         for i in 0..n {
             for j in 0..n {
@@ -60,7 +55,7 @@ impl SoftMarginSVM {
             }
         }
         
-        let mut qp_solver = QPSolver::new(q, p, a, b, l, u, targets.clone());
+        let mut qp_solver = QPSolver::svm_dual(q, targets.clone(), self.c);
         self.alpha = qp_solver.solve(1000, 1e-5);
         
         // Compute final weights and bias
@@ -5,4 +5,18 @@ pub fn sigmoid(x: f64) -> f64 {
 
 pub fn sigmoid_derivative(output: f64) -> f64 {
     output * (1.0 - output)
-}
\ No newline at end of file
+}
+
+// ReLU activation and its derivative (using input value)
+pub fn relu(x: f64) -> f64 {
+    x.max(0.0)
+}
+
+pub fn relu_derivative(x: f64) -> f64 {
+    if x > 0.0 { 1.0 } else { 0.0 }
+}
+
+// Tanh derivative (using output value)
+pub fn tanh_derivative(output: f64) -> f64 {
+    1.0 - output * output
+}
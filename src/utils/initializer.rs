@@ -0,0 +1,104 @@
+use crate::math::Matrix;
+use crate::utils::random::normal;
+use rand::Rng;
+
+/// Strategies for initializing a layer's weights or biases.
+///
+/// `fan_in` is the number of inputs to a neuron and `fan_out` the number
+/// of neurons in the layer; the scaled schemes use them to keep the
+/// activation variance roughly constant from layer to layer.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Initializer {
+    /// U(low, high)
+    Uniform { low: f64, high: f64 },
+    /// Xavier/Glorot uniform: U(-a, a) with a = sqrt(6 / (fan_in + fan_out))
+    XavierUniform,
+    /// Xavier/Glorot normal: N(0, 2 / (fan_in + fan_out))
+    XavierNormal,
+    /// He/Kaiming uniform: U(-a, a) with a = sqrt(6 / fan_in)
+    HeUniform,
+    /// He/Kaiming normal: N(0, 2 / fan_in)
+    HeNormal,
+    /// LeCun uniform: U(-a, a) with a = sqrt(3 / fan_in)
+    LeCunUniform,
+    /// LeCun normal: N(0, 1 / fan_in)
+    LeCunNormal,
+    /// A (semi-)orthogonal matrix scaled by `gain`
+    Orthogonal { gain: f64 },
+    /// All zeros (the usual choice for biases)
+    Zeros,
+    /// Every entry set to the given value
+    Constant(f64),
+}
+
+impl Initializer {
+    /// Creates a `fan_out x fan_in` matrix drawn from this distribution.
+    pub fn initialize<R: Rng + ?Sized>(&self, fan_in: usize, fan_out: usize, rng: &mut R) -> Matrix {
+        let fan_in_f = fan_in.max(1) as f64;
+        let fan_out_f = fan_out.max(1) as f64;
+        match *self {
+            Initializer::Uniform { low, high } => {
+                assert!(low < high, "Uniform initializer needs low < high, got [{}, {})", low, high);
+                Self::uniform(fan_in, fan_out, low, high, rng)
+            }
+            Initializer::XavierUniform => {
+                let a = (6.0 / (fan_in_f + fan_out_f)).sqrt();
+                Self::uniform(fan_in, fan_out, -a, a, rng)
+            }
+            Initializer::XavierNormal => Self::normal(fan_in, fan_out, (2.0 / (fan_in_f + fan_out_f)).sqrt(), rng),
+            Initializer::HeUniform => {
+                let a = (6.0 / fan_in_f).sqrt();
+                Self::uniform(fan_in, fan_out, -a, a, rng)
+            }
+            Initializer::HeNormal => Self::normal(fan_in, fan_out, (2.0 / fan_in_f).sqrt(), rng),
+            Initializer::LeCunUniform => {
+                let a = (3.0 / fan_in_f).sqrt();
+                Self::uniform(fan_in, fan_out, -a, a, rng)
+            }
+            Initializer::LeCunNormal => Self::normal(fan_in, fan_out, (1.0 / fan_in_f).sqrt(), rng),
+            Initializer::Orthogonal { gain } => Self::orthogonal(fan_in, fan_out, gain, rng),
+            Initializer::Zeros => Matrix::zeros(fan_out, fan_in),
+            Initializer::Constant(value) => Matrix::new(vec![vec![value; fan_in]; fan_out]),
+        }
+    }
+
+    /// Creates the biases of `size` neurons as a `1 x size` row. A bias has a
+    /// single constant input, so the scaled schemes use `fan_in = 1` and
+    /// `fan_out = size` for every layer type.
+    pub fn initialize_bias<R: Rng + ?Sized>(&self, size: usize, rng: &mut R) -> Matrix {
+        self.initialize(1, size, rng).transpose()
+    }
+
+    fn uniform<R: Rng + ?Sized>(fan_in: usize, fan_out: usize, low: f64, high: f64, rng: &mut R) -> Matrix {
+        Matrix::new(
+            (0..fan_out)
+                .map(|_| (0..fan_in).map(|_| rng.random_range(low..high)).collect())
+                .collect(),
+        )
+    }
+
+    fn normal<R: Rng + ?Sized>(fan_in: usize, fan_out: usize, std_dev: f64, rng: &mut R) -> Matrix {
+        Matrix::new(
+            (0..fan_out)
+                .map(|_| (0..fan_in).map(|_| normal(rng, 0.0, std_dev)).collect())
+                .collect(),
+        )
+    }
+
+    /// Orthogonalizes a Gaussian matrix with Gram–Schmidt. The result has
+    /// orthonormal columns when `fan_out >= fan_in` and orthonormal rows otherwise.
+    fn orthogonal<R: Rng + ?Sized>(fan_in: usize, fan_out: usize, gain: f64, rng: &mut R) -> Matrix {
+        let tall = fan_out >= fan_in;
+        let (rows, cols) = if tall { (fan_out, fan_in) } else { (fan_in, fan_out) };
+        let gaussian = Self::normal(cols, rows, 1.0, rng);
+        let (mut q, r) = gaussian.gram_schmidt();
+        // Fix the signs so the result is uniformly distributed over orthogonal matrices
+        for j in 0..cols {
+            if r[(j, j)] < 0.0 {
+                q.scale_column(j, -1.0);
+            }
+        }
+        q.scale(gain);
+        if tall { q } else { q.transpose() }
+    }
+}
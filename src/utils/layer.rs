@@ -1,4 +1,5 @@
 use crate::math::{Matrix, Vector};
+use crate::utils::initializer::Initializer;
 use rand::Rng;
 /// A single neural network layer.
 /// It holds a weight matrix of dimensions (num_neurons x (input_dim + 1)),
@@ -9,8 +10,8 @@ pub struct Layer {
 
 impl Layer {
     /// Creates a new layer with the given input and output sizes.
-    /// For simplicity, weights are initialized to zeros.
-    /// (In practice you’d want to initialize randomly.)
+    /// All weights, including the bias, are drawn from U(-1, 1);
+    /// use `with_initializer` for fan-in aware schemes.
     pub fn new(input_dim: usize, output_dim: usize) -> Self {
         let mut rng = rand::rng();
         let weights = Matrix::new(
@@ -25,6 +26,29 @@ impl Layer {
         Self { weights }
     }
 
+    /// Creates a new layer whose weights and biases are drawn from the given
+    /// initializers using `rng`, so a seeded RNG yields reproducible layers.
+    pub fn with_initializer<R: Rng + ?Sized>(
+        input_dim: usize,
+        output_dim: usize,
+        weights: &Initializer,
+        biases: &Initializer,
+        rng: &mut R,
+    ) -> Self {
+        let bias_row = biases.initialize_bias(output_dim, rng);
+        let weight_block = weights.initialize(input_dim, output_dim, rng);
+        let weights = Matrix::new(
+            (0..output_dim)
+                .map(|i| {
+                    let mut row = vec![bias_row[(0, i)]];
+                    row.extend_from_slice(&weight_block[i].data);
+                    row
+                })
+                .collect(),
+        );
+        Self { weights }
+    }
+
     /// Helper function: Extend a vector with a bias term (always 1.0)
     pub fn extend_with_bias(input: &Vector) -> Vector {
         let mut extended = vec![1.0];
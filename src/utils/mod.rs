@@ -1,5 +1,7 @@
 pub mod activation;
+pub mod initializer;
 pub mod layer;
+pub mod random;
 pub mod rff;
 
-pub use rff::RandomFourierFeatures;
\ No newline at end of file
+pub use rff::RandomFourierFeatures;
@@ -0,0 +1,173 @@
+#[cfg(test)]
+mod tests {
+    use rustbrain::gradcheck::check_layer;
+    use rustbrain::math::{Matrix, Vector};
+    use rustbrain::neuralnetwork::{
+        scaled_dot_product_attention, Adam, Dense, Layer, LearnedPositionalEncoding, Loss, Mask, MultiHeadAttention,
+        ReLU, Sequential, SinusoidalEncoding, TimeDistributed, TransformerEncoderBlock,
+    };
+
+    // The key bias has an exactly zero gradient (softmax is shift invariant),
+    // so its numerical estimate is pure round-off
+    const TOLERANCE: f64 = 1e-4;
+
+    /// Two sequences of three steps with four features each.
+    fn sequences() -> Matrix {
+        Matrix::new(vec![
+            vec![0.5, -0.3, 0.8, 0.1, 0.9, -0.7, -0.4, 0.2, 0.6, 0.3, -0.8, 0.5],
+            vec![-0.6, 0.4, 0.2, 0.7, -0.1, 0.3, 0.9, -0.5, -0.2, -0.3, 0.6, 0.8],
+        ])
+    }
+
+    #[test]
+    fn test_scaled_dot_product_attention() {
+        let q = Matrix::new(vec![vec![1.0, 0.0], vec![0.0, 1.0], vec![1.0, 1.0]]);
+        let v = Matrix::new(vec![vec![1.0, 2.0], vec![3.0, 4.0], vec![5.0, 6.0]]);
+
+        let (output, weights) = scaled_dot_product_attention(&q, &q, &v, None);
+        for row in &weights.rows {
+            assert!((row.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+        }
+        // Query 0 scores [1, 0, 1] / √2
+        let e = (1.0 / 2f64.sqrt()).exp();
+        assert!((weights[(0, 1)] - 1.0 / (2.0 * e + 1.0)).abs() < 1e-12);
+        assert!((output[(0, 0)] - (e + 3.0 + 5.0 * e) / (2.0 * e + 1.0)).abs() < 1e-12);
+
+        let (output, weights) = scaled_dot_product_attention(&q, &q, &v, Some(&Mask::Causal));
+        assert_eq!(weights[0].data, vec![1.0, 0.0, 0.0]);
+        assert_eq!(output[0].data, vec![1.0, 2.0]);
+        assert_eq!(weights[(1, 2)], 0.0);
+
+        let nothing = Mask::Custom(vec![vec![false; 3], vec![true; 3], vec![true; 3]]);
+        let (output, _) = scaled_dot_product_attention(&q, &q, &v, Some(&nothing));
+        assert_eq!(output[0].data, vec![0.0, 0.0], "A fully masked query attends to nothing");
+    }
+
+    #[test]
+    fn test_gradcheck_attention() {
+        let mut layers: Vec<Box<dyn Layer>> = vec![
+            Box::new(MultiHeadAttention::new(4, 1)),
+            Box::new(MultiHeadAttention::new(4, 2)),
+            Box::new(MultiHeadAttention::new(4, 2).with_mask(Mask::Causal)),
+        ];
+        for layer in &mut layers {
+            let report = check_layer(layer.as_mut(), &sequences());
+            assert_eq!(report.errors.len(), 9);
+            assert!(report.passed(TOLERANCE), "{}: {:?}", layer.name(), report.errors);
+        }
+
+        let mut attention = MultiHeadAttention::new(4, 2);
+        attention.forward(&sequences(), false);
+        let weights = attention.attention_weights().unwrap();
+        assert_eq!((weights.len(), weights[0].len(), weights[0][0].row_count()), (2, 2, 3));
+    }
+
+    #[test]
+    fn test_causal_layers_ignore_later_steps() {
+        // Changing the last step must leave the outputs of the first two alone
+        let mut changed = sequences();
+        for j in 8..12 {
+            changed[(0, j)] += 1.0;
+        }
+        let mut layers: Vec<Box<dyn Layer>> = vec![
+            Box::new(MultiHeadAttention::new(4, 2).with_mask(Mask::Causal)),
+            Box::new(TransformerEncoderBlock::new(4, 2, 6).with_mask(Mask::Causal)),
+        ];
+        for layer in &mut layers {
+            let before = layer.forward(&sequences(), false);
+            let after = layer.forward(&changed, false);
+            assert_eq!(before[0].data[..8], after[0].data[..8], "{}", layer.name());
+            assert_ne!(before[0].data[8..], after[0].data[8..], "{}", layer.name());
+            assert_eq!(before[1], after[1]);
+        }
+    }
+
+    #[test]
+    fn test_positional_encodings() {
+        let encoding = SinusoidalEncoding::new(4);
+        assert_eq!(encoding.encoding(0).data, vec![0.0, 1.0, 0.0, 1.0]);
+        let p = encoding.encoding(3);
+        assert!((p[0] - 3f64.sin()).abs() < 1e-12);
+        assert!((p[3] - (3.0 / 100.0f64).cos()).abs() < 1e-12);
+
+        let mut sinusoidal = SinusoidalEncoding::new(4);
+        let output = sinusoidal.forward(&sequences(), false);
+        assert!((output[(1, 5)] - (sequences()[(1, 5)] + 1f64.cos())).abs() < 1e-12);
+
+        let mut learned = LearnedPositionalEncoding::new(5, 4);
+        let report = check_layer(&mut learned, &sequences());
+        assert!(report.passed(TOLERANCE), "{:?}", report.errors);
+        assert!(learned.output_size(24).is_err(), "Six steps exceed the five learned positions");
+    }
+
+    #[test]
+    fn test_gradcheck_transformer_block() {
+        let mut layers: Vec<Box<dyn Layer>> = vec![
+            Box::new(TransformerEncoderBlock::new(4, 2, 6)),
+            Box::new(TransformerEncoderBlock::new(4, 1, 3).with_mask(Mask::Causal)),
+            Box::new(TimeDistributed::new(Dense::new(4, 3), 4)),
+        ];
+        for layer in &mut layers {
+            let report = check_layer(layer.as_mut(), &sequences());
+            assert!(report.passed(TOLERANCE), "{}: {:?}", layer.name(), report.errors);
+        }
+    }
+
+    #[test]
+    fn test_sequence_loss_and_shapes() {
+        let model = Sequential::builder(12)
+            .add(TimeDistributed::new(Dense::new(4, 8), 4))
+            .add(SinusoidalEncoding::new(8))
+            .add(TransformerEncoderBlock::new(8, 2, 16))
+            .add(TimeDistributed::new(Dense::new(8, 5), 8))
+            .build()
+            .unwrap();
+        assert_eq!(model.output_size(), 15);
+        let error = Sequential::builder(12).add(MultiHeadAttention::new(8, 2)).build().err().unwrap();
+        assert!(error.contains("multiple of 8"), "{}", error);
+
+        // Per-step softmax: two steps of two classes
+        let output = Matrix::new(vec![vec![0.0, 0.0, 2.0, 0.0]]);
+        let target = Matrix::new(vec![vec![1.0, 0.0, 0.0, 1.0]]);
+        let (loss, grad) = Loss::SequenceSoftmaxCrossEntropy { classes: 2 }.compute(&output, &target);
+        let p = 1.0 / (1.0 + 2f64.exp());
+        assert!((loss - (2f64.ln() - p.ln()) / 2.0).abs() < 1e-12);
+        assert!((grad[(0, 0)] + 0.25).abs() < 1e-12);
+        assert!((grad[(0, 3)] - (p - 1.0) / 2.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_transformer_tags_with_first_token() {
+        // Every position must be tagged with the symbol of the first token,
+        // which only attention can carry to the later positions
+        let one_hot = |s: usize| (0..3).map(|j| if j == s { 1.0 } else { 0.0 }).collect::<Vec<f64>>();
+        let mut inputs = Vec::new();
+        let mut targets = Vec::new();
+        for code in 0..81 {
+            let symbols: Vec<usize> = (0..4).map(|t| code / 3usize.pow(t) % 3).collect();
+            inputs.push(Vector::new(symbols.iter().flat_map(|&s| one_hot(s)).collect()));
+            targets.push(Vector::new((0..4).flat_map(|_| one_hot(symbols[0])).collect()));
+        }
+
+        let mut model = Sequential::builder(12)
+            .add(TimeDistributed::new(Dense::new(3, 8), 3))
+            .add(SinusoidalEncoding::new(8))
+            .add(TransformerEncoderBlock::new(8, 2, 16))
+            .add(TimeDistributed::new(ReLU::new(), 8))
+            .add(TimeDistributed::new(Dense::new(8, 3), 8))
+            .build()
+            .unwrap();
+        let loss = Loss::SequenceSoftmaxCrossEntropy { classes: 3 };
+        model.fit(&inputs, &targets, loss, &mut Adam::new(0.01), 9, 60);
+
+        let mut correct = 0;
+        for (x, t) in inputs.iter().zip(&targets) {
+            let y = model.predict(x);
+            for step in 0..4 {
+                let block = |v: &Vector| (0..3).max_by(|&a, &b| v[step * 3 + a].total_cmp(&v[step * 3 + b])).unwrap();
+                correct += (block(&y) == block(t)) as usize;
+            }
+        }
+        assert!(correct >= 81 * 4 * 95 / 100, "Tagged {} of {} positions", correct, 81 * 4);
+    }
+}
@@ -0,0 +1,118 @@
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+    use rustbrain::autoencoder::{Autoencoder, VAE};
+    use rustbrain::gradcheck::check_closure;
+    use rustbrain::math::Vector;
+    use rustbrain::neuralnetwork::{Initializer, NeuralNetwork};
+
+    fn one_hot(n: usize) -> Vec<Vector> {
+        (0..n).map(|k| Vector::new((0..n).map(|i| if i == k { 1.0 } else { 0.0 }).collect())).collect()
+    }
+
+    /// Six features driven by two latent factors: `[a, a, 1-a, b, b, 1-b]`.
+    fn structured(count: usize, rng: &mut StdRng) -> Vec<Vector> {
+        (0..count)
+            .map(|_| {
+                let (a, b): (f64, f64) = (rng.random(), rng.random());
+                Vector::new(vec![a, a, 1.0 - a, b, b, 1.0 - b])
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_input_gradients() {
+        let mut network = NeuralNetwork::new(&[3, 4, 2]).with_initializer(Initializer::XavierUniform, Initializer::Zeros, 0);
+        let inputs = vec![Vector::new(vec![0.2, -0.5, 0.9]), Vector::new(vec![-0.3, 0.1, 0.4])];
+        // Loss Σ (w · output), so the output gradient is constant
+        let w = [0.7, -1.3];
+        let loss = |outputs: &[Vector]| -> (f64, Vec<Vector>) {
+            let value = outputs.iter().map(|o| o[0] * w[0] + o[1] * w[1]).sum();
+            (value, outputs.iter().map(|_| Vector::new(w.to_vec())).collect())
+        };
+        let (_, _, input_grads) = network.backpropagate_with(&inputs, loss);
+        for (input, analytic) in inputs.iter().zip(&input_grads) {
+            let error = check_closure(|x| loss(std::slice::from_ref(&network.predict(x))).0, input, analytic);
+            assert!(error < 1e-6, "Input gradient error {}", error);
+        }
+    }
+
+    #[test]
+    fn test_autoencoder_shapes() {
+        let autoencoder = Autoencoder::new(6, &[5, 4], 2);
+        assert_eq!(autoencoder.network.layers.len(), 6);
+        assert_eq!(autoencoder.bottleneck_size(), 2);
+        let x = Vector::new(vec![0.1, 0.5, 0.9, 0.3, 0.2, 0.7]);
+        let code = autoencoder.encode(&x);
+        assert_eq!(code.len(), 2);
+        assert_eq!(autoencoder.decode(&code), autoencoder.reconstruct(&x));
+        let mse = autoencoder.reconstruction_error(&x);
+        let output = autoencoder.reconstruct(&x);
+        let expected = x.iter().zip(output.iter()).map(|(a, b)| (a - b).powi(2)).sum::<f64>() / 6.0;
+        assert!((mse - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    #[should_panic(expected = "Network output size must match its input size")]
+    fn test_from_network_checks_sizes() {
+        Autoencoder::from_network(NeuralNetwork::new(&[4, 2, 3]), 0);
+    }
+
+    #[test]
+    fn test_autoencoder_learns_8_3_8_encoding() {
+        let patterns = one_hot(8);
+        let network = NeuralNetwork::new(&[8, 3, 8]).with_initializer(Initializer::XavierUniform, Initializer::Zeros, 1);
+        let mut autoencoder = Autoencoder::from_network(network, 0);
+        let history = autoencoder.fit(&patterns, 2.0, 1, 3000);
+        assert!(history.last().unwrap() < &(0.1 * history[0]));
+        for (k, pattern) in patterns.iter().enumerate() {
+            let output = autoencoder.reconstruct(pattern);
+            let argmax = (0..8).max_by(|&i, &j| output[i].total_cmp(&output[j])).unwrap();
+            assert_eq!(argmax, k, "Pattern {} reconstructed as {:?}", k, output.data);
+        }
+    }
+
+    #[test]
+    fn test_reconstruction_error_flags_anomalies() {
+        let mut rng = StdRng::seed_from_u64(2);
+        let train = structured(200, &mut rng);
+        let network = NeuralNetwork::new(&[6, 2, 6]).with_initializer(Initializer::XavierUniform, Initializer::Zeros, 2);
+        let mut autoencoder = Autoencoder::from_network(network, 0);
+        autoencoder.fit(&train, 1.0, 4, 300);
+
+        let normal = autoencoder.reconstruction_errors(&structured(50, &mut rng));
+        let anomalies: Vec<Vector> =
+            (0..50).map(|_| Vector::new((0..6).map(|_| rng.random::<f64>()).collect())).collect();
+        let scores = autoencoder.reconstruction_errors(&anomalies);
+        let mean = |v: &[f64]| v.iter().sum::<f64>() / v.len() as f64;
+        assert!(mean(&scores) > 3.0 * mean(&normal), "anomalies {} normal {}", mean(&scores), mean(&normal));
+    }
+
+    #[test]
+    fn test_vae_trains_and_samples() {
+        let mut rng = StdRng::seed_from_u64(3);
+        // Four binary prototypes made of pairs of equal bits
+        let prototypes = [[1, 1, 1, 1, 0, 0, 0, 0], [0, 0, 0, 0, 1, 1, 1, 1], [1, 1, 0, 0, 1, 1, 0, 0], [0, 0, 1, 1, 0, 0, 1, 1]];
+        let train: Vec<Vector> =
+            (0..200).map(|i| Vector::new(prototypes[i % 4].iter().map(|&b| b as f64).collect())).collect();
+        let mut vae = VAE::new(8, &[8], 2).with_initializer(Initializer::XavierUniform, Initializer::Zeros, 5);
+        assert_eq!(vae.latent_size(), 2);
+        assert!(vae.kl_divergence(&train[0]) >= 0.0);
+
+        let before: f64 = train.iter().map(|x| vae.reconstruction_error(x)).sum::<f64>() / 200.0;
+        let history = vae.fit(&train, 0.5, 8, 300, &mut rng);
+        let after: f64 = train.iter().map(|x| vae.reconstruction_error(x)).sum::<f64>() / 200.0;
+        assert!(history.last().unwrap().total() < history[0].total());
+        assert!(after < 0.5 * before, "Reconstruction error {} -> {}", before, after);
+
+        // Samples from the prior keep the paired structure of the data
+        for sample in vae.sample(20, &mut rng) {
+            assert_eq!(sample.len(), 8);
+            for pair in sample.data.chunks(2) {
+                assert!((pair[0] - pair[1]).abs() < 0.05, "{:?}", sample.data);
+            }
+        }
+        assert_eq!(vae.sample_latent(&train[0], &mut rng).len(), 2);
+    }
+}
@@ -0,0 +1,162 @@
+#[cfg(test)]
+mod tests {
+    use rustbrain::autograd::{Tape, Var};
+    use rustbrain::math::{Matrix, Vector};
+    use rustbrain::SoftmaxRegression;
+
+    fn assert_matrix_close(a: &Matrix, b: &Matrix, tol: f64) {
+        assert_eq!((a.row_count(), a.col_count()), (b.row_count(), b.col_count()));
+        for i in 0..a.row_count() {
+            for j in 0..a.col_count() {
+                assert!((a[(i, j)] - b[(i, j)]).abs() < tol, "({}, {}): {} vs {}", i, j, a[(i, j)], b[(i, j)]);
+            }
+        }
+    }
+
+    /// Central-difference gradient of `f` at `x`.
+    fn numeric_gradient<F: Fn(&Matrix) -> f64>(f: F, x: &Matrix) -> Matrix {
+        let h = 1e-6;
+        let mut grad = Matrix::zeros(x.row_count(), x.col_count());
+        for i in 0..x.row_count() {
+            for j in 0..x.col_count() {
+                let mut plus = x.clone();
+                plus[(i, j)] += h;
+                let mut minus = x.clone();
+                minus[(i, j)] -= h;
+                grad[(i, j)] = (f(&plus) - f(&minus)) / (2.0 * h);
+            }
+        }
+        grad
+    }
+
+    fn softmax_row(row: &Vector) -> Vec<f64> {
+        let max = row.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        let exp: Vec<f64> = row.iter().map(|z| (z - max).exp()).collect();
+        let sum: f64 = exp.iter().sum();
+        exp.iter().map(|e| e / sum).collect()
+    }
+
+    #[test]
+    fn test_elementwise_and_reductions() {
+        let tape = Tape::new();
+        let a = tape.var(Matrix::new(vec![vec![1.0, 2.0], vec![3.0, 4.0]]));
+        let b = tape.var(Matrix::new(vec![vec![0.5, -1.0], vec![2.0, 0.25]]));
+        // f = sum(a * b + a) => df/da = b + 1, df/db = a
+        let f = (a * b + a).sum();
+        assert_eq!(f.scalar(), 0.5 - 2.0 + 6.0 + 1.0 + 10.0);
+
+        let grads = f.backward();
+        assert_eq!(grads.wrt(a), Matrix::new(vec![vec![1.5, 0.0], vec![3.0, 1.25]]));
+        assert_eq!(grads.wrt(b), a.value());
+    }
+
+    #[test]
+    fn test_broadcasting_gradients() {
+        let tape = Tape::new();
+        let x = tape.var(Matrix::new(vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]]));
+        let row = tape.var(Matrix::new(vec![vec![1.0, 0.0, -1.0]]));
+        let col = tape.var(Matrix::new(vec![vec![2.0], vec![3.0]]));
+        let s = tape.scalar(0.5);
+        let f = ((x + row) * col * s).mean();
+
+        let grads = f.backward();
+        // Gradients are reduced back to each operand's shape
+        assert_eq!(grads.wrt(row).row_count(), 1);
+        assert_eq!(grads.wrt(col).col_count(), 1);
+        assert_matrix_close(&grads.wrt(row), &Matrix::new(vec![vec![5.0 / 12.0; 3]]), 1e-12);
+        assert_matrix_close(&grads.wrt(col), &Matrix::new(vec![vec![0.5], vec![1.25]]), 1e-12);
+        assert!((grads.wrt(s)[(0, 0)] - 2.0 * f.scalar()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_composite_expression_matches_finite_differences() {
+        let w0 = Matrix::new(vec![vec![0.3, -0.2], vec![0.1, 0.4], vec![-0.5, 0.2]]);
+        let x = Matrix::new(vec![vec![1.0, 2.0, -1.0], vec![0.5, -0.3, 0.8]]);
+        fn build<'t>(tape: &'t Tape, w: &Matrix, x: &Matrix) -> (Var<'t>, Var<'t>) {
+            let w = tape.var(w.clone());
+            let x = tape.constant(x.clone());
+            let h = x.matmul(w).tanh();
+            let y = (h.exp() + h.sigmoid().log() + h.relu().powf(2.0)).scale(0.5) / tape.scalar(3.0);
+            let loss = (y - -y.transpose().transpose()).sum();
+            (w, loss)
+        }
+
+        let tape = Tape::new();
+        let (w, loss) = build(&tape, &w0, &x);
+        let analytic = loss.backward().wrt(w);
+        let numeric = numeric_gradient(|w| build(&Tape::new(), w, &x).1.scalar(), &w0);
+        assert_matrix_close(&analytic, &numeric, 1e-6);
+    }
+
+    #[test]
+    fn test_softmax_cross_entropy_gradient() {
+        let logits0 = Matrix::new(vec![vec![1.0, 2.0, 0.5], vec![-1.0, 0.0, 3.0]]);
+        let targets = Matrix::new(vec![vec![0.0, 1.0, 0.0], vec![1.0, 0.0, 0.0]]);
+        let tape = Tape::new();
+        let logits = tape.var(logits0.clone());
+        let loss = -(logits.softmax().log() * tape.constant(targets.clone())).sum_cols().mean();
+
+        // d(CE)/d(logits) = (softmax - targets) / n
+        let probabilities: Vec<Vec<f64>> = logits0
+            .rows
+            .iter()
+            .map(softmax_row)
+            .collect();
+        let expected = Matrix::new(probabilities).sub(&targets).map(|g| g / 2.0);
+        assert_matrix_close(&loss.backward().wrt(logits), &expected, 1e-12);
+    }
+
+    #[test]
+    fn test_train_softmax_regression_without_manual_gradients() {
+        let inputs = Matrix::new(vec![vec![1.0, 1.0, 0.0], vec![1.0, 0.0, 1.0], vec![1.0, 1.0, 1.0], vec![1.0, 0.5, 0.5]]);
+        let targets = Matrix::new(vec![
+            vec![1.0, 0.0, 0.0],
+            vec![0.0, 1.0, 0.0],
+            vec![0.0, 0.0, 1.0],
+            vec![0.0, 1.0, 0.0],
+        ]);
+        let mut weights = Matrix::zeros(3, 3);
+        for _ in 0..2000 {
+            let tape = Tape::new();
+            let w: Var = tape.var(weights.clone());
+            let loss = -(tape.constant(inputs.clone()).matmul(w).softmax().log() * tape.constant(targets.clone()))
+                .sum_cols()
+                .mean();
+            let mut step = loss.backward().wrt(w);
+            step.scale(-0.5);
+            weights.add_assign(&step);
+        }
+
+        // Same parameterization as SoftmaxRegression: one row per class, bias first
+        let mut model = SoftmaxRegression::new(2, 3);
+        model.weights = weights.transpose();
+        assert_eq!(model.predict(&Vector::new(vec![1.0, 0.0])), 0);
+        assert_eq!(model.predict(&Vector::new(vec![0.0, 1.0])), 1);
+        assert_eq!(model.predict(&Vector::new(vec![1.0, 1.0])), 2);
+    }
+
+    #[test]
+    fn test_axis_sums_and_unused_variables() {
+        let tape = Tape::new();
+        let x = tape.var(Matrix::new(vec![vec![1.0, 2.0], vec![3.0, 4.0], vec![5.0, 6.0]]));
+        let unused = tape.var(Matrix::zeros(2, 2));
+        let weights = tape.constant(Matrix::new(vec![vec![1.0, 10.0]]));
+        // sum_rows gives column totals and sum_cols row totals
+        assert_eq!(x.sum_rows().value(), Matrix::new(vec![vec![9.0, 12.0]]));
+        assert_eq!(x.sum_cols().shape(), (3, 1));
+        let f = (x.sum_rows() * weights).sum() + x.powf(2.0).sum_cols().sum();
+
+        let grads = f.backward();
+        assert_eq!(grads.wrt(x), Matrix::new(vec![vec![3.0, 14.0], vec![7.0, 18.0], vec![11.0, 22.0]]));
+        assert_eq!(grads.wrt(unused), Matrix::zeros(2, 2));
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot be broadcast together")]
+    fn test_incompatible_shapes_panic() {
+        let tape = Tape::new();
+        let a = tape.var(Matrix::zeros(2, 3));
+        let b = tape.var(Matrix::zeros(3, 2));
+        let _ = a + b;
+    }
+}
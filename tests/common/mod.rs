@@ -1,7 +1,7 @@
 use serde::Serialize;
 use std::fs::File;
 use std::io::Write;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 /// Used by test cases to export model internals for verification
 #[derive(Serialize)]
@@ -38,6 +38,20 @@ pub fn dump_to_verifier(
         .expect("Failed to write verifier output");
 }
 
+/// A path in the temp directory that is unique to this test process
+#[allow(dead_code)]
+pub fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("rustbrain_{}_{}", std::process::id(), name))
+}
+
+/// Writes `contents` to `temp_path(name)` for tests that read files
+#[allow(dead_code)]
+pub fn write_temp(name: &str, contents: &str) -> PathBuf {
+    let path = temp_path(name);
+    std::fs::write(&path, contents).expect("Failed to write temp file");
+    path
+}
+
 #[macro_export]
 macro_rules! export_verifier_output {
     (
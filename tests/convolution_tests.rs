@@ -0,0 +1,219 @@
+#[cfg(test)]
+mod tests {
+    use rustbrain::datasets::{load_mnist, read_idx};
+    use rustbrain::gradcheck::check_layer;
+    use rustbrain::math::{Matrix, Vector};
+    use rustbrain::neuralnetwork::{
+        Adam, AvgPool2D, Conv2D, Dense, Flatten, GlobalAvgPool, Initializer, Layer, Loss, MaxPool2D, ReLU,
+        Sequential, Tanh,
+    };
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+    use std::fs;
+
+    fn lenet() -> Sequential {
+        Sequential::builder(28 * 28)
+            .add(Conv2D::new((1, 28, 28), 6, 5).with_padding(2))
+            .add(ReLU::new())
+            .add(MaxPool2D::new((6, 28, 28), 2))
+            .add(Conv2D::new((6, 14, 14), 16, 5))
+            .add(ReLU::new())
+            .add(MaxPool2D::new((16, 10, 10), 2))
+            .add(Flatten::new((16, 5, 5)))
+            .add(Dense::new(400, 120))
+            .add(ReLU::new())
+            .add(Dense::new(120, 84))
+            .add(ReLU::new())
+            .add(Dense::new(84, 10))
+            .build()
+            .unwrap()
+    }
+
+    fn one_hot(label: usize, classes: usize) -> Vector {
+        let mut v = Vector::zeros(classes);
+        v[label] = 1.0;
+        v
+    }
+
+    fn argmax(v: &Vector) -> usize {
+        (0..v.len()).max_by(|&a, &b| v[a].total_cmp(&v[b])).unwrap()
+    }
+
+    #[test]
+    fn test_conv2d_forward_values() {
+        let mut rng = rand::rng();
+        let mut conv =
+            Conv2D::with_initializer((1, 3, 3), 1, 2, &Initializer::Constant(1.0), &Initializer::Constant(0.5), &mut rng);
+        let image = Matrix::new(vec![vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0]]);
+        let output = conv.forward(&image, false);
+        assert_eq!(output[0].data, vec![12.5, 16.5, 24.5, 28.5]);
+
+        // Padding 1 with stride 2 samples the corners of the padded image
+        let mut padded = Conv2D::with_initializer((1, 3, 3), 1, 2, &Initializer::Constant(1.0), &Initializer::Zeros, &mut rng)
+            .with_padding(1)
+            .with_stride(2);
+        assert_eq!(padded.output_shape(), Ok((1, 2, 2)));
+        assert_eq!(padded.forward(&image, false)[0].data, vec![1.0, 5.0, 11.0, 28.0]);
+
+        // Dilation 2 spreads a 2x2 kernel over the four corners
+        let mut dilated = Conv2D::with_initializer((1, 3, 3), 1, 2, &Initializer::Constant(1.0), &Initializer::Zeros, &mut rng)
+            .with_dilation(2);
+        assert_eq!(dilated.forward(&image, false)[0].data, vec![20.0]);
+    }
+
+    #[test]
+    fn test_lenet_shapes() {
+        let mut model = lenet();
+        assert_eq!(model.output_size(), 10);
+        let output = model.forward(&Matrix::random(2, 28 * 28), true);
+        assert_eq!((output.row_count(), output.col_count()), (2, 10));
+        assert_eq!(model.backward(&output).col_count(), 28 * 28);
+        assert!(model.summary().contains("Conv2D(5x5, 1 -> 6x28x28)"), "{}", model.summary());
+
+        let error = Sequential::builder(28 * 28)
+            .add(Conv2D::new((1, 28, 28), 6, 5))
+            .add(MaxPool2D::new((6, 28, 28), 2))
+            .build()
+            .err()
+            .unwrap();
+        assert!(error.starts_with("Layer 1 (MaxPool2D"), "{}", error);
+        assert!(error.contains("receives 3456"), "{}", error);
+    }
+
+    #[test]
+    fn test_oversized_window_is_a_build_error() {
+        let error = Sequential::builder(16).add(Conv2D::new((1, 4, 4), 2, 5)).build().err().unwrap();
+        assert_eq!(error, "Layer 0 (Conv2D(5x5, 1 -> ?)) has a window spanning 5 but the (padded) input is only 4 wide");
+        assert!(Sequential::builder(16).add(Conv2D::new((1, 4, 4), 2, 5).with_padding(1)).build().is_ok());
+
+        let error = Sequential::builder(9).add(MaxPool2D::new((1, 3, 3), 4)).build().err().unwrap();
+        assert!(error.starts_with("Layer 0 (MaxPool2D(4 -> ?))"), "{}", error);
+        assert!(AvgPool2D::new((1, 3, 3), 2).with_stride(1).output_shape().is_ok());
+        assert!(AvgPool2D::new((2, 3, 3), 4).output_shape().is_err());
+    }
+
+    #[test]
+    fn test_gradcheck_conv2d() {
+        let input = Matrix::random(2, 2 * 5 * 5);
+        let mut layers: Vec<Box<dyn Layer>> = vec![
+            Box::new(Conv2D::new((2, 5, 5), 3, 3)),
+            Box::new(Conv2D::new((2, 5, 5), 2, 2).with_stride(2).with_padding(1)),
+            Box::new(Conv2D::new((2, 5, 5), 2, 2).with_dilation(2).with_padding(1)),
+        ];
+        for layer in &mut layers {
+            let report = check_layer(layer.as_mut(), &input);
+            assert!(report.passed(1e-5), "{}: {:?}", layer.name(), report.errors);
+        }
+    }
+
+    #[test]
+    fn test_gradcheck_pooling() {
+        // Distinct values so that the maxima are unique
+        let input = Matrix::new(
+            (0..2).map(|s| (0..2 * 4 * 4).map(|i| ((i * 7 + s * 3) % 32) as f64 * 0.1 - 1.0).collect()).collect(),
+        );
+        let mut layers: Vec<Box<dyn Layer>> = vec![
+            Box::new(MaxPool2D::new((2, 4, 4), 2)),
+            Box::new(MaxPool2D::new((2, 4, 4), 2).with_stride(1)),
+            Box::new(AvgPool2D::new((2, 4, 4), 2)),
+            Box::new(AvgPool2D::new((2, 4, 4), 3).with_stride(1)),
+            Box::new(GlobalAvgPool::new((2, 4, 4))),
+            Box::new(Flatten::new((2, 4, 4))),
+        ];
+        for layer in &mut layers {
+            let report = check_layer(layer.as_mut(), &input);
+            assert!(report.passed(1e-5), "{}: {:?}", layer.name(), report.errors);
+        }
+
+        let mut pool = MaxPool2D::new((1, 2, 2), 2);
+        let output = pool.forward(&Matrix::new(vec![vec![1.0, 4.0, 3.0, 2.0]]), true);
+        assert_eq!(output[0].data, vec![4.0]);
+        assert_eq!(pool.backward(&Matrix::new(vec![vec![1.0]]))[0].data, vec![0.0, 1.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_read_idx_files() {
+        let dir = std::env::temp_dir().join(format!("rustbrain_idx_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        // Two 2x3 images and their labels
+        let mut images = vec![0, 0, 0x08, 3, 0, 0, 0, 2, 0, 0, 0, 2, 0, 0, 0, 3];
+        images.extend([0, 51, 102, 153, 204, 255, 255, 0, 0, 0, 0, 255]);
+        fs::write(dir.join("train-images-idx3-ubyte"), &images).unwrap();
+        fs::write(dir.join("train-labels-idx1-ubyte"), [0, 0, 0x08, 1, 0, 0, 0, 2, 7, 3]).unwrap();
+
+        let (x, y) = load_mnist(&dir, true).unwrap();
+        assert_eq!(y, vec![7, 3]);
+        assert_eq!(x.len(), 2);
+        assert!((x[0][1] - 0.2).abs() < 1e-12);
+        assert_eq!(x[1].data, vec![1.0, 0.0, 0.0, 0.0, 0.0, 1.0]);
+
+        // Big-endian floats and shape validation
+        let mut floats = vec![0, 0, 0x0D, 1, 0, 0, 0, 2];
+        floats.extend(1.5f32.to_be_bytes());
+        floats.extend((-2.0f32).to_be_bytes());
+        fs::write(dir.join("floats"), &floats).unwrap();
+        assert_eq!(read_idx(dir.join("floats")).unwrap(), (vec![2], vec![1.5, -2.0]));
+        fs::write(dir.join("truncated"), &floats[..10]).unwrap();
+        assert!(read_idx(dir.join("truncated")).is_err());
+        // A header whose size overflows is an error, not a panic
+        let mut huge = vec![0, 0, 0x08, 3];
+        huge.extend([0xFF; 12]);
+        fs::write(dir.join("huge"), &huge).unwrap();
+        assert!(read_idx(dir.join("huge")).unwrap_err().contains("too large"));
+        assert!(load_mnist(&dir, false).is_err(), "Missing test files must be reported");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_cnn_learns_bar_orientation() {
+        // 6x6 images with a single horizontal (class 0) or vertical (class 1) bar
+        let mut inputs = Vec::new();
+        let mut targets = Vec::new();
+        for position in 0..6 {
+            for class in 0..2 {
+                let mut image = Vector::zeros(36);
+                for i in 0..6 {
+                    let (y, x) = if class == 0 { (position, i) } else { (i, position) };
+                    image[y * 6 + x] = 1.0;
+                }
+                inputs.push(image);
+                targets.push(one_hot(class, 2));
+            }
+        }
+
+        let mut rng = StdRng::seed_from_u64(3);
+        let mut model = Sequential::builder(36)
+            .add(Conv2D::with_initializer((1, 6, 6), 4, 3, &Initializer::HeUniform, &Initializer::Zeros, &mut rng))
+            .add(Tanh::new())
+            .add(MaxPool2D::new((4, 4, 4), 2))
+            .add(Flatten::new((4, 2, 2)))
+            .add(Dense::with_initializer(16, 2, &Initializer::XavierUniform, &Initializer::Zeros, &mut rng))
+            .build()
+            .unwrap();
+        let history = model.fit(&inputs, &targets, Loss::SoftmaxCrossEntropy, &mut Adam::new(0.05), 4, 60);
+        assert!(history.last().unwrap() < &history[0]);
+
+        for (x, t) in inputs.iter().zip(&targets) {
+            assert_eq!(argmax(&model.predict(x)), argmax(t));
+        }
+    }
+
+    /// LeNet-5 on MNIST. Expects the uncompressed IDX files in `$MNIST_DIR`
+    /// (default `data/mnist`); run with `cargo test --release -- --ignored`.
+    #[test]
+    #[ignore]
+    fn test_lenet_mnist() {
+        let dir = std::env::var("MNIST_DIR").unwrap_or_else(|_| "data/mnist".to_string());
+        let (train_x, train_y) = load_mnist(&dir, true).unwrap();
+        let (test_x, test_y) = load_mnist(&dir, false).unwrap();
+        let train_t: Vec<Vector> = train_y.iter().map(|&y| one_hot(y, 10)).collect();
+
+        let mut model = lenet();
+        model.fit(&train_x[..6000], &train_t[..6000], Loss::SoftmaxCrossEntropy, &mut Adam::new(0.001), 32, 3);
+
+        let correct = test_x[..1000].iter().zip(&test_y).filter(|(x, y)| argmax(&model.predict(x)) == **y).count();
+        assert!(correct > 900, "LeNet accuracy too low: {}/1000", correct);
+    }
+}
@@ -0,0 +1,125 @@
+#[allow(dead_code)]
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{temp_path, write_temp};
+    use rustbrain::datasets::{infer_schema, read_csv, stream_csv, ColumnKind, CsvOptions};
+
+    #[test]
+    fn test_read_csv_with_header_and_target() {
+        let path = write_temp("target.csv", "size,rooms,price\n50,2,100.5\n80, 3 ,160\n\n120,4,250\r\n");
+        let dataset = read_csv(&path, &CsvOptions::default().with_target("price")).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(dataset.feature_names, vec!["size", "rooms"]);
+        assert_eq!(dataset.target_name.as_deref(), Some("price"));
+        assert_eq!(dataset.len(), 3);
+        assert_eq!(dataset.features.rows[1].data, vec![80.0, 3.0]);
+        assert_eq!(dataset.target.unwrap().data, vec![100.5, 160.0, 250.0]);
+        assert!(dataset.categories.iter().all(|c| c.is_none()));
+    }
+
+    #[test]
+    fn test_categorical_detection_and_missing_values() {
+        let path = write_temp("categorical.csv", "color;weight;label\nred;1.5;yes\n\"blue\";NA;no\ngreen;2;yes\nred;?;no\n");
+        let options = CsvOptions::default().with_delimiter(';').with_target(2);
+        let dataset = read_csv(&path, &options).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(dataset.categories[0], Some(vec!["blue".to_string(), "green".to_string(), "red".to_string()]));
+        assert_eq!(dataset.categories[1], None);
+        assert_eq!(dataset.features.get_column(0).data, vec![2.0, 0.0, 1.0, 2.0]);
+        let weights = dataset.features.get_column(1);
+        assert_eq!(weights[0], 1.5);
+        assert!(weights[1].is_nan() && weights[3].is_nan());
+        assert_eq!(dataset.target_classes, Some(vec!["no".to_string(), "yes".to_string()]));
+        assert_eq!(dataset.target.unwrap().data, vec![1.0, 0.0, 1.0, 0.0]);
+    }
+
+    #[test]
+    fn test_headerless_file_and_forced_categorical() {
+        let path = write_temp("headerless.csv", "1\t10\t0.5\n2\t20\t0.25\n1\t30\t\n");
+        let options = CsvOptions::default().with_delimiter('\t').with_header(false).with_categorical(0);
+        let dataset = read_csv(&path, &options).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(dataset.feature_names, vec!["x0", "x1", "x2"]);
+        assert_eq!(dataset.categories[0], Some(vec!["1".to_string(), "2".to_string()]));
+        assert_eq!(dataset.features.get_column(0).data, vec![0.0, 1.0, 0.0]);
+        assert!(dataset.features.rows[2][2].is_nan());
+        assert!(dataset.target.is_none());
+    }
+
+    #[test]
+    fn test_quoted_fields() {
+        let path = write_temp("quoted.csv", "name,value\n\"Smith, \"\"J\"\"\",1\n  \" padded \" ,2\n");
+        let dataset = read_csv(&path, &CsvOptions::default()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(dataset.categories[0], Some(vec![" padded ".to_string(), "Smith, \"J\"".to_string()]));
+        assert_eq!(dataset.features.get_column(1).data, vec![1.0, 2.0]);
+    }
+
+    #[test]
+    fn test_malformed_files() {
+        let ragged = write_temp("ragged.csv", "a,b\n1,2\n3\n");
+        let error = read_csv(&ragged, &CsvOptions::default()).unwrap_err();
+        assert!(error.contains("line 3") && error.contains("expected 2 fields, found 1"), "{}", error);
+        std::fs::remove_file(&ragged).unwrap();
+
+        let unterminated = write_temp("unterminated.csv", "a,b\n\"1,2\n");
+        assert!(read_csv(&unterminated, &CsvOptions::default()).unwrap_err().contains("unterminated"));
+        std::fs::remove_file(&unterminated).unwrap();
+
+        let valid = write_temp("valid.csv", "a,b\n1,2\n");
+        assert!(read_csv(&valid, &CsvOptions::default().with_target("c")).unwrap_err().contains("No column named \"c\""));
+        assert!(read_csv(&valid, &CsvOptions::default().with_categorical(2)).unwrap_err().contains("out of range"));
+        std::fs::remove_file(&valid).unwrap();
+
+        assert!(read_csv(temp_path("missing.csv"), &CsvOptions::default()).unwrap_err().starts_with("Cannot read"));
+    }
+
+    #[test]
+    fn test_stream_csv_in_batches() {
+        let mut contents = String::from("x,kind,y\n");
+        for i in 0..25 {
+            contents.push_str(&format!("{},{},{}\n", i, ["a", "b", "c"][i % 3], 2 * i));
+        }
+        let path = write_temp("stream.csv", &contents);
+        let options = CsvOptions::default().with_target("y");
+        let schema = infer_schema(&path, &options).unwrap();
+        assert_eq!(schema.kinds[0], ColumnKind::Numeric);
+        assert_eq!(schema.kinds[1], ColumnKind::Categorical(vec!["a".to_string(), "b".to_string(), "c".to_string()]));
+        assert_eq!(schema.target, Some(2));
+
+        let batches: Vec<_> = stream_csv(&path, &options, schema, 10).unwrap().collect::<Result<_, _>>().unwrap();
+        let whole = read_csv(&path, &options).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(batches.iter().map(|b| b.len()).collect::<Vec<_>>(), vec![10, 10, 5]);
+        let rows: Vec<_> = batches.iter().flat_map(|b| b.features.rows.clone()).collect();
+        assert_eq!(rows, whole.features.rows);
+        assert_eq!(batches[2].target.as_ref().unwrap().data, vec![40.0, 42.0, 44.0, 46.0, 48.0]);
+    }
+
+    #[test]
+    fn test_stream_csv_with_schema_of_another_file() {
+        // A test split encoded with the schema of its training split
+        let options = CsvOptions::default().with_target("y");
+        let train = write_temp("train.csv", "x,kind,y\n1,a,0\n2,b,1\n");
+        let schema = infer_schema(&train, &options).unwrap();
+        std::fs::remove_file(&train).unwrap();
+
+        let test = write_temp("test.csv", "x,kind,y\n3,b,1\n4,a,0\n5,c,1\n");
+        let mut batches = stream_csv(&test, &options, schema.clone(), 2).unwrap();
+        assert_eq!(batches.next().unwrap().unwrap().features.get_column(1).data, vec![1.0, 0.0]);
+        let error = batches.next().unwrap().unwrap_err();
+        assert!(error.contains("line 4") && error.contains("unknown category \"c\""), "{}", error);
+        std::fs::remove_file(&test).unwrap();
+
+        let wide = write_temp("wide.csv", "x,kind,y,z\n1,a,0,0\n");
+        assert!(stream_csv(&wide, &options, schema, 2).err().unwrap().contains("schema has 3 columns but the file has 4"));
+        std::fs::remove_file(&wide).unwrap();
+    }
+}
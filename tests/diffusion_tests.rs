@@ -0,0 +1,131 @@
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use rustbrain::datasets::swiss_roll_2d;
+    use rustbrain::diffusion::{NoiseSchedule, DDPM};
+    use rustbrain::math::Vector;
+    use rustbrain::neuralnetwork::{Adam, Dense, Initializer, ReLU, Sequential};
+    use rustbrain::utils::random::standard_normal;
+
+    fn denoiser(hidden: usize, time_dim: usize, rng: &mut StdRng) -> Sequential {
+        let mut dense = |i, o| Dense::with_initializer(i, o, &Initializer::HeUniform, &Initializer::Zeros, rng);
+        Sequential::builder(2 + time_dim)
+            .add(dense(2 + time_dim, hidden))
+            .add(ReLU::new())
+            .add(dense(hidden, hidden))
+            .add(ReLU::new())
+            .add(dense(hidden, 2))
+            .build()
+            .unwrap()
+    }
+
+    fn gaussian(rng: &mut StdRng) -> Vector {
+        Vector::new(vec![standard_normal(rng), standard_normal(rng)])
+    }
+
+    fn distance(a: &Vector, b: &Vector) -> f64 {
+        a.add(&b.scale(-1.0)).norm()
+    }
+
+    /// Mean distance from each sample to its nearest reference point.
+    fn mean_nearest_distance(samples: &[Vector], reference: &[Vector]) -> f64 {
+        samples.iter().map(|s| reference.iter().map(|r| distance(s, r)).fold(f64::INFINITY, f64::min)).sum::<f64>()
+            / samples.len() as f64
+    }
+
+    #[test]
+    fn test_noise_schedules() {
+        let linear = NoiseSchedule::linear(1000, 1e-4, 0.02);
+        assert_eq!(linear.steps(), 1000);
+        assert!((linear.betas[0] - 1e-4).abs() < 1e-15 && (linear.betas[999] - 0.02).abs() < 1e-15);
+        assert!((linear.alpha_bars[1] - (1.0 - 1e-4) * (1.0 - linear.betas[1])).abs() < 1e-15);
+        assert!(linear.alpha_bars[999] < 1e-4);
+
+        let cosine = NoiseSchedule::cosine(100);
+        assert!(cosine.alpha_bars.windows(2).all(|w| w[1] < w[0]));
+        assert!(cosine.alpha_bars[0] > 0.999 && cosine.alpha_bars[99] < 1e-3);
+        // ᾱ_t follows the cosine curve
+        let f = |t: f64| ((t / 100.0 + 0.008) / 1.008 * std::f64::consts::FRAC_PI_2).cos().powi(2);
+        assert!((cosine.alpha_bars[49] - f(50.0) / f(0.0)).abs() < 1e-9);
+
+        let t = 10;
+        let expected = (1.0 - cosine.alpha_bars[t - 1]) / (1.0 - cosine.alpha_bars[t]) * cosine.betas[t];
+        assert_eq!(cosine.posterior_variance(t), expected);
+        assert_eq!(cosine.posterior_variance(0), 0.0);
+    }
+
+    #[test]
+    fn test_forward_noising() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let ddpm = DDPM::new(denoiser(8, 4, &mut rng), NoiseSchedule::linear(100, 1e-4, 0.1), 2, 4);
+        let x0 = Vector::new(vec![2.0, -1.0]);
+
+        let t = 30;
+        let scale = ddpm.schedule.alpha_bars[t].sqrt();
+        assert_eq!(ddpm.q_sample(&x0, t, &Vector::zeros(2)).data, vec![2.0 * scale, -scale]);
+
+        // q(x_t | x_0) = N(√ᾱ_t x_0, (1 - ᾱ_t) I)
+        let alpha_bar = ddpm.schedule.alpha_bars[99];
+        let samples: Vec<Vector> = (0..4000).map(|_| ddpm.q_sample(&x0, 99, &gaussian(&mut rng))).collect();
+        let mean = samples.iter().map(|x| x[0]).sum::<f64>() / 4000.0;
+        let var = samples.iter().map(|x| (x[0] - mean).powi(2)).sum::<f64>() / 4000.0;
+        assert!((mean - 2.0 * alpha_bar.sqrt()).abs() < 0.05, "mean {}", mean);
+        assert!((var - (1.0 - alpha_bar)).abs() < 0.08, "var {}", var);
+    }
+
+    #[test]
+    #[should_panic(expected = "Model input must be data_dim + time_dim")]
+    fn test_model_shape_is_checked() {
+        let mut rng = StdRng::seed_from_u64(0);
+        DDPM::new(denoiser(8, 4, &mut rng), NoiseSchedule::cosine(10), 2, 8);
+    }
+
+    #[test]
+    fn test_ddim_is_deterministic() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut ddpm = DDPM::new(denoiser(8, 4, &mut rng), NoiseSchedule::cosine(40), 2, 4);
+        let noise: Vec<Vector> = (0..5).map(|_| gaussian(&mut rng)).collect();
+        let first = ddpm.ddim_from_noise(&noise, 10);
+        assert_eq!(first, ddpm.ddim_from_noise(&noise, 10));
+        assert_ne!(first, ddpm.ddim_from_noise(&noise, 40));
+        assert_eq!(ddpm.sample_ddpm(3, &mut rng).len(), 3);
+    }
+
+    #[test]
+    fn test_learns_two_modes() {
+        let mut rng = StdRng::seed_from_u64(2);
+        let modes = [Vector::new(vec![1.0, 0.5]), Vector::new(vec![-1.0, -0.5])];
+        let data: Vec<Vector> = (0..256).map(|i| modes[i % 2].add(&gaussian(&mut rng).scale(0.05))).collect();
+
+        let mut ddpm = DDPM::new(denoiser(32, 8, &mut rng), NoiseSchedule::linear(40, 1e-4, 0.25), 2, 8);
+        let history = ddpm.fit(&data, &mut Adam::new(0.005), 32, 150, &mut rng);
+        assert!(history.last().unwrap() < &history[0]);
+
+        for samples in [ddpm.sample_ddpm(100, &mut rng), ddpm.sample_ddim(100, 10, &mut rng)] {
+            let counts: Vec<usize> =
+                modes.iter().map(|m| samples.iter().filter(|s| distance(s, m) < 0.3).count()).collect();
+            assert!(counts[0] + counts[1] >= 80, "Only {:?} of 100 samples near a mode", counts);
+            assert!(counts[0] >= 25 && counts[1] >= 25, "A mode was dropped: {:?}", counts);
+        }
+    }
+
+    /// Acceptance test: fits a 2-D Swiss roll, scaled down to a few hundred
+    /// points and a small denoiser so it runs with the rest of the suite.
+    #[test]
+    fn test_swiss_roll() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let data = swiss_roll_2d(200, 0.02, &mut rng);
+        let mut ddpm = DDPM::new(denoiser(32, 8, &mut rng), NoiseSchedule::linear(40, 1e-4, 0.25), 2, 8);
+        ddpm.fit(&data, &mut Adam::new(0.005), 32, 120, &mut rng);
+        ddpm.fit(&data, &mut Adam::new(0.001), 32, 40, &mut rng);
+
+        let reference = swiss_roll_2d(1000, 0.0, &mut rng);
+        let noise: Vec<Vector> = (0..200).map(|_| gaussian(&mut rng)).collect();
+        let baseline = mean_nearest_distance(&noise, &reference);
+        let ddpm_distance = mean_nearest_distance(&ddpm.sample_ddpm(200, &mut rng), &reference);
+        let ddim_distance = mean_nearest_distance(&ddpm.sample_ddim(200, 20, &mut rng), &reference);
+        assert!(ddpm_distance < 0.75 * baseline, "DDPM {} vs N(0, I) {}", ddpm_distance, baseline);
+        assert!(ddim_distance < 0.75 * baseline, "DDIM {} vs N(0, I) {}", ddim_distance, baseline);
+    }
+}
@@ -0,0 +1,96 @@
+#[cfg(test)]
+mod tests {
+    use rustbrain::math::Vector;
+    use rustbrain::preprocessing::{HandleUnknown, LabelEncoder, OneHotEncoder, OrdinalEncoder, TargetEncoder};
+
+    fn rows() -> Vec<Vec<&'static str>> {
+        vec![vec!["red", "S"], vec!["green", "M"], vec!["blue", "S"], vec!["red", "L"]]
+    }
+
+    #[test]
+    fn test_one_hot_encoder() {
+        let mut encoder = OneHotEncoder::new();
+        let encoded = encoder.fit_transform(&rows());
+        assert_eq!(encoder.categories, vec![vec!["blue", "green", "red"], vec!["L", "M", "S"]]);
+        assert_eq!(encoded.rows[0].data, vec![0.0, 0.0, 1.0, 0.0, 0.0, 1.0]);
+        assert_eq!(encoded.rows[3].data, vec![0.0, 0.0, 1.0, 1.0, 0.0, 0.0]);
+        let names = vec!["color".to_string(), "size".to_string()];
+        assert_eq!(encoder.feature_names(Some(&names))[..3], ["color_blue", "color_green", "color_red"]);
+        assert_eq!(encoder.inverse_transform(&encoded)[1], vec![Some("green".to_string()), Some("M".to_string())]);
+    }
+
+    #[test]
+    fn test_one_hot_unknown_categories_and_sparse_output() {
+        let mut encoder = OneHotEncoder::new();
+        encoder.fit(&rows());
+        let unseen = vec![vec!["purple", "M"]];
+        assert_eq!(encoder.transform(&unseen).unwrap_err(), "Unknown category \"purple\" in column 0");
+
+        let encoder = encoder.with_handle_unknown(HandleUnknown::Ignore);
+        let encoded = encoder.transform(&unseen).unwrap();
+        assert_eq!(encoded.rows[0].data, vec![0.0, 0.0, 0.0, 0.0, 1.0, 0.0]);
+        assert_eq!(encoder.inverse_transform(&encoded)[0], vec![None, Some("M".to_string())]);
+
+        let sparse = encoder.transform_sparse(&rows()).unwrap();
+        assert_eq!(sparse.nnz(), 8);
+        assert_eq!(sparse.row(2), (&[0, 5][..], &[1.0, 1.0][..]));
+        assert_eq!(sparse.to_dense(), encoder.transform(&rows()).unwrap());
+    }
+
+    #[test]
+    fn test_ordinal_encoder() {
+        let mut encoder = OrdinalEncoder::new();
+        let encoded = encoder.fit_transform(&rows());
+        assert_eq!(encoded.get_column(0).data, vec![2.0, 1.0, 0.0, 2.0]);
+        assert_eq!(encoded.get_column(1).data, vec![2.0, 1.0, 2.0, 0.0]);
+        assert!(encoder.transform(&[vec!["red", "XL"]]).is_err());
+
+        let encoder = encoder.with_unknown_value(-1.0);
+        let encoded = encoder.transform(&[vec!["red", "XL"]]).unwrap();
+        assert_eq!(encoded.rows[0].data, vec![2.0, -1.0]);
+        assert_eq!(encoder.inverse_transform(&encoded), vec![vec![Some("red".to_string()), None]]);
+    }
+
+    #[test]
+    fn test_label_encoder() {
+        let labels = ["cat", "dog", "bird", "dog"];
+        let mut encoder = LabelEncoder::new();
+        assert_eq!(encoder.fit_transform(&labels), vec![1, 2, 0, 2]);
+        assert_eq!(encoder.transform_i32(&["bird", "cat"]).unwrap(), vec![0, 1]);
+        assert_eq!(encoder.inverse_transform(&[2, 0]), vec!["dog", "bird"]);
+        assert_eq!(encoder.transform(&["fish"]).unwrap_err(), "Unknown label \"fish\"");
+    }
+
+    #[test]
+    fn test_target_encoder() {
+        let rows: Vec<Vec<&str>> = ["a", "a", "b", "b", "b", "c"].iter().map(|c| vec![*c]).collect();
+        let targets = Vector::new(vec![1.0, 3.0, 10.0, 10.0, 10.0, 0.0]);
+        let mut encoder = TargetEncoder::new().with_smoothing(2.0);
+        encoder.fit(&rows, &targets);
+        assert!((encoder.global_mean - 34.0 / 6.0).abs() < 1e-12);
+        // a: (1 + 3 + 2 * mean) / (2 + 2)
+        let encoded = encoder.transform(&[vec!["a"], vec!["z"]]);
+        assert!((encoded[(0, 0)] - (4.0 + 2.0 * 34.0 / 6.0) / 4.0).abs() < 1e-12);
+        assert!((encoded[(1, 0)] - 34.0 / 6.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_target_encoder_cross_fitting_prevents_leakage() {
+        // Every row has its own category, so its only information is its own target
+        let rows: Vec<Vec<String>> = (0..10).map(|i| vec![format!("id{}", i)]).collect();
+        let targets = Vector::new((0..10).map(|i| if i % 2 == 0 { 1.0 } else { 0.0 }).collect());
+        let mut encoder = TargetEncoder::new().with_folds(5);
+        let cross_fitted = encoder.fit_transform(&rows, &targets);
+        let in_sample = encoder.transform(&rows);
+        // In-sample encodings reveal the target, cross-fitted ones fall back to the other folds' mean
+        assert!(in_sample.rows.iter().zip(targets.iter()).all(|(e, t)| (e[0] > 0.5) == (*t > 0.5)));
+        for (i, row) in cross_fitted.rows.iter().enumerate() {
+            let others: Vec<f64> = (0..10).filter(|j| j % 5 != i % 5).map(|j| targets[j]).collect();
+            assert!((row[0] - others.iter().sum::<f64>() / others.len() as f64).abs() < 1e-12);
+        }
+
+        let restored: TargetEncoder = serde_json::from_str(&serde_json::to_string(&encoder).unwrap()).unwrap();
+        assert_eq!(restored.transform(&rows), in_sample);
+        assert_eq!(restored.transform(&[vec!["unseen"]])[(0, 0)], 0.5);
+    }
+}
@@ -0,0 +1,132 @@
+#[cfg(test)]
+mod tests {
+    use rustbrain::gradcheck::{check_closure, check_layer, check_matrices, check_network, check_sequential};
+    use rustbrain::math::{Matrix, Vector};
+    use rustbrain::neuralnetwork::{
+        BatchNorm, Dense, Initializer, Layer, LayerNorm, Loss, NeuralNetwork, Normalization, Sequential, Sigmoid, Tanh,
+    };
+    use rustbrain::{LogisticRegression, SoftmaxRegression};
+
+    const TOLERANCE: f64 = 1e-5;
+
+    fn batch() -> Matrix {
+        Matrix::new(vec![
+            vec![0.5, -1.2, 0.3, 0.8],
+            vec![-0.7, 0.4, 1.1, -0.2],
+            vec![0.9, 0.1, -0.6, 0.4],
+        ])
+    }
+
+    #[test]
+    fn test_check_closure() {
+        // f(x) = x0² x1 + sin(x2)
+        let f = |x: &Vector| x[0] * x[0] * x[1] + x[2].sin();
+        let x = Vector::new(vec![1.5, -0.5, 0.3]);
+        let analytic = Vector::new(vec![2.0 * x[0] * x[1], x[0] * x[0], x[2].cos()]);
+        assert!(check_closure(f, &x, &analytic) < TOLERANCE);
+
+        // A wrong gradient must be detected
+        let wrong = Vector::new(vec![2.0 * x[0] * x[1], x[0], x[2].cos()]);
+        assert!(check_closure(f, &x, &wrong) > 0.1);
+    }
+
+    #[test]
+    fn test_custom_layers() {
+        let mut layers: Vec<Box<dyn Layer>> = vec![
+            Box::new(Dense::new(4, 3)),
+            Box::new(Sigmoid::new()),
+            Box::new(Tanh::new()),
+            Box::new(BatchNorm::new(4)),
+            Box::new(LayerNorm::new(4)),
+        ];
+        for layer in &mut layers {
+            let report = check_layer(layer.as_mut(), &batch());
+            assert!(report.passed(TOLERANCE), "{}: {:?}", layer.name(), report.errors);
+        }
+    }
+
+    #[test]
+    fn test_sequential_model() {
+        let mut model = Sequential::builder(4)
+            .add(Dense::new(4, 5))
+            .add(Tanh::new())
+            .add(LayerNorm::new(5))
+            .add(Dense::new(5, 3))
+            .build()
+            .unwrap();
+        let targets = Matrix::new(vec![vec![1.0, 0.0, 0.0], vec![0.0, 1.0, 0.0], vec![0.0, 0.0, 1.0]]);
+        let report = check_sequential(&mut model, &batch(), &targets, Loss::SoftmaxCrossEntropy);
+        assert_eq!(report.errors.len(), 6, "Two tensors per Dense and LayerNorm layer");
+        assert!(report.passed(TOLERANCE), "{:?}", report.errors);
+    }
+
+    #[test]
+    fn test_neural_network_backprop() {
+        let mut nn = NeuralNetwork::new(&[4, 6, 2])
+            .with_initializer(Initializer::XavierNormal, Initializer::Constant(0.1), 7)
+            .with_weight_decay(0.01, 0.05)
+            .with_layer_norm();
+        let inputs: Vec<Vector> = batch().rows;
+        let targets = vec![Vector::new(vec![1.0, 0.0]), Vector::new(vec![0.0, 1.0]), Vector::new(vec![0.5, 0.5])];
+        let report = check_network(&mut nn, &inputs, &targets);
+        assert_eq!(report.errors.len(), 4, "Two weight matrices plus the hidden layer's gamma and beta");
+        assert!(report.passed(TOLERANCE), "{:?}", report.errors);
+    }
+
+    #[test]
+    fn test_checks_leave_running_statistics_untouched() {
+        let mut layer = BatchNorm::new(4);
+        assert!(check_layer(&mut layer, &batch()).passed(TOLERANCE));
+        assert_eq!(layer.running_mean.data, vec![0.0; 4]);
+        assert_eq!(layer.running_var.data, vec![1.0; 4]);
+
+        let mut nn = NeuralNetwork::new(&[4, 6, 2]).with_initializer(Initializer::XavierNormal, Initializer::Constant(0.1), 3).with_batch_norm();
+        let inputs: Vec<Vector> = batch().rows;
+        let targets = vec![Vector::new(vec![1.0, 0.0]), Vector::new(vec![0.0, 1.0]), Vector::new(vec![0.5, 0.5])];
+        let report = check_network(&mut nn, &inputs, &targets);
+        assert!(report.passed(TOLERANCE), "{:?}", report.errors);
+        let Some(Normalization::Batch(norm)) = &nn.normalizations[0] else { panic!("Expected batch normalization") };
+        assert_eq!(norm.running_mean.data, vec![0.0; 6]);
+        assert_eq!(norm.running_var.data, vec![1.0; 6]);
+    }
+
+    #[test]
+    fn test_logistic_regression_gradient() {
+        let inputs: Vec<Vector> = batch().rows;
+        let targets = Vector::new(vec![1.0, 0.0, 1.0]);
+        let mut model = LogisticRegression::new(4, 0.0, 0.1);
+        model.weights = Vector::new(vec![0.2, -0.4, 0.7, 0.1, -0.3]);
+
+        let analytic = model.gradient(&inputs, &targets);
+        let weights = model.weights.clone();
+        let error = check_closure(
+            |w| {
+                model.weights = w.clone();
+                model.loss(&inputs, &targets)
+            },
+            &weights,
+            &analytic,
+        );
+        assert!(error < TOLERANCE, "Relative error {}", error);
+    }
+
+    #[test]
+    fn test_softmax_regression_gradient() {
+        let inputs: Vec<Vector> = batch().rows;
+        let targets = vec![0, 2, 1];
+        let mut model = SoftmaxRegression::new(4, 3);
+
+        let analytic = model.gradient(&inputs, &targets);
+        let weights = model.weights.clone();
+        let report = check_matrices(
+            |w| {
+                model.weights = w[0].clone();
+                model.loss(&inputs, &targets)
+            },
+            &[weights],
+            &[analytic],
+            &["weights".to_string()],
+        );
+        assert!(report.passed(TOLERANCE), "{:?}", report.errors);
+    }
+}
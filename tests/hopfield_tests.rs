@@ -0,0 +1,129 @@
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+    use rustbrain::gradcheck::check_layer;
+    use rustbrain::hopfield::{HopfieldNetwork, LearningRule, ModernHopfield, UpdateRule};
+    use rustbrain::math::{Matrix, Vector};
+
+    fn random_patterns(count: usize, size: usize, rng: &mut StdRng) -> Vec<Vector> {
+        (0..count)
+            .map(|_| Vector::new((0..size).map(|_| if rng.random_bool(0.5) { 1.0 } else { -1.0 }).collect()))
+            .collect()
+    }
+
+    /// Flips `flips` distinct entries of a bipolar pattern.
+    fn corrupt(pattern: &Vector, flips: usize, rng: &mut StdRng) -> Vector {
+        let mut noisy = pattern.clone();
+        let indices = rand::seq::index::sample(rng, pattern.len(), flips);
+        for i in indices {
+            noisy[i] = -noisy[i];
+        }
+        noisy
+    }
+
+    fn hamming(a: &Vector, b: &Vector) -> usize {
+        a.iter().zip(b.iter()).filter(|(x, y)| x != y).count()
+    }
+
+    #[test]
+    fn test_hebbian_weights() {
+        let patterns = vec![Vector::new(vec![1.0, -1.0, 1.0, -1.0]), Vector::new(vec![1.0, 1.0, -1.0, -1.0])];
+        let mut network = HopfieldNetwork::new(4);
+        network.train(&patterns, LearningRule::Hebbian);
+
+        for i in 0..4 {
+            assert_eq!(network.weights[(i, i)], 0.0);
+            for j in 0..4 {
+                assert_eq!(network.weights[(i, j)], network.weights[(j, i)]);
+            }
+        }
+        // w_01 = (1 * -1 + 1 * 1) / 4, w_03 = (1 * -1 + 1 * -1) / 4
+        assert_eq!(network.weights[(0, 1)], 0.0);
+        assert_eq!(network.weights[(0, 3)], -0.5);
+        assert!(patterns.iter().all(|p| network.is_fixed_point(p)));
+        assert!(network.energy(&patterns[0]) < network.energy(&Vector::new(vec![1.0, 1.0, 1.0, 1.0])));
+    }
+
+    #[test]
+    fn test_recovers_noisy_patterns() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let patterns = random_patterns(5, 100, &mut rng);
+        for rule in [LearningRule::Hebbian, LearningRule::Storkey] {
+            let mut network = HopfieldNetwork::new(100);
+            network.train(&patterns, rule);
+            for pattern in &patterns {
+                let noisy = corrupt(pattern, 15, &mut rng);
+                let recalled = network.recall_with_rng(&noisy, UpdateRule::Asynchronous, 20, &mut rng);
+                assert_eq!(hamming(&recalled, pattern), 0, "{:?} failed to recover a pattern", rule);
+                let recalled = network.recall(&noisy, UpdateRule::Synchronous, 20);
+                assert_eq!(hamming(&recalled, pattern), 0, "{:?} synchronous recall failed", rule);
+            }
+        }
+    }
+
+    #[test]
+    fn test_storkey_stores_more_patterns() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let patterns = random_patterns(14, 64, &mut rng);
+        let mut hebbian = HopfieldNetwork::new(64);
+        hebbian.train(&patterns, LearningRule::Hebbian);
+        let mut storkey = HopfieldNetwork::new(64);
+        storkey.train(&patterns, LearningRule::Storkey);
+
+        let stable = |network: &HopfieldNetwork| patterns.iter().filter(|p| network.is_fixed_point(p)).count();
+        assert!(stable(&storkey) > stable(&hebbian), "Storkey {} vs Hebbian {}", stable(&storkey), stable(&hebbian));
+    }
+
+    #[test]
+    fn test_asynchronous_updates_never_increase_energy() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let patterns = random_patterns(8, 50, &mut rng);
+        let mut network = HopfieldNetwork::new(50);
+        network.train(&patterns, LearningRule::Hebbian);
+
+        let mut state = random_patterns(1, 50, &mut rng).remove(0);
+        let mut energy = network.energy(&state);
+        for _ in 0..10 {
+            network.step_asynchronous(&mut state, &mut rng);
+            let next = network.energy(&state);
+            assert!(next <= energy + 1e-12, "Energy rose from {} to {}", energy, next);
+            energy = next;
+        }
+        assert!(network.is_fixed_point(&state));
+    }
+
+    #[test]
+    fn test_modern_hopfield_retrieval() {
+        // Far more patterns than a classical network of this size could hold
+        let mut rng = StdRng::seed_from_u64(5);
+        let patterns = random_patterns(40, 32, &mut rng);
+        let memory = ModernHopfield::new(&patterns, 2.0);
+
+        for pattern in &patterns {
+            let noisy = Vector::new(pattern.iter().map(|x| x + rng.random_range(-0.8..0.8)).collect());
+            let retrieved = memory.retrieve(&noisy);
+            assert!(retrieved.add(&pattern.scale(-1.0)).norm() < 1e-6);
+            assert!(memory.energy(&retrieved) <= memory.energy(&noisy));
+            assert!(memory.recall(&noisy, 5, 1e-9).add(&pattern.scale(-1.0)).norm() < 1e-6);
+        }
+
+        // A low inverse temperature blends the patterns instead
+        let blurry = ModernHopfield::new(&patterns, 0.01);
+        assert!(blurry.retrieve(&patterns[0]).norm() < 0.5 * patterns[0].norm());
+    }
+
+    #[test]
+    fn test_modern_hopfield_layer_gradients() {
+        let patterns = vec![
+            Vector::new(vec![0.5, -0.2, 0.8]),
+            Vector::new(vec![-0.3, 0.9, 0.1]),
+            Vector::new(vec![0.7, 0.4, -0.6]),
+            Vector::new(vec![-0.1, -0.5, -0.4]),
+        ];
+        let mut layer = ModernHopfield::new(&patterns, 2.0);
+        let queries = Matrix::new(vec![vec![0.3, -0.1, 0.6], vec![-0.4, 0.8, 0.2]]);
+        let report = check_layer(&mut layer, &queries);
+        assert!(report.passed(1e-5), "{:?}", report.errors);
+    }
+}
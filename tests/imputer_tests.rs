@@ -0,0 +1,143 @@
+#[cfg(test)]
+mod tests {
+    use rustbrain::math::Matrix;
+    use rustbrain::preprocessing::{ImputeStrategy, IterativeImputer, KNNImputer, MissingIndicator, SimpleImputer};
+
+    const NAN: f64 = f64::NAN;
+
+    fn data() -> Matrix {
+        Matrix::new(vec![vec![1.0, 2.0, NAN], vec![NAN, 2.0, 3.0], vec![7.0, 5.0, NAN], vec![4.0, NAN, NAN]])
+    }
+
+    #[test]
+    fn test_simple_imputer_strategies() {
+        let fill = |strategy| {
+            let mut imputer = SimpleImputer::new(strategy);
+            imputer.fit(&data());
+            imputer.statistics.data
+        };
+        assert_eq!(fill(ImputeStrategy::Mean), vec![4.0, 3.0, 3.0]);
+        assert_eq!(fill(ImputeStrategy::Median), vec![4.0, 2.0, 3.0]);
+        assert_eq!(fill(ImputeStrategy::MostFrequent), vec![1.0, 2.0, 3.0]);
+        assert_eq!(fill(ImputeStrategy::Constant(-1.0)), vec![-1.0, -1.0, -1.0]);
+
+        let mut imputer = SimpleImputer::new(ImputeStrategy::Median);
+        let imputed = imputer.fit_transform(&data());
+        assert_eq!(imputed.rows[1].data, vec![4.0, 2.0, 3.0]);
+        assert_eq!(imputed.rows[2].data, vec![7.0, 5.0, 3.0]);
+        assert!(imputed.rows.iter().all(|row| row.iter().all(|v| !v.is_nan())));
+    }
+
+    #[test]
+    fn test_missing_indicator() {
+        let mut indicator = MissingIndicator::new();
+        let complete = Matrix::new(vec![vec![1.0, NAN, 0.0], vec![2.0, 1.0, 0.0]]);
+        let flags = indicator.fit_transform(&complete);
+        assert_eq!(indicator.features, vec![1]);
+        assert_eq!(flags.get_column(0).data, vec![1.0, 0.0]);
+
+        let mut imputer = SimpleImputer::new(ImputeStrategy::Mean).with_indicator();
+        let imputed = imputer.fit_transform(&data());
+        assert_eq!(imputed.col_count(), 6);
+        assert_eq!(imputed.rows[3].data, vec![4.0, 3.0, 3.0, 0.0, 1.0, 1.0]);
+    }
+
+    #[test]
+    fn test_knn_imputer() {
+        let inputs = Matrix::new(vec![vec![1.0, 1.0, 10.0], vec![1.1, 0.9, 12.0], vec![5.0, 5.0, 50.0], vec![5.2, 4.8, NAN], vec![1.05, NAN, NAN]]);
+        let mut imputer = KNNImputer::new(2);
+        let imputed = imputer.fit_transform(&inputs);
+        // Rows 0 and 1 are nearest to row 4; row 2 is the only other observer of column 2 near row 3
+        assert!((imputed[(4, 1)] - 0.95).abs() < 1e-12);
+        assert!((imputed[(4, 2)] - 11.0).abs() < 1e-12);
+        assert!((imputed[(3, 2)] - 31.0).abs() < 1e-12);
+        assert_eq!(imputed.rows[0], inputs.rows[0]);
+
+        let mut nearest = KNNImputer::new(1);
+        nearest.fit(&inputs);
+        assert_eq!(nearest.transform(&inputs)[(3, 2)], 50.0);
+    }
+
+    #[test]
+    fn test_iterative_imputer_recovers_linear_relation() {
+        // x2 = 2 x0 - x1 + 1 with every fourth x2 missing
+        let rows: Vec<Vec<f64>> = (0..20)
+            .map(|i| {
+                let (x0, x1) = (i as f64 * 0.5, ((i * 7) % 5) as f64);
+                vec![x0, x1, if i % 4 == 0 { NAN } else { 2.0 * x0 - x1 + 1.0 }]
+            })
+            .collect();
+        let inputs = Matrix::new(rows);
+        let mut imputer = IterativeImputer::new();
+        let imputed = imputer.fit_transform(&inputs);
+        for (i, row) in imputed.rows.iter().enumerate() {
+            assert!((row[2] - (2.0 * row[0] - row[1] + 1.0)).abs() < 1e-6, "row {}: {:?}", i, row.data);
+        }
+        assert!(imputer.models[2].is_some() && imputer.models[0].is_none());
+
+        let unseen = imputer.transform(&Matrix::new(vec![vec![3.0, 2.0, NAN]]));
+        assert!((unseen[(0, 2)] - 5.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_iterative_imputer_with_gaps_in_several_columns() {
+        let rows: Vec<Vec<f64>> = (0..30)
+            .map(|i| {
+                let t = i as f64 / 3.0;
+                let mut row = vec![t, 3.0 * t - 2.0, ((i * 11) % 7) as f64];
+                if i % 5 == 1 {
+                    row[0] = NAN;
+                }
+                if i % 6 == 2 {
+                    row[1] = NAN;
+                }
+                row
+            })
+            .collect();
+        let inputs = Matrix::new(rows);
+        let mut imputer = IterativeImputer::new().with_max_iter(50).with_tolerance(1e-10).with_indicator();
+        let imputed = imputer.fit_transform(&inputs);
+        assert_eq!(imputed.col_count(), 5);
+        for i in 0..30 {
+            assert!((imputed[(i, 1)] - (3.0 * imputed[(i, 0)] - 2.0)).abs() < 1e-3, "row {}: {:?}", i, imputed.rows[i].data);
+            assert_eq!(imputed[(i, 3)], if i % 5 == 1 { 1.0 } else { 0.0 });
+        }
+        assert!(imputer.n_iter < 50, "{} rounds", imputer.n_iter);
+    }
+
+    #[test]
+    fn test_iterative_imputer_on_degenerate_columns() {
+        // x1 = 2 x0 is collinear with x0, x2 = x0 + 1 is observed in fewer rows
+        // than there are features and x3 is never observed
+        let rows: Vec<Vec<f64>> = (0..8)
+            .map(|i| {
+                let x0 = i as f64;
+                vec![x0, 2.0 * x0, if i < 2 { x0 + 1.0 } else { NAN }, NAN]
+            })
+            .collect();
+        let inputs = Matrix::new(rows);
+        let mut imputer = IterativeImputer::new();
+        let imputed = imputer.fit_transform(&inputs);
+        for (i, row) in imputed.rows.iter().enumerate() {
+            assert!((row[2] - (row[0] + 1.0)).abs() < 1e-3, "row {}: {:?}", i, row.data);
+            assert_eq!(row[3], 0.0);
+        }
+        assert!(imputer.models[2].is_some() && imputer.models[3].is_none());
+    }
+
+    #[test]
+    fn test_imputers_serialize() {
+        let mut simple = SimpleImputer::new(ImputeStrategy::Constant(0.5)).with_indicator();
+        simple.fit(&data());
+        let restored: SimpleImputer = serde_json::from_str(&serde_json::to_string(&simple).unwrap()).unwrap();
+        assert_eq!(restored, simple);
+
+        // The training rows of the KNN imputer hold NaNs, written as null
+        let mut knn = KNNImputer::new(2);
+        knn.fit(&data());
+        let json = serde_json::to_string(&knn).unwrap();
+        assert!(json.contains("null"));
+        let restored: KNNImputer = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.transform(&data()), knn.transform(&data()));
+    }
+}
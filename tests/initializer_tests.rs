@@ -0,0 +1,114 @@
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use rustbrain::math::Matrix;
+    use rustbrain::neuralnetwork::{Conv2D, Dense, Initializer, NeuralNetwork};
+
+    fn entries(m: &Matrix) -> Vec<f64> {
+        m.rows.iter().flat_map(|row| row.data.clone()).collect()
+    }
+
+    fn variance(values: &[f64]) -> f64 {
+        let mean = values.iter().sum::<f64>() / values.len() as f64;
+        values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / values.len() as f64
+    }
+
+    #[test]
+    fn test_uniform_schemes_respect_bounds() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let (fan_in, fan_out) = (50, 30);
+        let cases = [
+            (Initializer::XavierUniform, (6.0 / 80.0_f64).sqrt()),
+            (Initializer::HeUniform, (6.0 / 50.0_f64).sqrt()),
+            (Initializer::LeCunUniform, (3.0 / 50.0_f64).sqrt()),
+        ];
+        for (init, bound) in cases {
+            let m = init.initialize(fan_in, fan_out, &mut rng);
+            assert_eq!((m.row_count(), m.col_count()), (fan_out, fan_in));
+            assert!(entries(&m).iter().all(|w| w.abs() <= bound), "{:?} exceeded its bound", init);
+        }
+    }
+
+    #[test]
+    fn test_normal_schemes_match_variance() {
+        let mut rng = StdRng::seed_from_u64(11);
+        let (fan_in, fan_out) = (200, 100);
+        let cases = [
+            (Initializer::XavierNormal, 2.0 / 300.0),
+            (Initializer::HeNormal, 2.0 / 200.0),
+            (Initializer::LeCunNormal, 1.0 / 200.0),
+        ];
+        for (init, expected) in cases {
+            let var = variance(&entries(&init.initialize(fan_in, fan_out, &mut rng)));
+            assert!((var - expected).abs() / expected < 0.1, "{:?}: variance {} vs {}", init, var, expected);
+        }
+    }
+
+    #[test]
+    fn test_orthogonal_initializer() {
+        let mut rng = StdRng::seed_from_u64(3);
+        // Tall matrix: orthonormal columns
+        let tall = Initializer::Orthogonal { gain: 1.0 }.initialize(4, 6, &mut rng);
+        let gram = tall.transpose().gemm(&tall);
+        // Wide matrix: orthonormal rows, scaled by the gain
+        let wide = Initializer::Orthogonal { gain: 2.0 }.initialize(6, 4, &mut rng);
+        let wide_gram = wide.gemm(&wide.transpose());
+        for i in 0..4 {
+            for j in 0..4 {
+                let expected = if i == j { 1.0 } else { 0.0 };
+                assert!((gram[(i, j)] - expected).abs() < 1e-9);
+                assert!((wide_gram[(i, j)] - 4.0 * expected).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn test_network_initialization_is_seeded() {
+        let a = NeuralNetwork::new(&[3, 5, 2]).with_initializer(Initializer::HeNormal, Initializer::Zeros, 42);
+        let b = NeuralNetwork::new(&[3, 5, 2]).with_initializer(Initializer::HeNormal, Initializer::Zeros, 42);
+        let c = NeuralNetwork::new(&[3, 5, 2]).with_initializer(Initializer::HeNormal, Initializer::Zeros, 43);
+
+        for (la, lb) in a.layers.iter().zip(&b.layers) {
+            assert_eq!(la.weights, lb.weights);
+            assert!(la.weights.get_column(0).iter().all(|&bias| bias == 0.0));
+        }
+        assert_ne!(a.layers[0].weights, c.layers[0].weights);
+        assert_eq!(a.layers[1].weights.col_count(), 6);
+    }
+
+    #[test]
+    fn test_constant_schemes_and_single_layer() {
+        let mut nn = NeuralNetwork::new(&[2, 4, 1]);
+        let untouched = nn.layers[1].weights.clone();
+        let mut rng = StdRng::seed_from_u64(5);
+        assert_eq!(Initializer::Zeros.initialize(3, 2, &mut rng), Matrix::zeros(2, 3));
+        assert!(entries(&Initializer::Constant(0.5).initialize(3, 2, &mut rng)).iter().all(|&w| w == 0.5));
+
+        nn.initialize_layer(0, &Initializer::Constant(0.25), &Initializer::Constant(-1.0), &mut rng);
+
+        assert_eq!(nn.layers[0].weights[(2, 0)], -1.0);
+        assert_eq!(nn.layers[0].weights[(2, 1)], 0.25);
+        assert_eq!(nn.layers[1].weights, untouched);
+    }
+
+    #[test]
+    fn test_biases_use_the_same_fans_in_every_layer_type() {
+        // He normal biases have fan_in 1, so variance 2, wherever they live
+        let mut rng = StdRng::seed_from_u64(13);
+        let dense = Dense::with_initializer(50, 400, &Initializer::Zeros, &Initializer::HeNormal, &mut rng);
+        let conv = Conv2D::with_initializer((1, 3, 3), 400, 2, &Initializer::Zeros, &Initializer::HeNormal, &mut rng);
+        let network = NeuralNetwork::new(&[50, 400]).with_initializer(Initializer::Zeros, Initializer::HeNormal, 13);
+        assert_eq!((dense.bias.row_count(), dense.bias.col_count()), (1, 400));
+        for biases in [entries(&dense.bias), entries(&conv.bias), network.layers[0].weights.get_column(0).data] {
+            let var = variance(&biases);
+            assert!((var - 2.0).abs() < 0.3, "variance {}", var);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Uniform initializer needs low < high")]
+    fn test_empty_uniform_range_is_rejected() {
+        Initializer::Uniform { low: 0.5, high: 0.5 }.initialize(2, 2, &mut StdRng::seed_from_u64(0));
+    }
+}
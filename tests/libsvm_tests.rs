@@ -0,0 +1,113 @@
+#[allow(dead_code)]
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{temp_path, write_temp};
+    use rustbrain::datasets::{read_libsvm, write_libsvm, IndexBase, LibsvmOptions, SvmLightData};
+    use rustbrain::math::{Matrix, Vector};
+    use rustbrain::svm::SoftMarginSVM;
+
+    #[test]
+    fn test_read_labels_features_and_qids() {
+        let path = write_temp("one_based.txt", "# header comment\n+1 1:0.5 3:2\n-1 2:-1.25 # trailing comment\n\n-1\n");
+        let data = read_libsvm(&path, &LibsvmOptions::default()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(data.len(), 3);
+        assert_eq!(data.labels.data, vec![1.0, -1.0, -1.0]);
+        assert_eq!(data.features.col_count(), 3);
+        assert_eq!(data.features.nnz(), 3);
+        assert_eq!(data.to_dense().rows[0].data, vec![0.5, 0.0, 2.0]);
+        assert_eq!(data.to_dense().rows[1].data, vec![0.0, -1.25, 0.0]);
+        assert_eq!(data.to_dense().rows[2].data, vec![0.0, 0.0, 0.0]);
+        assert!(data.qids.is_none());
+
+        // Ranking data, where lines without a qid get 0
+        let path = write_temp("qid.txt", "3 qid:1 1:0.1 2:0.2\n2 qid:1 1:0.3\n1 qid:2 2:0.4\n0 1:0.5\n");
+        let data = read_libsvm(&path, &LibsvmOptions::default()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(data.qids, Some(vec![1, 1, 2, 0]));
+        assert_eq!(data.labels.data, vec![3.0, 2.0, 1.0, 0.0]);
+        assert_eq!(data.features.get(2, 1), 0.4);
+    }
+
+    #[test]
+    fn test_index_base_detection_and_n_features() {
+        let path = write_temp("zero_based.txt", "0 0:1 2:3\n1 1:4\n");
+        let auto = read_libsvm(&path, &LibsvmOptions::default()).unwrap();
+        assert_eq!(auto.to_dense().rows[0].data, vec![1.0, 0.0, 3.0]);
+
+        let padded = read_libsvm(&path, &LibsvmOptions::default().with_index_base(IndexBase::Zero).with_n_features(5)).unwrap();
+        assert_eq!(padded.to_dense().rows[1].data, vec![0.0, 4.0, 0.0, 0.0, 0.0]);
+
+        assert!(read_libsvm(&path, &LibsvmOptions::default().with_index_base(IndexBase::One)).unwrap_err().contains("index 0"));
+        assert!(read_libsvm(&path, &LibsvmOptions::default().with_n_features(2)).unwrap_err().contains("n_features is 2"));
+        std::fs::remove_file(&path).unwrap();
+
+        // Without a zero index, auto means one-based
+        let path = write_temp("auto_one.txt", "1 1:1 2:2\n");
+        assert_eq!(read_libsvm(&path, &LibsvmOptions::default()).unwrap().to_dense().rows[0].data, vec![1.0, 2.0]);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_write_read_round_trip() {
+        let features = Matrix::new(vec![vec![0.0, 1.5, 0.0, -2.0], vec![0.1, 0.0, 0.0, 0.0], vec![0.0, 0.0, 0.0, 1e-12]]);
+        let mut data = SvmLightData::from_dense(&features, &Vector::new(vec![1.0, -1.0, 2.5]));
+        data.qids = Some(vec![7, 7, 8]);
+
+        for zero_based in [false, true] {
+            let path = temp_path(&format!("round_trip_{}.txt", zero_based));
+            write_libsvm(&path, &data, zero_based).unwrap();
+            let contents = std::fs::read_to_string(&path).unwrap();
+            let base = if zero_based { IndexBase::Zero } else { IndexBase::One };
+            let restored = read_libsvm(&path, &LibsvmOptions::default().with_index_base(base).with_n_features(4)).unwrap();
+            std::fs::remove_file(&path).unwrap();
+
+            let first_line = if zero_based { "1 qid:7 1:1.5 3:-2" } else { "1 qid:7 2:1.5 4:-2" };
+            assert_eq!(contents.lines().next(), Some(first_line));
+            assert_eq!(restored, data);
+        }
+    }
+
+    #[test]
+    fn test_malformed_lines() {
+        let cases = [
+            ("label", "yes 1:1\n", "line 1: invalid label \"yes\""),
+            ("pair", "1 1:1\n1 2\n", "line 2: expected index:value"),
+            ("order", "1 3:1 2:1\n", "index 2 does not increase"),
+            ("duplicate", "1 2:1 2:5\n", "index 2 does not increase"),
+            ("value", "1 1:abc\n", "invalid value \"abc\""),
+            ("qid", "1 1:1 qid:3\n", "qid must come once"),
+        ];
+        for (name, contents, expected) in cases {
+            let path = write_temp(&format!("{}.txt", name), contents);
+            let error = read_libsvm(&path, &LibsvmOptions::default()).unwrap_err();
+            std::fs::remove_file(&path).unwrap();
+            assert!(error.contains(expected), "{}: {}", name, error);
+        }
+        assert!(read_libsvm(temp_path("missing.txt"), &LibsvmOptions::default()).unwrap_err().starts_with("Cannot read"));
+    }
+
+    #[test]
+    fn test_soft_margin_svm_on_libsvm_file() {
+        // Two well separated clusters, written the way LIBSVM datasets ship
+        let mut contents = String::new();
+        for i in 0..40 {
+            let (label, center) = if i % 2 == 0 { ("+1", 2.0) } else { ("-1", -2.0) };
+            let jitter = (i as f64 * 0.37).sin() * 0.5;
+            contents.push_str(&format!("{} 1:{} 2:{}\n", label, center + jitter, center - jitter));
+        }
+        let path = write_temp("svm.txt", &contents);
+        let data = read_libsvm(&path, &LibsvmOptions::default()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let inputs = data.to_dense().rows;
+        let mut svm = SoftMarginSVM::new(2, 0.01, 50, 1.0);
+        svm.fit(&inputs, &data.labels);
+        let correct = inputs.iter().zip(data.labels.iter()).filter(|(x, y)| svm.predict(x) as f64 == **y).count();
+        assert_eq!(correct, 40);
+    }
+}
@@ -0,0 +1,113 @@
+#[cfg(test)]
+mod tests {
+    use rustbrain::math::{Matrix, Vector};
+    use rustbrain::optimize::{LPStatus, LinearProgram};
+
+    fn assert_close(x: &Vector, expected: &[f64], tol: f64) {
+        assert_eq!(x.len(), expected.len());
+        for (v, e) in x.iter().zip(expected) {
+            assert!((v - e).abs() < tol, "{:?} != {:?}", x.data, expected);
+        }
+    }
+
+    fn matrix(rows: &[&[f64]]) -> Matrix {
+        Matrix::from_vector(rows.iter().map(|r| Vector::new(r.to_vec())).collect())
+    }
+
+    #[test]
+    fn test_inequality_form_with_shadow_prices() {
+        // Maximize 3x + 5y s.t. x ≤ 4, 2y ≤ 12, 3x + 2y ≤ 18, x, y ≥ 0
+        let program = LinearProgram::new(Vector::new(vec![-3.0, -5.0]))
+            .with_inequality(matrix(&[&[1.0, 0.0], &[0.0, 2.0], &[3.0, 2.0]]), Vector::new(vec![4.0, 12.0, 18.0]));
+        let solution = program.solve(100);
+        assert_eq!(solution.status, LPStatus::Optimal);
+        assert_close(&solution.x, &[2.0, 6.0], 1e-9);
+        assert!((solution.objective + 36.0).abs() < 1e-9);
+        assert_close(&solution.inequality_duals, &[0.0, 1.5, 1.0], 1e-9);
+        assert_close(&solution.bound_duals, &[0.0, 0.0], 1e-9);
+    }
+
+    #[test]
+    fn test_general_bounds() {
+        // min -x₁ - x₂ s.t. x₁ + 2x₂ ≤ 4, -1 ≤ x₁ ≤ 1, x₂ ≤ 3 (x₂ unbounded below)
+        let program = LinearProgram::new(Vector::new(vec![-1.0, -1.0]))
+            .with_inequality(matrix(&[&[1.0, 2.0]]), Vector::new(vec![4.0]))
+            .with_bounds(Vector::new(vec![-1.0, f64::NEG_INFINITY]), Vector::new(vec![1.0, 3.0]));
+        let solution = program.solve(100);
+        assert_eq!(solution.status, LPStatus::Optimal);
+        assert_close(&solution.x, &[1.0, 1.5], 1e-9);
+        // -1 + λ + μ₁ = 0 and -1 + 2λ = 0: the upper bound on x₁ is worth 0.5
+        assert_close(&solution.inequality_duals, &[0.5], 1e-9);
+        assert_close(&solution.bound_duals, &[0.5, 0.0], 1e-9);
+    }
+
+    #[test]
+    fn test_resource_allocation_with_redundant_equality() {
+        // Cover a demand of 10 units from three sources with costs 2, 3 and 1;
+        // the cheapest source supplies at most 5. The second equality repeats the first.
+        let program = LinearProgram::new(Vector::new(vec![2.0, 3.0, 1.0]))
+            .with_equality(matrix(&[&[1.0, 1.0, 1.0], &[2.0, 2.0, 2.0]]), Vector::new(vec![10.0, 20.0]))
+            .with_inequality(matrix(&[&[0.0, 0.0, 1.0]]), Vector::new(vec![5.0]));
+        let solution = program.solve(100);
+        assert_eq!(solution.status, LPStatus::Optimal);
+        assert_close(&solution.x, &[5.0, 0.0, 5.0], 1e-9);
+        assert!((solution.objective - 15.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_l1_regression_ignores_outlier() {
+        // min Σ |y - a - b t| as an LP in (a, b, e⁺, e⁻) with a, b free
+        let t = [0.0, 1.0, 2.0, 3.0, 4.0];
+        let y = [1.0, 3.0, 5.0, 7.0, 100.0];
+        let n = t.len();
+        let mut c = vec![0.0, 0.0];
+        c.extend(vec![1.0; 2 * n]);
+        let rows: Vec<Vector> = (0..n)
+            .map(|i| {
+                let mut row = vec![0.0; 2 + 2 * n];
+                row[0] = 1.0;
+                row[1] = t[i];
+                row[2 + i] = 1.0;
+                row[2 + n + i] = -1.0;
+                Vector::new(row)
+            })
+            .collect();
+        let mut lower = vec![f64::NEG_INFINITY; 2];
+        lower.extend(vec![0.0; 2 * n]);
+        let program = LinearProgram::new(Vector::new(c))
+            .with_equality(Matrix::from_vector(rows), Vector::new(y.to_vec()))
+            .with_bounds(Vector::new(lower), Vector::new(vec![f64::INFINITY; 2 + 2 * n]));
+        let solution = program.solve(1000);
+        assert_eq!(solution.status, LPStatus::Optimal);
+        assert_close(&Vector::new(solution.x.data[..2].to_vec()), &[1.0, 2.0], 1e-9);
+        assert!((solution.objective - 91.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_infeasible_and_unbounded() {
+        let infeasible = LinearProgram::new(Vector::new(vec![1.0, 1.0]))
+            .with_equality(matrix(&[&[1.0, 1.0]]), Vector::new(vec![3.0]))
+            .with_inequality(matrix(&[&[1.0, 1.0]]), Vector::new(vec![1.0]));
+        assert_eq!(infeasible.solve(100).status, LPStatus::Infeasible);
+
+        // min -x₁ s.t. x₁ - x₂ ≤ 1 grows without bound along (1, 1)
+        let unbounded = LinearProgram::new(Vector::new(vec![-1.0, 0.0])).with_inequality(matrix(&[&[1.0, -1.0]]), Vector::new(vec![1.0]));
+        assert_eq!(unbounded.solve(100).status, LPStatus::Unbounded);
+
+        let limited = LinearProgram::new(Vector::new(vec![-1.0, -1.0])).with_inequality(matrix(&[&[1.0, 1.0]]), Vector::new(vec![1.0]));
+        assert_eq!(limited.solve(0).status, LPStatus::MaxIterations);
+    }
+
+    #[test]
+    fn test_bland_rule_terminates_on_beale_example() {
+        // Beale's example cycles under Dantzig's rule; the optimum is -1/20
+        let program = LinearProgram::new(Vector::new(vec![-0.75, 150.0, -0.02, 6.0])).with_inequality(
+            matrix(&[&[0.25, -60.0, -0.04, 9.0], &[0.5, -90.0, -0.02, 3.0], &[0.0, 0.0, 1.0, 0.0]]),
+            Vector::new(vec![0.0, 0.0, 1.0]),
+        );
+        let solution = program.solve(100);
+        assert_eq!(solution.status, LPStatus::Optimal);
+        assert!((solution.objective + 0.05).abs() < 1e-9, "{}", solution.objective);
+        assert_close(&solution.x, &[0.04, 0.0, 1.0, 0.0], 1e-9);
+    }
+}
@@ -126,4 +126,77 @@ use rustbrain::{LogisticRegression, SoftmaxRegression};
         );
 
     }
-}
\ No newline at end of file
+
+    /// Binary feature with known class frequencies: 1/4 positives when
+    /// x = 0 and 3/5 when x = 1. The unpenalized maximum likelihood solution
+    /// is bias = logit(1/4), slope = logit(3/5) - logit(1/4).
+    fn frequency_data() -> (Vec<Vector>, Vector) {
+        let xs = [0.0, 0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 1.0, 1.0];
+        let ys = [1.0, 0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 0.0, 0.0];
+        (xs.iter().map(|&x| Vector::new(vec![x])).collect(), Vector::new(ys.to_vec()))
+    }
+
+    #[test]
+    fn test_newton_and_lbfgs_reach_the_maximum_likelihood_solution() {
+        let (inputs, targets) = frequency_data();
+        let logit = |p: f64| (p / (1.0 - p)).ln();
+        let expected = [logit(0.25), logit(0.6) - logit(0.25)];
+
+        let mut newton = LogisticRegression::new(1, 0.0, 0.0);
+        let status = newton.fit_newton(&inputs, &targets, 1e-10, 50).unwrap();
+        assert!(status.converged && status.iterations < 10 && status.gradient_norm < 1e-10);
+
+        let mut lbfgs = LogisticRegression::new(1, 0.0, 0.0);
+        let status = lbfgs.fit_lbfgs(&inputs, &targets, 1e-10, 200);
+        assert!(status.converged, "{:?}", status);
+        for (j, expected) in expected.iter().enumerate() {
+            assert!((newton.weights[j] - expected).abs() < 1e-9);
+            assert!((lbfgs.weights[j] - expected).abs() < 1e-8);
+        }
+
+        // Both solvers start from zero, so refitting is exactly reproducible
+        let weights = lbfgs.weights.clone();
+        lbfgs.fit_lbfgs(&inputs, &targets, 1e-10, 200);
+        assert_eq!(lbfgs.weights, weights);
+    }
+
+    #[test]
+    fn test_l2_penalty_makes_separable_data_solvable() {
+        let inputs = vec![
+            Vector::new(vec![0.0, 0.0]),
+            Vector::new(vec![0.0, 1.0]),
+            Vector::new(vec![1.0, 0.0]),
+            Vector::new(vec![1.0, 1.0]),
+        ];
+        let targets = Vector::new(vec![0.0, 0.0, 0.0, 1.0]);
+
+        // Without a penalty the weights grow until the gradient vanishes in
+        // floating point, far from any finite optimum
+        let mut model = LogisticRegression::new(2, 0.0, 0.0);
+        let status = model.fit_newton(&inputs, &targets, 1e-8, 20).unwrap();
+        assert!(status.converged && status.iterations > 10);
+        assert!(model.weights.norm() > 50.0);
+        let status = model.fit_lbfgs(&inputs, &targets, 1e-12, 5);
+        assert!(!status.converged && status.iterations == 5);
+
+        // scikit-learn's default C = 1 corresponds to l2_lambda = 1 / (2 n C)
+        let mut newton = LogisticRegression::new(2, 0.0, 1.0 / 8.0);
+        assert!(newton.fit_newton(&inputs, &targets, 1e-10, 50).unwrap().converged);
+        let mut lbfgs = LogisticRegression::new(2, 0.0, 1.0 / 8.0);
+        assert!(lbfgs.fit_lbfgs(&inputs, &targets, 1e-10, 200).converged);
+        for j in 0..3 {
+            assert!((newton.weights[j] - lbfgs.weights[j]).abs() < 1e-8);
+        }
+        // Symmetric features get equal weights
+        assert!((newton.weights[1] - newton.weights[2]).abs() < 1e-10);
+        assert!(newton.gradient(&inputs, &targets).iter().all(|g| g.abs() < 1e-10));
+    }
+
+    #[test]
+    #[should_panic(expected = "set l1_lambda to 0")]
+    fn test_newton_rejects_l1() {
+        let (inputs, targets) = frequency_data();
+        let mut model = LogisticRegression::new(1, 0.1, 0.0);
+        let _ = model.fit_newton(&inputs, &targets, 1e-8, 10);
+    }
+}
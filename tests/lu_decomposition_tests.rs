@@ -1,6 +1,6 @@
 #[cfg(test)]
 mod tests {
-    use rustbrain::math::Matrix;
+    use rustbrain::math::{Matrix, Vector};
 
 #[test]
 fn test_lu_decomposition_triangular_forms() {
@@ -36,4 +36,26 @@ fn test_lu_decomposition_triangular_forms() {
         }
     }
 }
+
+#[test]
+fn test_lu_factorization_solves_both_systems() {
+    // The first column needs a row swap
+    let a = Matrix::new(vec![
+        vec![0.0, 2.0, 1.0],
+        vec![1.0, 1.0, 0.0],
+        vec![3.0, 0.0, 1.0],
+    ]);
+    let factorization = a.lu_factorization().unwrap();
+    let x = Vector::new(vec![1.0, -2.0, 3.0]);
+
+    let solved = factorization.solve(&a.gemv(&x));
+    let solved_transpose = factorization.solve_transpose(&a.transpose().gemv(&x));
+    for i in 0..3 {
+        assert!((solved[i] - x[i]).abs() < 1e-12, "{:?}", solved.data);
+        assert!((solved_transpose[i] - x[i]).abs() < 1e-12, "{:?}", solved_transpose.data);
+    }
+
+    let singular = Matrix::new(vec![vec![1.0, 2.0], vec![2.0, 4.0]]);
+    assert!(singular.lu_factorization().is_err());
+}
 }
@@ -4,6 +4,7 @@ mod tests {
     use approx::assert_relative_eq;
     use rustbrain::math::Matrix;
     use rustbrain::math::Vector;
+    use rustbrain::math::SparseMatrix;
     #[test]
     fn test_matrix_vector_multiplication() {
         let a = Matrix::new(vec![
@@ -424,5 +425,67 @@ mod tests {
         assert_ne!(ab, ba);
     }
 
+    #[test]
+    fn test_cholesky() {
+        let a = Matrix::new(vec![
+            vec![4.0, 12.0, -16.0],
+            vec![12.0, 37.0, -43.0],
+            vec![-16.0, -43.0, 98.0]
+        ]);
+        let l = a.cholesky().unwrap();
+        assert_eq!(l, Matrix::new(vec![
+            vec![2.0, 0.0, 0.0],
+            vec![6.0, 1.0, 0.0],
+            vec![-8.0, 5.0, 3.0]
+        ]));
+
+        let x = a.cholesky_solve(&Vector::new(vec![1.0, 2.0, 3.0])).unwrap();
+        let b = a.gemv(&x);
+        for (bi, expected) in b.iter().zip([1.0, 2.0, 3.0]) {
+            assert!((bi - expected).abs() < 1e-9);
+        }
+
+        let indefinite = Matrix::new(vec![vec![1.0, 2.0], vec![2.0, 1.0]]);
+        assert!(indefinite.cholesky().is_err());
+    }
+
+    #[test]
+    fn test_sparse_matrix() {
+        let dense = Matrix::new(vec![
+            vec![0.0, 2.0, 0.0],
+            vec![0.0, 0.0, 0.0],
+            vec![1.0, 0.0, 3.0]
+        ]);
+        let sparse = SparseMatrix::from_dense(&dense);
+        assert_eq!(sparse.row_count(), 3);
+        assert_eq!(sparse.nnz(), 3);
+        assert_eq!(sparse.indptr, vec![0, 1, 1, 3]);
+        assert_eq!(sparse.get(2, 2), 3.0);
+        assert_eq!(sparse.get(1, 0), 0.0);
+        assert_eq!(sparse.to_dense(), dense);
+        let v = Vector::new(vec![1.0, 2.0, 3.0]);
+        assert_eq!(sparse.gemv(&v), dense.gemv(&v));
+
+        let mut built = SparseMatrix::new(3);
+        built.push_row(vec![(1, 2.0)]);
+        built.push_row(vec![]);
+        built.push_row(vec![(2, 3.0), (0, 1.0)]);
+        assert_eq!(built, sparse);
+    }
+
+    #[test]
+    fn test_column_blocks() {
+        let mut m = Matrix::new(vec![
+            vec![1.0, 2.0, 3.0, 4.0],
+            vec![5.0, 6.0, 7.0, 8.0]
+        ]);
+        let block = m.columns(1, 3);
+        assert_eq!(block, Matrix::new(vec![vec![2.0, 3.0], vec![6.0, 7.0]]));
+        assert_eq!(m.columns(2, 2).col_count(), 0);
+
+        m.set_columns(2, &block);
+        assert_eq!(m.rows[0].data, vec![1.0, 2.0, 2.0, 3.0]);
+        assert_eq!(m.rows[1].data, vec![5.0, 6.0, 6.0, 7.0]);
+    }
+
 }
-    
\ No newline at end of file
@@ -0,0 +1,173 @@
+#[cfg(test)]
+mod tests {
+    use rustbrain::math::Vector;
+    use rustbrain::neuralnetwork::{BatchNorm, Initializer, LayerNorm, NeuralNetwork, Normalization};
+
+    fn sample_batch() -> Vec<Vector> {
+        vec![
+            Vector::new(vec![1.0, -2.0, 0.5]),
+            Vector::new(vec![3.0, 0.0, -1.5]),
+            Vector::new(vec![-1.0, 4.0, 2.0]),
+            Vector::new(vec![0.5, 1.0, 0.0]),
+        ]
+    }
+
+    /// Fixed weights defining the scalar loss L = Σ c_ij * y_ij.
+    fn loss_weights() -> Vec<Vector> {
+        vec![
+            Vector::new(vec![0.3, -1.2, 0.7]),
+            Vector::new(vec![-0.4, 0.9, 0.1]),
+            Vector::new(vec![1.1, 0.2, -0.6]),
+            Vector::new(vec![-0.8, 0.5, 0.4]),
+        ]
+    }
+
+    fn loss(outputs: &[Vector]) -> f64 {
+        outputs.iter().zip(loss_weights().iter()).map(|(y, c)| y.dot(c)).sum()
+    }
+
+    /// Compares the analytic input gradient of `norm` against central differences.
+    fn check_input_gradient(norm: &mut Normalization) {
+        let batch = sample_batch();
+        norm.forward(&batch, true);
+        let grad = norm.backward(&loss_weights());
+
+        let h = 1e-6;
+        for s in 0..batch.len() {
+            for j in 0..batch[s].len() {
+                let mut plus = batch.clone();
+                plus[s][j] += h;
+                let mut minus = batch.clone();
+                minus[s][j] -= h;
+                let numeric = (loss(&norm.forward(&plus, true)) - loss(&norm.forward(&minus, true))) / (2.0 * h);
+                assert!(
+                    (numeric - grad[s][j]).abs() < 1e-5,
+                    "Gradient mismatch at ({}, {}): analytic {} vs numeric {}",
+                    s, j, grad[s][j], numeric
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_batch_norm_standardizes_features() {
+        let mut bn = BatchNorm::new(3);
+        let output = bn.forward(&sample_batch(), true);
+
+        for j in 0..3 {
+            let mean = output.iter().map(|y| y[j]).sum::<f64>() / 4.0;
+            let var = output.iter().map(|y| (y[j] - mean).powi(2)).sum::<f64>() / 4.0;
+            assert!(mean.abs() < 1e-9, "Feature {} mean is {}", j, mean);
+            assert!((var - 1.0).abs() < 1e-3, "Feature {} variance is {}", j, var);
+        }
+    }
+
+    #[test]
+    fn test_batch_norm_running_statistics() {
+        let mut bn = BatchNorm::new(3);
+        bn.momentum = 0.0; // Running statistics track the last batch exactly
+        let batch = sample_batch();
+        let training_output = bn.forward(&batch, true);
+
+        assert!((bn.running_mean[0] - 0.875).abs() < 1e-12);
+        // Evaluation mode with the same statistics reproduces the training output
+        let eval_output = bn.forward(&batch, false);
+        for (a, b) in training_output.iter().zip(eval_output.iter()) {
+            for j in 0..3 {
+                assert!((a[j] - b[j]).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn test_batch_norm_backward() {
+        let mut norm = Normalization::Batch(BatchNorm::new(3));
+        if let Normalization::Batch(bn) = &mut norm {
+            bn.gamma = Vector::new(vec![1.5, -0.5, 2.0]);
+            bn.beta = Vector::new(vec![0.1, 0.2, -0.3]);
+        }
+        check_input_gradient(&mut norm);
+
+        if let Normalization::Batch(bn) = &norm {
+            // dL/dbeta is the column sum of the upstream gradient
+            assert!((bn.grad_beta[0] - 0.2).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_layer_norm_standardizes_samples() {
+        let ln = LayerNorm::new(3);
+        for x in sample_batch() {
+            let y = ln.normalize(&x);
+            let mean = y.iter().sum::<f64>() / 3.0;
+            let var = y.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / 3.0;
+            assert!(mean.abs() < 1e-9);
+            assert!((var - 1.0).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_layer_norm_backward() {
+        let mut ln = LayerNorm::new(3);
+        ln.gamma = Vector::new(vec![0.5, 1.5, -1.0]);
+        let mut norm = Normalization::Layer(ln);
+        check_input_gradient(&mut norm);
+    }
+
+    #[test]
+    fn test_deep_network_with_normalization_trains() {
+        let inputs = vec![
+            Vector::new(vec![0.0, 0.0]),
+            Vector::new(vec![0.0, 1.0]),
+            Vector::new(vec![1.0, 0.0]),
+            Vector::new(vec![1.0, 1.0]),
+        ];
+        let targets = vec![
+            Vector::new(vec![0.0]),
+            Vector::new(vec![0.0]),
+            Vector::new(vec![0.0]),
+            Vector::new(vec![1.0]),
+        ];
+        let error = |nn: &NeuralNetwork| -> f64 {
+            inputs
+                .iter()
+                .zip(&targets)
+                .map(|(x, t)| (nn.predict(x)[0] - t[0]).powi(2))
+                .sum()
+        };
+
+        for mut nn in [
+            NeuralNetwork::new(&[2, 8, 8, 8, 1]).with_batch_norm(),
+            NeuralNetwork::new(&[2, 8, 8, 8, 1]).with_layer_norm(),
+        ] {
+            nn.train_batch(&inputs, &targets, 1.0, 4, 2000);
+            assert!(error(&nn) < 0.1, "Normalized network failed to fit AND, error {}", error(&nn));
+        }
+    }
+
+    #[test]
+    fn test_batch_norm_merges_trailing_single_sample() {
+        // 5 samples in batches of 4 train exactly like one batch of 5
+        let inputs: Vec<Vector> = (0..5).map(|i| Vector::new(vec![i as f64 * 0.3, 1.0 - i as f64 * 0.2])).collect();
+        let targets: Vec<Vector> = (0..5).map(|i| Vector::new(vec![(i % 2) as f64])).collect();
+        let network = || NeuralNetwork::new(&[2, 4, 1]).with_initializer(Initializer::HeNormal, Initializer::Zeros, 9).with_batch_norm();
+        let (mut chunked, mut whole) = (network(), network());
+        chunked.train_batch(&inputs, &targets, 0.1, 4, 20);
+        whole.train_batch(&inputs, &targets, 0.1, 5, 20);
+
+        let Some(Normalization::Batch(norm)) = &chunked.normalizations[0] else { panic!("Expected batch normalization") };
+        assert!(norm.running_var.iter().all(|&v| v > 1e-3), "{:?}", norm.running_var.data);
+        for x in &inputs {
+            assert_eq!(chunked.predict(x), whole.predict(x));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Batch normalization needs a batch size above 1")]
+    fn test_batch_norm_rejects_single_sample_training() {
+        let inputs = vec![Vector::new(vec![0.0, 1.0]), Vector::new(vec![1.0, 0.0])];
+        let targets = vec![Vector::new(vec![1.0]), Vector::new(vec![0.0])];
+        let mut nn = NeuralNetwork::new(&[2, 4, 1]).with_batch_norm();
+        nn.train(&inputs, &targets, 0.1, 1);
+    }
+}
@@ -0,0 +1,108 @@
+#[cfg(test)]
+mod tests {
+    use rustbrain::math::Vector;
+    use rustbrain::optimize::{minimize, minimize_with_options, LineSearch, Method, Options, Status};
+
+    fn rosenbrock(x: &Vector) -> f64 {
+        (1.0 - x[0]).powi(2) + 100.0 * (x[1] - x[0] * x[0]).powi(2)
+    }
+
+    fn rosenbrock_gradient(x: &Vector) -> Vector {
+        Vector::new(vec![-2.0 * (1.0 - x[0]) - 400.0 * x[0] * (x[1] - x[0] * x[0]), 200.0 * (x[1] - x[0] * x[0])])
+    }
+
+    /// Ill-conditioned quadratic ½ Σ i (x_i - 1)², minimized at x = 1.
+    fn quadratic(x: &Vector) -> f64 {
+        x.iter().enumerate().map(|(i, v)| 0.5 * (i + 1) as f64 * (v - 1.0).powi(2)).sum()
+    }
+
+    fn quadratic_gradient(x: &Vector) -> Vector {
+        Vector::new(x.iter().enumerate().map(|(i, v)| (i + 1) as f64 * (v - 1.0)).collect())
+    }
+
+    fn assert_close(x: &Vector, expected: &[f64], tol: f64) {
+        for (v, e) in x.iter().zip(expected) {
+            assert!((v - e).abs() < tol, "{:?} != {:?}", x.data, expected);
+        }
+    }
+
+    #[test]
+    fn test_quasi_newton_methods_on_rosenbrock() {
+        let x0 = Vector::new(vec![-1.2, 1.0]);
+        for method in [Method::BFGS, Method::LBFGS { memory: 5 }, Method::ConjugateGradient] {
+            let result = minimize(rosenbrock, rosenbrock_gradient, &x0, method.clone());
+            assert!(result.converged(), "{:?}: {:?}", method, result);
+            assert!(result.gradient_norm < 1e-6 && result.value < 1e-10);
+            assert_close(&result.x, &[1.0, 1.0], 1e-5);
+        }
+        let bfgs = minimize(rosenbrock, rosenbrock_gradient, &x0, Method::BFGS);
+        let gradient_descent =
+            minimize(rosenbrock, rosenbrock_gradient, &x0, Method::GradientDescent { line_search: LineSearch::Wolfe });
+        assert!(bfgs.iterations < 100);
+        assert!(gradient_descent.iterations > bfgs.iterations);
+    }
+
+    #[test]
+    fn test_gradient_descent_line_searches() {
+        let x0 = Vector::new(vec![5.0, -3.0, 0.0, 2.0]);
+        for line_search in [LineSearch::Armijo, LineSearch::Wolfe] {
+            let result = minimize(quadratic, quadratic_gradient, &x0, Method::GradientDescent { line_search });
+            assert_eq!(result.status, Status::Converged, "{:?}", line_search);
+            assert_close(&result.x, &[1.0; 4], 1e-6);
+            assert_eq!(result.gradient_norm, quadratic_gradient(&result.x).iter().fold(0.0f64, |m, g| m.max(g.abs())));
+        }
+        // Already at the minimum: no iterations
+        let result = minimize(quadratic, quadratic_gradient, &Vector::new(vec![1.0; 4]), Method::BFGS);
+        assert!(result.converged() && result.iterations == 0);
+    }
+
+    #[test]
+    fn test_conjugate_gradient_on_quadratic() {
+        // With exact line searches CG finishes a quadratic in n steps; allow a few more
+        let x0 = Vector::new(vec![0.0; 6]);
+        let result = minimize(quadratic, quadratic_gradient, &x0, Method::ConjugateGradient);
+        assert!(result.converged());
+        assert!(result.iterations <= 30, "{} iterations", result.iterations);
+        assert_close(&result.x, &[1.0; 6], 1e-6);
+    }
+
+    #[test]
+    fn test_nelder_mead() {
+        let options = Options { max_iter: 5000, tol: 1e-10 };
+        let result = minimize_with_options(rosenbrock, rosenbrock_gradient, &Vector::new(vec![-1.2, 1.0]), Method::NelderMead, options);
+        assert!(result.converged(), "{:?}", result);
+        assert_close(&result.x, &[1.0, 1.0], 1e-4);
+        // The report still includes the gradient at the solution
+        assert!(result.gradient_norm < 1e-2);
+    }
+
+    #[test]
+    fn test_projected_gradient_box_constraints() {
+        // Unconstrained minimum at 1 lies outside the box for the first two coordinates
+        let lower = Vector::new(vec![-1.0, 2.0, -5.0]);
+        let upper = Vector::new(vec![0.5, 3.0, 5.0]);
+        let x0 = Vector::new(vec![10.0, 10.0, 10.0]);
+        let method = Method::ProjectedGradient { lower, upper };
+        let result = minimize(quadratic, quadratic_gradient, &x0, method);
+        assert!(result.converged(), "{:?}", result);
+        assert_close(&result.x, &[0.5, 2.0, 1.0], 1e-8);
+        // The full gradient is not zero at the solution, but the projected gradient is
+        assert!(quadratic_gradient(&result.x)[0] < -0.4 && result.gradient_norm < 1e-6);
+    }
+
+    #[test]
+    fn test_iteration_limit() {
+        let options = Options { max_iter: 3, tol: 1e-12 };
+        let x0 = Vector::new(vec![-1.2, 1.0]);
+        let result = minimize_with_options(
+            rosenbrock,
+            rosenbrock_gradient,
+            &x0,
+            Method::GradientDescent { line_search: LineSearch::Armijo },
+            options,
+        );
+        assert_eq!(result.status, Status::MaxIterations);
+        assert_eq!(result.iterations, 3);
+        assert!(!result.converged() && result.value < rosenbrock(&x0));
+    }
+}
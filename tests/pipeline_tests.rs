@@ -0,0 +1,132 @@
+#[cfg(test)]
+mod tests {
+    use rustbrain::linear_regression::{LinearRegression, Ridge};
+    use rustbrain::logistic_regression::LogisticRegression;
+    use rustbrain::math::{Matrix, Vector};
+    use rustbrain::pipeline::{ColumnTransformer, Pipeline, Remainder, Transformer};
+    use rustbrain::preprocessing::{ImputeStrategy, OneHotEncoder, PolynomialFeatures, SimpleImputer, StandardScaler};
+
+    /// Rows of (size, category code, index) with a few missing sizes. The
+    /// price is linear in the size, taking the mean size where it is missing.
+    fn houses() -> (Matrix, Vector) {
+        let sizes: Vec<f64> = (0..40).map(|i| if i % 9 == 4 { f64::NAN } else { 50.0 + 3.0 * i as f64 }).collect();
+        let observed: Vec<f64> = sizes.iter().copied().filter(|s| !s.is_nan()).collect();
+        let mean = observed.iter().sum::<f64>() / observed.len() as f64;
+        let rows: Vec<Vec<f64>> = (0..40).map(|i| vec![sizes[i], (i % 3) as f64, i as f64]).collect();
+        let targets = Vector::new((0..40).map(|i| 10.0 + 0.5 * if sizes[i].is_nan() { mean } else { sizes[i] } + [0.0, 20.0, -5.0][i % 3]).collect());
+        (Matrix::new(rows), targets)
+    }
+
+    #[test]
+    fn test_pipeline_chains_transformers_and_estimator() {
+        let inputs = Matrix::new((0..20).map(|i| vec![i as f64 * 10.0]).collect());
+        let targets = Vector::new(inputs.rows.iter().map(|x| 3.0 + 0.02 * x[0] + 0.001 * x[0] * x[0]).collect());
+        let mut pipeline = Pipeline::new()
+            .with_step("scale", StandardScaler::new())
+            .with_step("poly", PolynomialFeatures::new(2).with_include_bias(false))
+            .with_estimator(LinearRegression::new());
+        pipeline.fit(&inputs, &targets).unwrap();
+
+        let unseen = Matrix::new(vec![vec![55.0], vec![120.0]]);
+        let predictions = pipeline.predict(&unseen);
+        for (x, p) in unseen.rows.iter().zip(predictions.iter()) {
+            assert!((p - (3.0 + 0.02 * x[0] + 0.001 * x[0] * x[0])).abs() < 1e-6);
+        }
+        assert_eq!(pipeline.transform(&unseen).col_count(), 2);
+        assert!(matches!(pipeline.step("scale"), Some(Transformer::StandardScaler(_))));
+        assert!(pipeline.step("missing").is_none());
+    }
+
+    #[test]
+    fn test_column_transformer_layout() {
+        let (inputs, _) = houses();
+        let mut columns = ColumnTransformer::new()
+            .with_transformer("size", SimpleImputer::new(ImputeStrategy::Constant(0.0)), &[0])
+            .with_transformer("kind", OneHotEncoder::new(), &[1])
+            .with_remainder(Remainder::Passthrough);
+        let output = columns.fit_transform(&inputs);
+        assert_eq!(columns.remainder_columns(), vec![2]);
+        assert_eq!(output.col_count(), 5);
+        assert_eq!(output.rows[4].data, vec![0.0, 0.0, 1.0, 0.0, 4.0]);
+        assert_eq!(output.rows[5].data, vec![65.0, 0.0, 0.0, 1.0, 5.0]);
+
+        let mut dropped = columns.clone().with_remainder(Remainder::Drop);
+        assert_eq!(dropped.fit_transform(&inputs).col_count(), 4);
+    }
+
+    #[test]
+    fn test_column_transformer_with_nested_pipeline() {
+        let (inputs, targets) = houses();
+        let numeric = Pipeline::new().with_step("impute", SimpleImputer::new(ImputeStrategy::Mean)).with_step("scale", StandardScaler::new());
+        let columns = ColumnTransformer::new().with_transformer("numeric", numeric, &[0]).with_transformer("kind", OneHotEncoder::new(), &[1]);
+        let mut pipeline = Pipeline::new().with_step("columns", columns).with_estimator(Ridge::new(1e-9));
+        pipeline.fit(&inputs, &targets).unwrap();
+
+        let predictions = pipeline.predict(&inputs);
+        for i in 0..40 {
+            assert!((predictions[i] - targets[i]).abs() < 1e-6, "row {}: {} != {}", i, predictions[i], targets[i]);
+        }
+    }
+
+    #[test]
+    fn test_classification_pipeline() {
+        // The class depends on a feature with a large offset
+        let inputs = Matrix::new((0..60).map(|i| vec![1000.0 + i as f64, ((i * 7) % 5) as f64]).collect());
+        let targets = Vector::new((0..60).map(|i| if i >= 30 { 1.0 } else { 0.0 }).collect());
+        let mut pipeline = Pipeline::new().with_step("scale", StandardScaler::new()).with_estimator(LogisticRegression::new(2, 0.0, 0.01));
+        pipeline.fit(&inputs, &targets).unwrap();
+        let predictions = pipeline.predict(&inputs);
+        let correct = predictions.iter().zip(targets.iter()).filter(|(p, t)| p == t).count();
+        assert!(correct >= 58, "{} of 60 correct", correct);
+    }
+
+    #[test]
+    fn test_classification_pipeline_rejects_l1_penalty() {
+        let inputs = Matrix::new((0..10).map(|i| vec![i as f64]).collect());
+        let targets = Vector::new((0..10).map(|i| if i >= 5 { 1.0 } else { 0.0 }).collect());
+        let mut pipeline = Pipeline::new().with_estimator(LogisticRegression::new(1, 0.1, 0.01));
+        assert!(pipeline.fit(&inputs, &targets).unwrap_err().contains("l1_lambda"));
+    }
+
+    #[test]
+    fn test_classification_pipeline_reports_non_convergence() {
+        // Overlapping classes on a feature in the millions, left unscaled
+        let inputs = Matrix::new((0..20).map(|i| vec![1e6 * i as f64]).collect());
+        let targets = Vector::new((0..20).map(|i| if (i >= 10) ^ (i == 9 || i == 10) { 1.0 } else { 0.0 }).collect());
+        let mut pipeline = Pipeline::new().with_estimator(LogisticRegression::new(1, 0.0, 0.0));
+        let error = pipeline.fit(&inputs, &targets).unwrap_err();
+        assert!(error.contains("did not converge"), "{}", error);
+
+        let mut scaled = Pipeline::new().with_step("scale", StandardScaler::new()).with_estimator(LogisticRegression::new(1, 0.0, 0.0));
+        scaled.fit(&inputs, &targets).unwrap();
+    }
+
+    #[test]
+    fn test_fitted_pipeline_serializes_as_one_artifact() {
+        let (inputs, targets) = houses();
+        let columns = ColumnTransformer::new()
+            .with_transformer("size", Pipeline::new().with_step("impute", SimpleImputer::new(ImputeStrategy::Median)).with_step("scale", StandardScaler::new()), &[0])
+            .with_transformer("kind", OneHotEncoder::new(), &[1]);
+        // The one-hot columns sum to one, so the intercept needs a little regularization
+        let mut pipeline = Pipeline::new().with_step("columns", columns).with_estimator(Ridge::new(1e-6));
+        pipeline.fit(&inputs, &targets).unwrap();
+
+        let json = serde_json::to_string(&pipeline).unwrap();
+        let restored: Pipeline = serde_json::from_str(&json).unwrap();
+        for (a, b) in restored.predict(&inputs).iter().zip(pipeline.predict(&inputs).iter()) {
+            assert!((a - b).abs() < 1e-9);
+        }
+        assert!(json.contains("\"columns\"") && json.contains("\"Ridge\""));
+    }
+
+    #[test]
+    fn test_pipeline_without_estimator() {
+        let (inputs, targets) = houses();
+        let mut pipeline = Pipeline::new().with_step("impute", SimpleImputer::new(ImputeStrategy::Mean));
+        assert_eq!(pipeline.fit(&inputs, &targets).unwrap_err(), "Pipeline has no estimator to fit");
+        let output = pipeline.fit_transform(&inputs);
+        assert!(output.rows.iter().all(|row| row.iter().all(|v| !v.is_nan())));
+        let result = std::panic::catch_unwind(|| pipeline.predict(&inputs));
+        assert!(result.is_err());
+    }
+}
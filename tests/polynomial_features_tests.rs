@@ -0,0 +1,86 @@
+#[cfg(test)]
+mod tests {
+    use rustbrain::linear_regression::LinearRegression;
+    use rustbrain::math::{Matrix, Vector};
+    use rustbrain::preprocessing::{PolynomialFeatures, SplineFeatures};
+
+    #[test]
+    fn test_polynomial_terms_and_names() {
+        let inputs = Matrix::new(vec![vec![2.0, 3.0]]);
+        let mut poly = PolynomialFeatures::new(2);
+        let output = poly.fit_transform(&inputs);
+        assert_eq!(output.rows[0].data, vec![1.0, 2.0, 3.0, 4.0, 6.0, 9.0]);
+        assert_eq!(poly.feature_names(None), vec!["1", "x0", "x1", "x0^2", "x0 x1", "x1^2"]);
+
+        let names = vec!["a".to_string(), "b".to_string()];
+        let mut cubic = PolynomialFeatures::new(3).with_include_bias(false);
+        cubic.fit(&inputs);
+        assert_eq!(cubic.output_size(), 9);
+        assert_eq!(cubic.feature_names(Some(&names))[5..], ["a^3", "a^2 b", "a b^2", "b^3"]);
+    }
+
+    #[test]
+    fn test_interaction_only() {
+        let inputs = Matrix::new(vec![vec![2.0, 3.0, 5.0]]);
+        let mut poly = PolynomialFeatures::new(3).with_interaction_only(true).with_include_bias(false);
+        let output = poly.fit_transform(&inputs);
+        assert_eq!(output.rows[0].data, vec![2.0, 3.0, 5.0, 6.0, 10.0, 15.0, 30.0]);
+        assert_eq!(poly.feature_names(None)[3..], ["x0 x1", "x0 x2", "x1 x2", "x0 x1 x2"]);
+    }
+
+    #[test]
+    fn test_linear_regression_fits_quadratic_trend() {
+        let inputs = Matrix::new((0..20).map(|i| vec![i as f64 / 4.0 - 2.0]).collect());
+        let targets = Vector::new(inputs.rows.iter().map(|x| 1.0 - 2.0 * x[0] + 0.5 * x[0] * x[0]).collect());
+        let mut poly = PolynomialFeatures::new(2).with_include_bias(false);
+        let features = poly.fit_transform(&inputs);
+
+        let mut model = LinearRegression::new();
+        model.fit(&features.rows, &targets);
+        for (w, expected) in model.weights.iter().zip([1.0, -2.0, 0.5]) {
+            assert!((w - expected).abs() < 1e-6, "{:?}", model.weights);
+        }
+    }
+
+    #[test]
+    fn test_spline_basis_is_partition_of_unity() {
+        let inputs = Matrix::new((0..=10).map(|i| vec![i as f64, 5.0]).collect());
+        let mut splines = SplineFeatures::new(4, 3);
+        let output = splines.fit_transform(&inputs);
+        assert_eq!(splines.basis_size(), 6);
+        assert_eq!(output.col_count(), 12);
+        for row in &output.rows {
+            assert!(row.iter().all(|&v| v >= 0.0));
+            assert!((row.data[..6].iter().sum::<f64>() - 1.0).abs() < 1e-12);
+            assert!((row.data[6..].iter().sum::<f64>() - 1.0).abs() < 1e-12);
+        }
+        // Values outside the fitted range are clamped to its ends
+        let outside = splines.transform(&Matrix::new(vec![vec![-3.0, 5.0], vec![14.0, 5.0]]));
+        assert_eq!(outside.rows[0], output.rows[0]);
+        assert_eq!(outside.rows[1], output.rows[10]);
+        assert_eq!(splines.feature_names(None)[7], "x1_sp1");
+    }
+
+    #[test]
+    fn test_spline_regression_fits_sine() {
+        let inputs = Matrix::new((0..60).map(|i| vec![i as f64 * 0.1]).collect());
+        let targets = Vector::new(inputs.rows.iter().map(|x| x[0].sin()).collect());
+        let mut splines = SplineFeatures::new(8, 3).with_include_bias(false);
+        let features = splines.fit_transform(&inputs);
+        assert_eq!(features.col_count(), 9);
+
+        let mut model = LinearRegression::new();
+        model.fit(&features.rows, &targets);
+        for (x, y) in features.rows.iter().zip(targets.iter()) {
+            assert!((model.predict(x) - y).abs() < 0.01);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Transformer was fitted on 2 features, got 3")]
+    fn test_transform_checks_feature_count() {
+        let mut poly = PolynomialFeatures::new(2);
+        poly.fit(&Matrix::new(vec![vec![1.0, 2.0]]));
+        poly.transform(&Matrix::new(vec![vec![1.0, 2.0, 3.0]]));
+    }
+}
@@ -0,0 +1,124 @@
+#[cfg(test)]
+mod tests {
+    use rustbrain::math::{Matrix, Vector};
+    use rustbrain::optimize::{QPSettings, QPStatus, QuadraticProgram};
+    use rustbrain::svm::qpsolver::QPSolver;
+
+    fn assert_close(x: &Vector, expected: &[f64], tol: f64) {
+        for (v, e) in x.iter().zip(expected) {
+            assert!((v - e).abs() < tol, "{:?} != {:?}", x.data, expected);
+        }
+    }
+
+    fn matrix(rows: &[&[f64]]) -> Matrix {
+        Matrix::from_vector(rows.iter().map(|r| Vector::new(r.to_vec())).collect())
+    }
+
+    #[test]
+    fn test_unconstrained() {
+        // Minimizer of ½ xᵀQx + pᵀx is -Q⁻¹p = (1, -1)
+        let q = matrix(&[&[4.0, 1.0], &[1.0, 2.0]]);
+        let p = Vector::new(vec![-3.0, 1.0]);
+        let solution = QuadraticProgram::new(q, p).solve(&QPSettings::default());
+        assert_eq!(solution.status, QPStatus::Solved);
+        assert_close(&solution.x, &[1.0, -1.0], 1e-5);
+        assert!((solution.objective + 2.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_equality_constrained() {
+        // min ½||x||² s.t. x₁ + x₂ + x₃ = 3: x = (1, 1, 1) with ν = -1
+        let program = QuadraticProgram::new(Matrix::identity(3), Vector::zeros(3))
+            .with_equality(matrix(&[&[1.0, 1.0, 1.0]]), Vector::new(vec![3.0]));
+        let solution = program.solve(&QPSettings::default());
+        assert_eq!(solution.status, QPStatus::Solved);
+        assert_close(&solution.x, &[1.0, 1.0, 1.0], 1e-5);
+        assert_close(&solution.equality_duals, &[-1.0], 1e-5);
+        assert!(solution.report.is_optimal(1e-5), "{:?}", solution.report);
+    }
+
+    #[test]
+    fn test_inequalities_and_bounds() {
+        // min (x₁ - 2)² + (x₂ - 2)² s.t. x₁ + x₂ ≤ 2, 0 ≤ x₂ ≤ 0.5
+        // Without the bound the optimum is (1, 1); the bound pushes it to (1.5, 0.5).
+        let program = QuadraticProgram::new(matrix(&[&[2.0, 0.0], &[0.0, 2.0]]), Vector::new(vec![-4.0, -4.0]))
+            .with_inequality(matrix(&[&[1.0, 1.0]]), Vector::new(vec![2.0]))
+            .with_bounds(Vector::new(vec![f64::NEG_INFINITY, 0.0]), Vector::new(vec![f64::INFINITY, 0.5]));
+        let solution = program.solve(&QPSettings::default());
+        assert_eq!(solution.status, QPStatus::Solved);
+        assert_close(&solution.x, &[1.5, 0.5], 1e-5);
+        // Stationarity: 2(x - 2) + λ (1, 1) + μ = 0 gives λ = 1, μ₂ = 2
+        assert_close(&solution.inequality_duals, &[1.0], 1e-4);
+        assert_close(&solution.bound_duals, &[0.0, 2.0], 1e-4);
+        assert!(solution.report.is_optimal(1e-5), "{:?}", solution.report);
+
+        // The report flags a point that is not optimal
+        let report = program.report(&Vector::new(vec![1.0, 1.0]), &Vector::zeros(0), &Vector::new(vec![2.0]), &Vector::zeros(2));
+        assert!((report.bound_violation - 0.5).abs() < 1e-12);
+        assert!(!report.is_optimal(1e-3));
+    }
+
+    #[test]
+    fn test_primal_infeasible() {
+        // x₁ + x₂ = 3 cannot hold with 0 ≤ x ≤ 1
+        let program = QuadraticProgram::new(Matrix::identity(2), Vector::zeros(2))
+            .with_equality(matrix(&[&[1.0, 1.0]]), Vector::new(vec![3.0]))
+            .with_bounds(Vector::zeros(2), Vector::new(vec![1.0, 1.0]));
+        let solution = program.solve(&QPSettings::default());
+        assert_eq!(solution.status, QPStatus::PrimalInfeasible);
+    }
+
+    #[test]
+    fn test_dual_infeasible() {
+        // min -x₁ + x₂ s.t. x₂ ≥ 0 is unbounded along x₁
+        let program = QuadraticProgram::new(Matrix::zeros(2, 2), Vector::new(vec![-1.0, 1.0]))
+            .with_bounds(Vector::new(vec![f64::NEG_INFINITY, 0.0]), Vector::new(vec![f64::INFINITY, f64::INFINITY]));
+        let solution = program.solve(&QPSettings::default());
+        assert_eq!(solution.status, QPStatus::DualInfeasible);
+    }
+
+    #[test]
+    fn test_non_convex() {
+        // Q is indefinite, so the ADMM system has no Cholesky factorization
+        let program = QuadraticProgram::new(matrix(&[&[-1.0, 0.0], &[0.0, 1.0]]), Vector::zeros(2));
+        let solution = program.solve(&QPSettings::default());
+        assert_eq!(solution.status, QPStatus::NonConvex);
+    }
+
+    #[test]
+    fn test_qpsolver_svm_dual() {
+        // Points x = 2 (y = +1) and x = -1 (y = -1): α₁ = α₂ = a maximizes 2a - 9a²/2, so a = 2/9
+        let q = matrix(&[&[4.0, 2.0], &[2.0, 1.0]]);
+        let y = Vector::new(vec![1.0, -1.0]);
+        let dual = QPSolver::svm_dual(q.clone(), y.clone(), 10.0);
+        assert_eq!(dual.p.data, vec![-1.0, -1.0]);
+        assert_eq!(dual.a[0].data, vec![1.0, -1.0]);
+        assert_eq!(dual.u.data, vec![10.0, 10.0]);
+
+        // The same problem built with `new` goes through ADMM, even though A is the labels
+        let mut general = QPSolver::new(q, dual.p.clone(), dual.a.clone(), dual.b.clone(), dual.l.clone(), dual.u.clone(), y);
+        assert_close(&general.solve(10_000, 1e-8), &[2.0 / 9.0, 2.0 / 9.0], 1e-5);
+    }
+
+    #[test]
+    fn test_qpsolver_honors_equality_constraints() {
+        // Minimum-variance portfolio: min ½ wᵀΣw s.t. Σ w = 1, 0 ≤ w ≤ 1.
+        // With diagonal Σ the weights are proportional to 1 / σ²: (4, 2, 1) / 7.
+        let q = matrix(&[&[1.0, 0.0, 0.0], &[0.0, 2.0, 0.0], &[0.0, 0.0, 4.0]]);
+        let mut solver = QPSolver::new(
+            q,
+            Vector::zeros(3),
+            matrix(&[&[1.0, 1.0, 1.0]]),
+            Vector::new(vec![1.0]),
+            Vector::zeros(3),
+            Vector::new(vec![1.0, 1.0, 1.0]),
+            Vector::new(vec![1.0, 1.0, 1.0]),
+        );
+        let weights = solver.solve(10_000, 1e-8);
+        assert_close(&weights, &[4.0 / 7.0, 2.0 / 7.0, 1.0 / 7.0], 1e-5);
+
+        let solution = solver.solve_with_report(&QPSettings::default());
+        assert_eq!(solution.status, QPStatus::Solved);
+        assert!(solution.report.equality_violation < 1e-6);
+    }
+}
@@ -0,0 +1,145 @@
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+    use rustbrain::math::{Matrix, Vector};
+    use rustbrain::neuralnetwork::{Layer, NeuralNetwork};
+    use rustbrain::rbm::{Training, VisibleUnits, RBM};
+
+    /// 3x3 images containing one horizontal or vertical bar.
+    fn bars() -> Vec<Vector> {
+        let mut images = Vec::new();
+        for k in 0..3 {
+            let horizontal = (0..9).map(|i| if i / 3 == k { 1.0 } else { 0.0 }).collect();
+            let vertical = (0..9).map(|i| if i % 3 == k { 1.0 } else { 0.0 }).collect();
+            images.push(Vector::new(horizontal));
+            images.push(Vector::new(vertical));
+        }
+        images
+    }
+
+    fn small_rbm(visible: VisibleUnits) -> RBM {
+        let mut rbm = RBM::new(3, 2, visible);
+        rbm.weights = Matrix::new(vec![vec![0.5, -1.0, 0.3], vec![-0.2, 0.8, 1.1]]);
+        rbm.visible_bias = Vector::new(vec![0.1, -0.3, 0.2]);
+        rbm.hidden_bias = Vector::new(vec![-0.4, 0.6]);
+        rbm
+    }
+
+    #[test]
+    fn test_free_energy_matches_enumeration() {
+        let v = Vector::new(vec![1.0, 0.0, 1.0]);
+        for visible in [VisibleUnits::Bernoulli, VisibleUnits::Gaussian] {
+            let rbm = small_rbm(visible);
+            let mut partition = 0.0;
+            for code in 0..4 {
+                let h = Vector::new(vec![(code & 1) as f64, (code >> 1) as f64]);
+                let visible_energy = match visible {
+                    VisibleUnits::Bernoulli => -rbm.visible_bias.dot(&v),
+                    VisibleUnits::Gaussian => v.iter().zip(rbm.visible_bias.iter()).map(|(a, b)| 0.5 * (a - b).powi(2)).sum(),
+                };
+                let energy = visible_energy - rbm.hidden_bias.dot(&h) - h.dot(&rbm.weights.gemv(&v));
+                partition += (-energy).exp();
+            }
+            assert!((rbm.free_energy(&v) + partition.ln()).abs() < 1e-12, "{:?}", visible);
+        }
+    }
+
+    #[test]
+    fn test_conditionals_and_sampling() {
+        let rbm = small_rbm(VisibleUnits::Bernoulli);
+        let v = Vector::new(vec![1.0, 0.0, 1.0]);
+        let p = rbm.hidden_probabilities(&v);
+        assert!((p[0] - 1.0 / (1.0 + (-0.4f64).exp())).abs() < 1e-12);
+        assert_eq!(rbm.transform(std::slice::from_ref(&v)), vec![p]);
+
+        let mut rng = StdRng::seed_from_u64(0);
+        let sample = rbm.gibbs_sample_with_rng(&v, 5, &mut rng);
+        assert_eq!(sample.len(), 3);
+        assert!(sample.iter().all(|&x| x == 0.0 || x == 1.0));
+
+        let gaussian = small_rbm(VisibleUnits::Gaussian);
+        let mean = gaussian.visible_mean(&Vector::new(vec![1.0, 0.0]));
+        assert_eq!(mean.data, vec![0.6, -1.3, 0.5]);
+    }
+
+    #[test]
+    fn test_cd_reduces_reconstruction_error() {
+        let data = bars();
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut rbm = RBM::new(9, 8, VisibleUnits::Bernoulli);
+        let before = rbm.reconstruction_error(&data);
+        let history = rbm.fit_with_rng(&data, Training::ContrastiveDivergence { k: 1 }, 0.1, 2000, 3, &mut rng);
+        assert!(history.last().unwrap() < &(before / 5.0), "{} -> {}", before, history.last().unwrap());
+    }
+
+    #[test]
+    fn test_pcd_learns_data_distribution() {
+        let data = bars();
+        let mut rng = StdRng::seed_from_u64(2);
+        let mut rbm = RBM::new(9, 8, VisibleUnits::Bernoulli);
+        rbm.fit_with_rng(&data, Training::PersistentContrastiveDivergence { k: 1 }, 0.05, 3000, 6, &mut rng);
+
+        // Training images are far more probable than random images
+        let mean_data = data.iter().map(|v| rbm.free_energy(v)).sum::<f64>() / data.len() as f64;
+        let noise: Vec<Vector> =
+            (0..50).map(|_| Vector::new((0..9).map(|_| rng.random_range(0..2) as f64).collect())).collect();
+        let mean_noise = noise.iter().map(|v| rbm.free_energy(v)).sum::<f64>() / noise.len() as f64;
+        assert!(mean_data + 2.0 < mean_noise, "F(data) = {}, F(noise) = {}", mean_data, mean_noise);
+    }
+
+    #[test]
+    fn test_pcd_keeps_its_chains_across_epochs() {
+        let data = bars();
+        let batch_size = 4;
+        assert_ne!(data.len() % batch_size, 0, "The last batch must be short");
+        let mut rng = StdRng::seed_from_u64(3);
+        let mut rbm = RBM::new(9, 8, VisibleUnits::Bernoulli);
+        assert!(rbm.chains().is_none());
+        for _ in 0..5 {
+            rbm.fit_with_rng(&data, Training::PersistentContrastiveDivergence { k: 1 }, 0.05, 1, batch_size, &mut rng);
+            assert_eq!(rbm.chains().unwrap().len(), batch_size);
+        }
+    }
+
+    #[test]
+    fn test_gaussian_bernoulli_rbm() {
+        // Two well separated clusters of standardized data
+        let mut rng = StdRng::seed_from_u64(4);
+        let data: Vec<Vector> = (0..40)
+            .map(|i| {
+                let center = if i % 2 == 0 { 1.0 } else { -1.0 };
+                Vector::new((0..4).map(|j| center * if j < 2 { 1.0 } else { -1.0 } + rng.random_range(-0.2..0.2)).collect())
+            })
+            .collect();
+        let mut rbm = RBM::new(4, 3, VisibleUnits::Gaussian);
+        let before = rbm.reconstruction_error(&data);
+        let history = rbm.fit_with_rng(&data, Training::ContrastiveDivergence { k: 1 }, 0.01, 300, 10, &mut rng);
+        assert!(history.last().unwrap() < &(before / 2.0), "{} -> {}", before, history.last().unwrap());
+
+        // The hidden code separates the clusters
+        let features = rbm.transform(&data[..2]);
+        let distance = features[0].add(&features[1].scale(-1.0)).norm();
+        assert!(distance > 0.5, "Hidden codes too similar: {:?}", features);
+    }
+
+    #[test]
+    fn test_pretraining_helpers() {
+        let rbm = small_rbm(VisibleUnits::Bernoulli);
+        let v = Vector::new(vec![0.2, 0.7, 1.0]);
+
+        let mut dense = rbm.to_dense();
+        let pre_activation = dense.forward(&Matrix::from_vector(vec![v.clone()]), false);
+        let hidden = rbm.hidden_probabilities(&v);
+        for j in 0..2 {
+            assert!((1.0 / (1.0 + (-pre_activation[(0, j)]).exp()) - hidden[j]).abs() < 1e-12);
+        }
+
+        let mut network = NeuralNetwork::new(&[3, 2, 1]);
+        rbm.initialize_layer(&mut network, 0);
+        let activations = network.forward(&v);
+        for j in 0..2 {
+            assert!((activations[0][j] - hidden[j]).abs() < 1e-12);
+        }
+    }
+}
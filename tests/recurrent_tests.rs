@@ -0,0 +1,139 @@
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+    use rustbrain::gradcheck::check_layer;
+    use rustbrain::math::{Matrix, Vector};
+    use rustbrain::neuralnetwork::{
+        pack_sequences, unpack_sequence, Adam, Bidirectional, Dense, Initializer, LSTMCell, Layer, Loss, Recurrent,
+        Sequential, GRU, LSTM, RNN,
+    };
+
+    const TOLERANCE: f64 = 1e-5;
+
+    /// Two sequences of four steps with three features each.
+    fn sequences() -> Matrix {
+        Matrix::new(vec![
+            vec![0.5, -0.3, 0.8, 0.1, 0.9, -0.7, -0.4, 0.2, 0.6, 0.3, -0.8, 0.5],
+            vec![-0.6, 0.4, 0.2, 0.7, -0.1, 0.3, 0.9, -0.5, -0.2, -0.3, 0.6, 0.8],
+        ])
+    }
+
+    #[test]
+    fn test_gradcheck_cells() {
+        let mut layers: Vec<Box<dyn Layer>> = vec![
+            Box::new(RNN::new(3, 4)),
+            Box::new(RNN::new(3, 4).with_return_sequences()),
+            Box::new(LSTM::new(3, 4)),
+            Box::new(LSTM::new(3, 4).with_return_sequences()),
+            Box::new(GRU::new(3, 4)),
+            Box::new(GRU::new(3, 4).with_return_sequences().reversed()),
+        ];
+        for layer in &mut layers {
+            let report = check_layer(layer.as_mut(), &sequences());
+            assert!(report.passed(TOLERANCE), "{}: {:?}", layer.name(), report.errors);
+        }
+    }
+
+    #[test]
+    fn test_gradcheck_bidirectional() {
+        let mut layers: Vec<Box<dyn Layer>> = vec![
+            Box::new(Bidirectional::new(LSTM::new(3, 2), LSTM::new(3, 3))),
+            Box::new(Bidirectional::new(GRU::new(3, 2).with_return_sequences(), GRU::new(3, 2).with_return_sequences())),
+        ];
+        for layer in &mut layers {
+            let report = check_layer(layer.as_mut(), &sequences());
+            assert_eq!(report.errors.len(), 7, "Input plus three tensors per direction");
+            assert!(report.passed(TOLERANCE), "{}: {:?}", layer.name(), report.errors);
+        }
+    }
+
+    #[test]
+    fn test_sequence_helpers_and_modes() {
+        let steps = vec![Vector::new(vec![1.0, 0.0, -1.0]), Vector::new(vec![0.5, 0.5, 0.5])];
+        let packed = pack_sequences(std::slice::from_ref(&steps));
+        assert_eq!(packed.col_count(), 6);
+        assert_eq!(unpack_sequence(&packed[0], 3), steps);
+
+        let mut lstm = LSTM::new(3, 4).with_return_sequences();
+        let outputs = lstm.forward_sequence(&steps);
+        assert_eq!(outputs.len(), 2);
+        assert!(outputs.iter().all(|h| h.len() == 4 && h.iter().all(|v| v.abs() < 1.0)));
+
+        // The last state equals the last element of the returned sequence
+        lstm.return_sequences = false;
+        assert_eq!(lstm.forward_sequence(&steps), vec![outputs[1].clone()]);
+
+        // A reversed layer reads the steps backwards but keeps outputs aligned
+        let mut reversed = LSTM::new(3, 4).with_return_sequences().reversed();
+        reversed.set_parameters(&lstm.parameters());
+        lstm.return_sequences = true;
+        let backwards = reversed.forward_sequence(&steps);
+        let flipped = lstm.forward_sequence(&[steps[1].clone(), steps[0].clone()]);
+        assert_eq!(backwards[0], flipped[1]);
+        assert_eq!(backwards[1], flipped[0]);
+    }
+
+    #[test]
+    fn test_truncated_bptt() {
+        let input = sequences();
+        let mut full = RNN::new(3, 4);
+        let mut truncated = RNN::new(3, 4).with_truncation(2);
+        truncated.set_parameters(&full.parameters());
+
+        let grad = Matrix::new(vec![vec![1.0; 4]; 2]);
+        full.forward(&input, true);
+        truncated.forward(&input, true);
+        let full_input = full.backward(&grad);
+        let truncated_input = truncated.backward(&grad);
+
+        // Only the last window (steps 2 and 3) receives gradient
+        for row in &truncated_input.rows {
+            assert!(row.data[..6].iter().all(|&g| g == 0.0));
+        }
+        assert_eq!(truncated_input[0].data[6..], full_input[0].data[6..]);
+        assert!(full_input[0].data[..6].iter().any(|&g| g != 0.0));
+        assert_ne!(full.gradients()[1], truncated.gradients()[1]);
+    }
+
+    #[test]
+    fn test_recurrent_shapes_in_sequential() {
+        let model = Sequential::builder(12)
+            .add(Bidirectional::new(LSTM::new(3, 4).with_return_sequences(), LSTM::new(3, 4).with_return_sequences()))
+            .add(GRU::new(8, 5))
+            .add(Dense::new(5, 2))
+            .build()
+            .unwrap();
+        assert_eq!(model.output_size(), 2);
+        assert!(model.summary().contains("GRU(8 -> 5) -> 5"), "{}", model.summary());
+
+        let error = Sequential::builder(10).add(RNN::new(3, 4)).build().err().unwrap();
+        assert!(error.contains("expects a multiple of 3 input features"), "{}", error);
+    }
+
+    #[test]
+    fn test_lstm_remembers_first_event() {
+        // The class is decided by the sign of the first step; later steps are distractors
+        let mut inputs = Vec::new();
+        let mut targets = Vec::new();
+        for i in 0..16 {
+            let first = if i % 2 == 0 { 1.0 } else { -1.0 };
+            let steps: Vec<Vector> = (0..5)
+                .map(|t| Vector::new(vec![if t == 0 { first } else { ((i * 5 + t) as f64 * 1.3).sin() * 0.5 }]))
+                .collect();
+            inputs.push(pack_sequences(&[steps])[0].clone());
+            targets.push(Vector::new(if first > 0.0 { vec![1.0, 0.0] } else { vec![0.0, 1.0] }));
+        }
+
+        let mut rng = StdRng::seed_from_u64(11);
+        let orthogonal = Initializer::Orthogonal { gain: 1.0 };
+        let cell = LSTMCell::with_initializer(1, 8, &Initializer::XavierUniform, &orthogonal, &mut rng);
+        let mut model = Sequential::builder(5)
+            .add(Recurrent::from_cell(cell))
+            .add(Dense::with_initializer(8, 2, &Initializer::XavierUniform, &Initializer::Zeros, &mut rng))
+            .build()
+            .unwrap();
+        let history = model.fit(&inputs, &targets, Loss::SoftmaxCrossEntropy, &mut Adam::new(0.05), 8, 100);
+        assert!(history.last().unwrap() < &0.1, "Final loss {}", history.last().unwrap());
+    }
+}
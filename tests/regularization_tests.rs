@@ -0,0 +1,143 @@
+#[cfg(test)]
+mod tests {
+    use rustbrain::math::{Matrix, Vector};
+    use rustbrain::neuralnetwork::{Dropout, NeuralNetwork, Regularizer};
+
+    fn xor_data() -> (Vec<Vector>, Vec<Vector>) {
+        let inputs = vec![
+            Vector::new(vec![0.0, 0.0]),
+            Vector::new(vec![0.0, 1.0]),
+            Vector::new(vec![1.0, 0.0]),
+            Vector::new(vec![1.0, 1.0]),
+        ];
+        let targets = vec![
+            Vector::new(vec![0.0]),
+            Vector::new(vec![1.0]),
+            Vector::new(vec![1.0]),
+            Vector::new(vec![0.0]),
+        ];
+        (inputs, targets)
+    }
+
+    fn non_bias_norm(nn: &NeuralNetwork) -> f64 {
+        nn.layers
+            .iter()
+            .flat_map(|layer| layer.weights.rows.iter())
+            .flat_map(|row| row.data.iter().skip(1))
+            .map(|w| w * w)
+            .sum::<f64>()
+            .sqrt()
+    }
+
+    #[test]
+    fn test_penalty_excludes_bias_column() {
+        let weights = Matrix::new(vec![vec![10.0, 1.0, -2.0], vec![-10.0, 3.0, 0.0]]);
+        let regularizer = Regularizer::new(0.5, 0.25);
+        // L1: 0.5 * (1 + 2 + 3) = 3.0, L2: 0.25 * (1 + 4 + 9) = 3.5
+        assert!((regularizer.penalty(&weights) - 6.5).abs() < 1e-12);
+        assert_eq!(Regularizer::none().penalty(&weights), 0.0);
+    }
+
+    #[test]
+    fn test_l1_gradient_keeps_zero_weights() {
+        let regularizer = Regularizer::new(0.5, 0.0);
+        assert_eq!(regularizer.gradient(0.0), 0.0);
+        assert_eq!(regularizer.gradient(-0.0), 0.0);
+        assert_eq!(regularizer.gradient(2.0), 0.5);
+        assert_eq!(regularizer.gradient(-1e-9), -0.5);
+    }
+
+    #[test]
+    fn test_l1_update_settles_at_zero() {
+        let regularizer = Regularizer::new(0.5, 0.0);
+        assert_eq!(regularizer.update(0.3, 0.0, 0.1), 0.25);
+        assert_eq!(regularizer.update(0.03, 0.0, 0.1), 0.0, "A step across zero stops at zero");
+        assert_eq!(regularizer.update(0.0, -1.0, 0.1), 0.05);
+
+        // Without a data gradient the penalty drives every weight to exactly zero and keeps it there
+        let mut nn = NeuralNetwork::new(&[3, 4, 2]).with_weight_decay(0.1, 0.01);
+        let zero_grads: Vec<Matrix> = nn.layers.iter().map(|layer| Matrix::zeros(layer.weights.row_count(), layer.weights.col_count())).collect();
+        let biases: Vec<Vector> = nn.layers.iter().map(|layer| layer.weights.get_column(0)).collect();
+        for _ in 0..200 {
+            nn.apply_gradients(&zero_grads, 0.5);
+        }
+        for (layer, bias) in nn.layers.iter().zip(&biases) {
+            assert!(layer.weights.rows.iter().all(|row| row.data[1..].iter().all(|&w| w == 0.0)));
+            assert_eq!(&layer.weights.get_column(0), bias);
+        }
+    }
+
+    #[test]
+    fn test_max_norm_rescales_rows() {
+        let mut weights = Matrix::new(vec![vec![5.0, 3.0, 4.0], vec![5.0, 0.3, 0.4]]);
+        let regularizer = Regularizer { max_norm: Some(1.0), ..Regularizer::none() };
+        regularizer.apply_max_norm(&mut weights);
+
+        assert_eq!(weights[(0, 0)], 5.0, "Bias must not be rescaled");
+        assert!((weights[(0, 1)] - 0.6).abs() < 1e-12);
+        assert!((weights[(0, 2)] - 0.8).abs() < 1e-12);
+        // Rows already inside the constraint are untouched
+        assert_eq!(weights[(1, 1)], 0.3);
+        assert_eq!(weights[(1, 2)], 0.4);
+    }
+
+    #[test]
+    fn test_dropout_mask_is_inverted() {
+        let dropout = Dropout::new(0.5);
+        let mask = dropout.mask(10_000);
+        assert!(mask.iter().all(|&m| m == 0.0 || m == 2.0));
+        let mean = mask.iter().sum::<f64>() / mask.len() as f64;
+        assert!((mean - 1.0).abs() < 0.05, "Mask should preserve the expected activation, got {}", mean);
+    }
+
+    #[test]
+    fn test_dropout_only_in_training_mode() {
+        let mut nn = NeuralNetwork::new(&[4, 64, 1]).with_dropout(0.5);
+        let input = Vector::new(vec![0.3, -0.2, 0.9, 0.1]);
+
+        // Evaluation mode is the default and is deterministic
+        assert_eq!(nn.forward(&input), nn.forward(&input));
+
+        nn.train_mode();
+        let hidden = &nn.forward(&input)[0];
+        assert!(hidden.iter().any(|&a| a == 0.0), "Training mode should drop some hidden units");
+
+        nn.eval_mode();
+        assert!(nn.forward(&input)[0].iter().all(|&a| a > 0.0));
+    }
+
+    #[test]
+    fn test_weight_decay_shrinks_weights() {
+        let (inputs, targets) = xor_data();
+
+        let mut plain = NeuralNetwork::new(&[2, 4, 1]);
+        let mut decayed = NeuralNetwork::new(&[2, 4, 1]).with_weight_decay(0.0, 0.01);
+        for (p, d) in plain.layers.iter().zip(decayed.layers.iter_mut()) {
+            d.weights = p.weights.clone();
+        }
+
+        plain.train(&inputs, &targets, 0.5, 2000);
+        decayed.train(&inputs, &targets, 0.5, 2000);
+
+        assert!(
+            non_bias_norm(&decayed) < non_bias_norm(&plain),
+            "L2 penalty should yield smaller weights ({} vs {})",
+            non_bias_norm(&decayed),
+            non_bias_norm(&plain)
+        );
+    }
+
+    #[test]
+    fn test_max_norm_holds_after_training() {
+        let (inputs, targets) = xor_data();
+        let mut nn = NeuralNetwork::new(&[2, 4, 1]).with_max_norm(1.5).with_dropout(0.2);
+        nn.train(&inputs, &targets, 0.5, 500);
+
+        for layer in &nn.layers {
+            for row in &layer.weights.rows {
+                let norm = row.data.iter().skip(1).map(|w| w * w).sum::<f64>().sqrt();
+                assert!(norm <= 1.5 + 1e-9, "Row norm {} exceeds max-norm", norm);
+            }
+        }
+    }
+}
@@ -0,0 +1,159 @@
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+    use rustbrain::linear_regression::{ElasticNet, Lasso, LinearRegression, Ridge};
+    use rustbrain::math::{Matrix, Vector};
+
+    /// 100 samples of 6 features where only features 0 and 2 matter:
+    /// `y = 1 + 3 x0 - 2 x2 + noise`.
+    fn sparse_problem(seed: u64) -> (Vec<Vector>, Vector) {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let inputs: Vec<Vector> =
+            (0..100).map(|_| Vector::new((0..6).map(|_| rng.random_range(-1.0..1.0)).collect())).collect();
+        let targets = Vector::new(
+            inputs.iter().map(|x| 1.0 + 3.0 * x[0] - 2.0 * x[2] + rng.random_range(-0.1..0.1)).collect(),
+        );
+        (inputs, targets)
+    }
+
+    #[test]
+    fn test_ridge_closed_form() {
+        let (inputs, targets) = sparse_problem(0);
+        let mut ridge = Ridge::new(0.0);
+        ridge.fit(&inputs, &targets).unwrap();
+        let mut ols = LinearRegression::new();
+        ols.fit(&inputs, &targets);
+        for j in 0..7 {
+            assert_relative_eq!(ridge.weights[j], ols.weights[j], epsilon = 1e-8);
+        }
+
+        // With a penalty: (XcᵀXc + nλI) w = Xcᵀ yc on centered data
+        let lambda = 0.5;
+        let mut ridge = Ridge::new(lambda);
+        ridge.fit(&inputs, &targets).unwrap();
+        let x_mean: Vec<f64> = (0..6).map(|j| inputs.iter().map(|x| x[j]).sum::<f64>() / 100.0).collect();
+        let y_mean = targets.iter().sum::<f64>() / 100.0;
+        let xc = Matrix::new(inputs.iter().map(|x| (0..6).map(|j| x[j] - x_mean[j]).collect()).collect());
+        let yc = Vector::new(targets.iter().map(|y| y - y_mean).collect());
+        let mut gram = xc.transpose().gemm(&xc);
+        for j in 0..6 {
+            gram[(j, j)] += 100.0 * lambda;
+        }
+        let expected = gram.inverse().gemv(&xc.transpose().gemv(&yc));
+        for j in 0..6 {
+            assert_relative_eq!(ridge.weights[j + 1], expected[j], epsilon = 1e-8);
+        }
+        let prediction = y_mean + (0..6).map(|j| expected[j] * (inputs[0][j] - x_mean[j])).sum::<f64>();
+        assert_relative_eq!(ridge.predict(&inputs[0]), prediction, epsilon = 1e-8);
+    }
+
+    #[test]
+    fn test_ridge_shrinkage_and_sgd() {
+        let (inputs, targets) = sparse_problem(1);
+        let norm = |lambda: f64| {
+            let mut ridge = Ridge::new(lambda);
+            ridge.fit(&inputs, &targets).unwrap();
+            Vector::new(ridge.weights.data[1..].to_vec()).norm()
+        };
+        assert!(norm(0.0) > norm(0.1) && norm(0.1) > norm(1.0) && norm(1.0) > norm(10.0));
+
+        let mut closed_form = Ridge::new(0.1);
+        closed_form.fit(&inputs, &targets).unwrap();
+        let mut sgd = Ridge::new(0.1);
+        sgd.fit_sgd(&inputs, &targets, 0.005, 300);
+        for j in 0..7 {
+            assert!((sgd.weights[j] - closed_form.weights[j]).abs() < 0.05, "{:?} vs {:?}", sgd.weights, closed_form.weights);
+        }
+    }
+
+    #[test]
+    fn test_lasso_exact_zeros() {
+        let (inputs, targets) = sparse_problem(2);
+        let mut lasso = Lasso::new(0.05);
+        lasso.fit(&inputs, &targets);
+        assert!(lasso.converged && lasso.n_iter < lasso.max_iter);
+        for j in [1, 3, 4, 5] {
+            assert_eq!(lasso.weights[j + 1], 0.0, "Coefficient {} is not exactly zero", j);
+        }
+        // The relevant coefficients are shrunk towards zero but kept
+        assert!(lasso.weights[1] > 2.5 && lasso.weights[1] < 3.0);
+        assert!(lasso.weights[3] < -1.5 && lasso.weights[3] > -2.0);
+        assert!((lasso.weights[0] - 1.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_lasso_single_feature_soft_thresholding() {
+        // With one feature the solution is S(xᵀy / n, α) / (xᵀx / n) on centered data
+        let inputs: Vec<Vector> = [-2.0, -1.0, 0.0, 1.0, 2.0].iter().map(|&x| Vector::new(vec![x])).collect();
+        let targets = Vector::new(vec![-3.0, -1.0, 1.0, 2.0, 6.0]);
+        let (xty, xtx) = (21.0 / 5.0, 10.0 / 5.0);
+        for alpha in [0.0, 1.0, 3.0, 5.0] {
+            let mut lasso = Lasso::new(alpha);
+            lasso.fit(&inputs, &targets);
+            let expected = (xty - alpha).max(0.0) / xtx;
+            assert_relative_eq!(lasso.weights[1], expected, epsilon = 1e-10);
+            assert_relative_eq!(lasso.weights[0], 1.0, epsilon = 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_elastic_net_limits() {
+        let (inputs, targets) = sparse_problem(3);
+        // l1_ratio = 0 is Ridge
+        let mut net = ElasticNet::new(0.3, 0.0).with_tolerance(1e-12).with_max_iter(10_000);
+        net.fit(&inputs, &targets);
+        let mut ridge = Ridge::new(0.3);
+        ridge.fit(&inputs, &targets).unwrap();
+        for j in 0..7 {
+            assert_relative_eq!(net.weights[j], ridge.weights[j], epsilon = 1e-8);
+        }
+        // l1_ratio = 1 is Lasso
+        let mut net = ElasticNet::new(0.05, 1.0);
+        net.fit(&inputs, &targets);
+        let mut lasso = Lasso::new(0.05);
+        lasso.fit(&inputs, &targets);
+        assert_eq!(net.weights, lasso.weights);
+        assert_eq!(ElasticNet::alpha_grid(&inputs, &targets, 1.0, 5, 0.01), Lasso::alpha_grid(&inputs, &targets, 5, 0.01));
+
+        // Above the largest alpha of the grid every coefficient is zero and
+        // the model predicts the mean
+        let alpha_max = ElasticNet::alpha_grid(&inputs, &targets, 0.5, 1, 0.01)[0];
+        let mut net = ElasticNet::new(alpha_max * 1.001, 0.5);
+        net.fit(&inputs, &targets);
+        assert!(net.weights.data[1..].iter().all(|&w| w == 0.0));
+        assert_relative_eq!(net.predict(&inputs[0]), targets.iter().sum::<f64>() / 100.0, epsilon = 1e-12);
+        let mut net = ElasticNet::new(alpha_max * 0.9, 0.5);
+        net.fit(&inputs, &targets);
+        assert!(net.weights.data[1..].iter().any(|&w| w != 0.0));
+    }
+
+    #[test]
+    fn test_regularization_path_with_warm_starts() {
+        let (inputs, targets) = sparse_problem(4);
+        let alphas = Lasso::alpha_grid(&inputs, &targets, 20, 1e-3);
+        assert_eq!(alphas.len(), 20);
+        assert!(alphas.windows(2).all(|a| a[1] < a[0]));
+
+        let mut lasso = Lasso::new(1.0).with_tolerance(1e-8);
+        let path = lasso.path(&inputs, &targets, &alphas);
+        let nonzero: Vec<usize> = path.iter().map(|w| w.data[1..].iter().filter(|&&c| c != 0.0).count()).collect();
+        assert_eq!(nonzero[0], 0);
+        // The two true features enter the model first
+        let first_two = nonzero.iter().position(|&k| k == 2).expect("No step with two features");
+        assert!(nonzero[..first_two].iter().all(|&k| k < 2));
+        assert!(path[first_two][1] != 0.0 && path[first_two][3] != 0.0);
+
+        // The end of the path approaches ordinary least squares
+        let mut ols = LinearRegression::new();
+        ols.fit(&inputs, &targets);
+        for (w, expected) in path[19].iter().zip(ols.weights.iter()) {
+            assert!((w - expected).abs() < 0.01);
+        }
+
+        // Refitting from a converged warm start takes a single pass
+        lasso.fit(&inputs, &targets);
+        assert_eq!(lasso.n_iter, 1);
+    }
+}
@@ -0,0 +1,121 @@
+#[cfg(test)]
+mod tests {
+    use rustbrain::linear_regression::LinearRegression;
+    use rustbrain::math::{Matrix, Vector};
+    use rustbrain::preprocessing::{MinMaxScaler, Norm, Normalizer, RobustScaler, StandardScaler};
+
+    fn assert_matrix_close(a: &Matrix, b: &Matrix, tol: f64) {
+        assert_eq!(a.row_count(), b.row_count());
+        for (x, y) in a.rows.iter().zip(&b.rows) {
+            for (u, v) in x.iter().zip(y.iter()) {
+                assert!((u - v).abs() < tol, "{:?} != {:?}", x.data, y.data);
+            }
+        }
+    }
+
+    fn assert_close(x: &Vector, expected: &[f64]) {
+        for (v, e) in x.iter().zip(expected) {
+            assert!((v - e).abs() < 1e-12, "{:?} != {:?}", x.data, expected);
+        }
+    }
+
+    fn data() -> Matrix {
+        Matrix::new(vec![vec![1.0, 10.0, 5.0], vec![2.0, 20.0, 5.0], vec![3.0, 30.0, 5.0], vec![4.0, 1000.0, 5.0]])
+    }
+
+    #[test]
+    fn test_standard_scaler() {
+        let mut scaler = StandardScaler::new();
+        let scaled = scaler.fit_transform(&data());
+        assert!((scaler.mean[0] - 2.5).abs() < 1e-12);
+        assert!((scaler.scale[0] - 1.25f64.sqrt()).abs() < 1e-12);
+        // The constant column is centered but not scaled
+        assert_eq!(scaler.scale[2], 1.0);
+        for j in 0..3 {
+            let column = scaled.get_column(j);
+            assert!(column.iter().sum::<f64>().abs() < 1e-9);
+            if j < 2 {
+                assert!((column.dot(&column) / 4.0 - 1.0).abs() < 1e-9);
+            }
+        }
+        assert_matrix_close(&scaler.inverse_transform(&scaled), &data(), 1e-9);
+
+        let mut unscaled = StandardScaler::new().with_std(false);
+        assert_eq!(unscaled.fit_transform(&data()).rows[0].data, vec![-1.5, -255.0, 0.0]);
+        let mut uncentered = StandardScaler::new().with_mean(false);
+        let scaled = uncentered.fit_transform(&data());
+        assert!((scaled[(0, 0)] - 1.0 / 1.25f64.sqrt()).abs() < 1e-12);
+        assert_matrix_close(&uncentered.inverse_transform(&scaled), &data(), 1e-9);
+    }
+
+    #[test]
+    fn test_min_max_scaler() {
+        let mut scaler = MinMaxScaler::new().with_feature_range(-1.0, 1.0);
+        let scaled = scaler.fit_transform(&data());
+        assert_close(&scaled.get_column(0), &[-1.0, -1.0 / 3.0, 1.0 / 3.0, 1.0]);
+        assert_eq!(scaled.get_column(2).data, vec![-1.0; 4]);
+        assert_matrix_close(&scaler.inverse_transform(&scaled), &data(), 1e-9);
+        // New data outside the fitted range is not clipped
+        let outside = scaler.transform(&Matrix::new(vec![vec![7.0, 10.0, 5.0]]));
+        assert!((outside[(0, 0)] - 3.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_robust_scaler_ignores_outlier() {
+        let mut scaler = RobustScaler::new();
+        let scaled = scaler.fit_transform(&data());
+        // Column 1: median 25, quartiles 17.5 and 272.5 with linear interpolation
+        assert_eq!(scaler.center.data, vec![2.5, 25.0, 5.0]);
+        assert_eq!(scaler.scale.data, vec![1.5, 255.0, 1.0]);
+        assert!((scaled[(2, 1)] - 5.0 / 255.0).abs() < 1e-12);
+        assert_matrix_close(&scaler.inverse_transform(&scaled), &data(), 1e-9);
+
+        let mut wide = RobustScaler::new().with_quantile_range(0.0, 100.0).with_centering(false);
+        wide.fit(&data());
+        assert_eq!(wide.scale.data, vec![3.0, 990.0, 1.0]);
+        assert_eq!(wide.transform(&data())[(3, 0)], 4.0 / 3.0);
+    }
+
+    #[test]
+    fn test_normalizer() {
+        let inputs = Matrix::new(vec![vec![3.0, -4.0], vec![0.0, 0.0]]);
+        assert_close(&Normalizer::new(Norm::L2).transform(&inputs).rows[0], &[0.6, -0.8]);
+        assert_close(&Normalizer::new(Norm::L1).transform(&inputs).rows[0], &[3.0 / 7.0, -4.0 / 7.0]);
+        assert_close(&Normalizer::new(Norm::Max).transform(&inputs).rows[0], &[0.75, -1.0]);
+        assert_eq!(Normalizer::new(Norm::L2).transform(&inputs).rows[1].data, vec![0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_scalers_serialize() {
+        let mut standard = StandardScaler::new();
+        standard.fit(&data());
+        let json = serde_json::to_string(&standard).unwrap();
+        let restored: StandardScaler = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, standard);
+        assert_eq!(restored.transform(&data()), standard.transform(&data()));
+
+        let mut robust = RobustScaler::new();
+        robust.fit(&data());
+        let restored: RobustScaler = serde_json::from_str(&serde_json::to_string(&robust).unwrap()).unwrap();
+        assert_eq!(restored, robust);
+
+        let normalizer = Normalizer::new(Norm::Max);
+        let restored: Normalizer = serde_json::from_str(&serde_json::to_string(&normalizer).unwrap()).unwrap();
+        assert_eq!(restored, normalizer);
+    }
+
+    #[test]
+    fn test_sgd_converges_on_standardized_features() {
+        // Features on very different scales make plain SGD diverge at this learning rate
+        let inputs = Matrix::new((0..50).map(|i| vec![i as f64 * 100.0, (i % 7) as f64 * 0.01]).collect());
+        let targets = Vector::new(inputs.rows.iter().map(|x| 3.0 + 0.02 * x[0] - 50.0 * x[1]).collect());
+        let mut scaler = StandardScaler::new();
+        let scaled = scaler.fit_transform(&inputs);
+
+        let mut model = LinearRegression::new();
+        model.fit_sgd(&scaled.rows, &targets, 0.01, 300);
+        for (x, y) in scaled.rows.iter().zip(targets.iter()) {
+            assert!((model.predict(x) - y).abs() < 1e-3, "{} != {}", model.predict(x), y);
+        }
+    }
+}
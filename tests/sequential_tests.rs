@@ -0,0 +1,206 @@
+#[cfg(test)]
+mod tests {
+    use rustbrain::math::{Matrix, Vector};
+    use rustbrain::neuralnetwork::{
+        Adam, BatchNorm, Dense, Dropout, Layer, Loss, Optimizer, ReLU, Sequential, Sigmoid, Tanh, SGD,
+    };
+
+    fn xor_data() -> (Vec<Vector>, Vec<Vector>) {
+        let inputs = vec![
+            Vector::new(vec![0.0, 0.0]),
+            Vector::new(vec![0.0, 1.0]),
+            Vector::new(vec![1.0, 0.0]),
+            Vector::new(vec![1.0, 1.0]),
+        ];
+        let targets = vec![
+            Vector::new(vec![0.0]),
+            Vector::new(vec![1.0]),
+            Vector::new(vec![1.0]),
+            Vector::new(vec![0.0]),
+        ];
+        (inputs, targets)
+    }
+
+    /// A user-defined layer that multiplies its input by a learnable scalar.
+    struct Scale {
+        factor: Matrix,
+        grad: Matrix,
+        input: Option<Matrix>,
+    }
+
+    impl Layer for Scale {
+        fn name(&self) -> String {
+            "Scale".to_string()
+        }
+
+        fn output_size(&self, input_size: usize) -> Result<usize, String> {
+            Ok(input_size)
+        }
+
+        fn forward(&mut self, input: &Matrix, _training: bool) -> Matrix {
+            self.input = Some(input.clone());
+            input.map(|x| x * self.factor[(0, 0)])
+        }
+
+        fn backward(&mut self, grad_output: &Matrix) -> Matrix {
+            let input = self.input.as_ref().unwrap();
+            self.grad[(0, 0)] = input.hadamard(grad_output).column_sums().iter().sum();
+            grad_output.map(|g| g * self.factor[(0, 0)])
+        }
+
+        fn parameters(&self) -> Vec<Matrix> {
+            vec![self.factor.clone()]
+        }
+
+        fn gradients(&self) -> Vec<Matrix> {
+            vec![self.grad.clone()]
+        }
+
+        fn set_parameters(&mut self, parameters: &[Matrix]) {
+            self.factor = parameters[0].clone();
+        }
+    }
+
+    #[test]
+    fn test_shape_mismatch_is_reported() {
+        let result = Sequential::builder(784)
+            .add(Dense::new(784, 128))
+            .add(ReLU::new())
+            .add(Dense::new(64, 10))
+            .build();
+        let error = result.err().expect("Mismatched shapes must be rejected");
+        assert_eq!(error, "Layer 2 (Dense(64 -> 10)) expects 64 input features but receives 128");
+
+        let error = Sequential::builder(3).add(BatchNorm::new(4)).build().err().unwrap();
+        assert!(error.contains("BatchNorm(4)"), "{}", error);
+        assert!(Sequential::builder(3).build().is_err());
+    }
+
+    #[test]
+    fn test_summary_and_output_size() {
+        let model = Sequential::builder(4)
+            .add(Dense::new(4, 8))
+            .add(ReLU::new())
+            .add(Dropout::new(0.2))
+            .add(Dense::new(8, 3))
+            .build()
+            .unwrap();
+        assert_eq!(model.output_size(), 3);
+        let summary = model.summary();
+        assert!(summary.contains("Dense(4 -> 8) -> 8 (40 parameters)"), "{}", summary);
+        assert!(summary.contains("Dropout(0.2) -> 8 (0 parameters)"), "{}", summary);
+    }
+
+    #[test]
+    fn test_sequential_learns_xor() {
+        let (inputs, targets) = xor_data();
+        let mut model = Sequential::builder(2)
+            .add(Dense::new(2, 8))
+            .add(Tanh::new())
+            .add(Dense::new(8, 1))
+            .add(Sigmoid::new())
+            .build()
+            .unwrap();
+
+        let history = model.fit(&inputs, &targets, Loss::BinaryCrossEntropy, &mut Adam::new(0.05), 4, 500);
+        assert!(history.last().unwrap() < &history[0]);
+
+        for (x, t) in inputs.iter().zip(&targets) {
+            let output = model.predict(x);
+            assert!((output[0] - t[0]).abs() < 0.2, "For input {:?} got {:?}", x.data, output.data);
+        }
+    }
+
+    #[test]
+    fn test_softmax_cross_entropy_classification() {
+        let inputs = vec![
+            Vector::new(vec![1.0, 0.0]),
+            Vector::new(vec![0.0, 1.0]),
+            Vector::new(vec![-1.0, 0.0]),
+            Vector::new(vec![0.0, -1.0]),
+        ];
+        let targets: Vec<Vector> = (0..4)
+            .map(|c| Vector::new((0..4).map(|k| if k == c { 1.0 } else { 0.0 }).collect()))
+            .collect();
+        let mut model = Sequential::builder(2)
+            .add(Dense::new(2, 16))
+            .add(ReLU::new())
+            .add(Dense::new(16, 4))
+            .build()
+            .unwrap();
+
+        model.fit(&inputs, &targets, Loss::SoftmaxCrossEntropy, &mut SGD::with_momentum(0.1, 0.9), 4, 300);
+
+        for (c, x) in inputs.iter().enumerate() {
+            let logits = model.predict(x);
+            let predicted = logits.iter().enumerate().max_by(|a, b| a.1.partial_cmp(b.1).unwrap()).unwrap().0;
+            assert_eq!(predicted, c);
+        }
+    }
+
+    #[test]
+    fn test_custom_layer_plugs_into_training_loop() {
+        // Learn y = 3x with a single custom layer
+        let inputs: Vec<Vector> = (1..=5).map(|i| Vector::new(vec![i as f64 / 5.0])).collect();
+        let targets: Vec<Vector> = inputs.iter().map(|x| x.scale(3.0)).collect();
+        let scale = Scale { factor: Matrix::new(vec![vec![0.5]]), grad: Matrix::zeros(1, 1), input: None };
+        let mut model = Sequential::builder(1).add(scale).build().unwrap();
+
+        model.fit(&inputs, &targets, Loss::MeanSquaredError, &mut SGD::new(0.1), 5, 200);
+        assert!((model.layers[0].parameters()[0][(0, 0)] - 3.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_dropout_is_identity_in_inference() {
+        let mut model = Sequential::builder(3).add(Dropout::new(0.9)).build().unwrap();
+        let input = Matrix::new(vec![vec![1.0, 2.0, 3.0]]);
+        assert_eq!(model.forward(&input, false), input);
+    }
+
+    #[test]
+    fn test_loss_values_and_gradients() {
+        let output = Matrix::new(vec![vec![0.5, 1.0], vec![0.0, -1.0]]);
+        let target = Matrix::new(vec![vec![1.0, 1.0], vec![0.0, 0.0]]);
+        let (loss, grad) = Loss::MeanSquaredError.compute(&output, &target);
+        assert!((loss - 0.625).abs() < 1e-12);
+        assert_eq!(grad, Matrix::new(vec![vec![-0.5, 0.0], vec![0.0, -1.0]]));
+
+        // Equal logits: probability 1/2 for each class
+        let (loss, grad) = Loss::SoftmaxCrossEntropy.compute(&Matrix::new(vec![vec![2.0, 2.0]]), &Matrix::new(vec![vec![0.0, 1.0]]));
+        assert!((loss - 2.0_f64.ln()).abs() < 1e-12);
+        assert_eq!(grad, Matrix::new(vec![vec![0.5, -0.5]]));
+
+        let (loss, grad) = Loss::BinaryCrossEntropy.compute(&Matrix::new(vec![vec![0.8]]), &Matrix::new(vec![vec![1.0]]));
+        assert!((loss + 0.8_f64.ln()).abs() < 1e-12);
+        assert!((grad[(0, 0)] + 1.25).abs() < 1e-12);
+
+        // Two time steps of two classes average like two separate rows
+        let (sequence_loss, sequence_grad) = Loss::SequenceSoftmaxCrossEntropy { classes: 2 }.compute(&Matrix::new(vec![vec![2.0, 2.0, 0.0, 3.0]]), &Matrix::new(vec![vec![0.0, 1.0, 0.0, 1.0]]));
+        let (loss, grad) = Loss::SoftmaxCrossEntropy.compute(&Matrix::new(vec![vec![2.0, 2.0], vec![0.0, 3.0]]), &Matrix::new(vec![vec![0.0, 1.0], vec![0.0, 1.0]]));
+        assert!((sequence_loss - loss).abs() < 1e-12);
+        assert_eq!(sequence_grad.rows[0].data, grad.rows.iter().flat_map(|row| row.data.clone()).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_optimizer_steps() {
+        let gradients = [Matrix::new(vec![vec![1.0, -2.0]])];
+
+        // Momentum adds the previous step to the current one
+        let mut sgd = SGD::with_momentum(0.1, 0.5);
+        let mut parameters = [Matrix::zeros(1, 2)];
+        sgd.step(0, &mut parameters, &gradients);
+        sgd.step(0, &mut parameters, &gradients);
+        assert!((parameters[0][(0, 0)] + 0.25).abs() < 1e-12 && (parameters[0][(0, 1)] - 0.5).abs() < 1e-12);
+
+        // Adam's first step moves every parameter by about the learning rate
+        let mut adam = Adam::new(0.01);
+        let mut parameters = [Matrix::zeros(1, 2)];
+        adam.step(0, &mut parameters, &gradients);
+        assert!((parameters[0][(0, 0)] + 0.01).abs() < 1e-8 && (parameters[0][(0, 1)] - 0.01).abs() < 1e-8);
+
+        // Each key keeps its own moments, so a new layer starts fresh
+        let mut other = [Matrix::zeros(1, 2)];
+        adam.step(1, &mut other, &gradients);
+        assert_eq!(other, parameters);
+    }
+}